@@ -0,0 +1,100 @@
+/// Statuses of the `_bulk` response items which are worth sending again.
+pub fn is_retryable_status(status: u16) -> bool {
+    status == 429 || status >= 500
+}
+
+pub struct BulkItemError {
+    pub index: usize,
+    pub status: u16,
+    pub reason: String,
+}
+
+/// Parses the `_bulk` response and returns the failed items in request order.
+pub fn parse_bulk_response(body: &[u8]) -> Result<Vec<BulkItemError>, String> {
+    let response: serde_json::Value = serde_json::from_slice(body)
+        .map_err(|err| format!("Can not parse bulk response. Err: {}", err))?;
+
+    let mut result = Vec::new();
+
+    if !response["errors"].as_bool().unwrap_or(false) {
+        return Ok(result);
+    }
+
+    let items = match response["items"].as_array() {
+        Some(items) => items,
+        None => return Err("There is no items in bulk response".to_string()),
+    };
+
+    for (index, item) in items.iter().enumerate() {
+        let item = match item.as_object().and_then(|itm| itm.values().next()) {
+            Some(item) => item,
+            None => continue,
+        };
+
+        let status = item["status"].as_u64().unwrap_or(0) as u16;
+        if (200..300).contains(&status) {
+            continue;
+        }
+
+        result.push(BulkItemError {
+            index,
+            status,
+            reason: item["error"]["reason"]
+                .as_str()
+                .unwrap_or_default()
+                .to_string(),
+        });
+    }
+
+    Ok(result)
+}
+
+/// Every bulk item is two lines: the action and the document.
+pub fn split_bulk_items(chunk: &[u8]) -> Vec<&[u8]> {
+    let mut result = Vec::new();
+    let mut item_start = 0;
+    let mut lines_in_item = 0;
+
+    for (index, b) in chunk.iter().enumerate() {
+        if *b == b'\n' {
+            lines_in_item += 1;
+            if lines_in_item == 2 {
+                result.push(&chunk[item_start..=index]);
+                item_start = index + 1;
+                lines_in_item = 0;
+            }
+        }
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn test_parse_bulk_response() {
+        let body = r#"{"took":3,"errors":true,"items":[
+            {"create":{"_index":"logs","status":201}},
+            {"create":{"_index":"logs","status":429,"error":{"type":"es_rejected_execution_exception","reason":"queue is full"}}},
+            {"create":{"_index":"logs","status":400,"error":{"type":"mapper_parsing_exception","reason":"bad field"}}}
+        ]}"#;
+
+        let result = super::parse_bulk_response(body.as_bytes()).unwrap();
+
+        assert_eq!(2, result.len());
+        assert_eq!(1, result[0].index);
+        assert!(super::is_retryable_status(result[0].status));
+        assert_eq!(2, result[1].index);
+        assert!(!super::is_retryable_status(result[1].status));
+        assert_eq!("bad field", result[1].reason);
+    }
+
+    #[test]
+    fn test_split_bulk_items() {
+        let chunk = b"{\"create\":{}}\n{\"a\":1}\n{\"create\":{}}\n{\"a\":2}\n";
+        let items = super::split_bulk_items(chunk);
+
+        assert_eq!(2, items.len());
+        assert_eq!(b"{\"create\":{}}\n{\"a\":2}\n", items[1]);
+    }
+}
@@ -0,0 +1,117 @@
+use std::{sync::Arc, time::Duration};
+
+use crate::{ElasticLoggerInner, ElasticLoggerSettings, ElasticSettings};
+use my_logger_core::{MyLogEvent, MyLoggerReader};
+use rust_extensions::{events_loop::EventsLoop, AppStates};
+
+pub struct ElasticLogger {
+    inner: Arc<ElasticLoggerInner>,
+    events_loop: EventsLoop<()>,
+    app_states: Arc<AppStates>,
+}
+
+impl ElasticLogger {
+    pub async fn enable_from_connection_string(
+        settings: Arc<dyn ElasticSettings + Send + Sync + 'static>,
+    ) {
+        let mut inner = ElasticLoggerInner::new(settings.clone());
+        let settings = ElasticLoggerSettings::read(&settings).await;
+
+        if let Some(queue_size) = settings.queue_size {
+            inner.configure(queue_size)
+        }
+
+        let result = Self {
+            app_states: AppStates::create_initialized().into(),
+            inner: Arc::new(inner),
+            events_loop: EventsLoop::new("ElasticLogger".to_string())
+                .set_iteration_timeout(settings.timeout),
+        };
+
+        result.events_loop.register_event_loop(result.inner.clone());
+
+        result
+            .events_loop
+            .start(result.app_states.clone(), my_logger_core::LOGGER.clone());
+
+        let elastic_logger = Arc::new(result);
+        my_seq_logger::start_flush_timer(
+            Arc::downgrade(&elastic_logger),
+            settings.flush_delay,
+            |logger| logger.events_loop.send(()),
+        );
+        my_logger_core::LOGGER.plug_reader(elastic_logger.clone());
+    }
+}
+
+impl MyLoggerReader for ElasticLogger {
+    fn write_log(&self, log_event: Arc<MyLogEvent>) {
+        self.inner.log_events.enqueue(log_event);
+    }
+
+    fn flush(&self, timeout: Duration) {
+        let inner = self.inner.clone();
+        my_seq_logger::flush_on_private_runtime("Elasticsearch", timeout, async move {
+            inner.upload_queued_events().await
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{sync::Arc, time::Duration};
+
+    use my_logger_core::{test_utils::create_test_event, LogLevel, MyLoggerReader};
+    use my_seq_logger::LogsChunkUploader;
+    use parking_lot::Mutex;
+    use rust_extensions::{events_loop::EventsLoop, AppStates};
+
+    use crate::{ElasticLoggerInner, ElasticSettings};
+
+    use super::ElasticLogger;
+
+    struct TestSettings;
+
+    #[async_trait::async_trait]
+    impl ElasticSettings for TestSettings {
+        async fn get_conn_string(&self) -> String {
+            "url=http://localhost:9200".to_string()
+        }
+    }
+
+    #[derive(Default)]
+    struct MockUploader {
+        chunks: Mutex<Vec<String>>,
+    }
+
+    #[async_trait::async_trait]
+    impl LogsChunkUploader for MockUploader {
+        async fn upload_chunk(&self, chunk: Vec<u8>) {
+            self.chunks.lock().push(String::from_utf8(chunk).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_flush_uploads_queued_events() {
+        let uploader = Arc::new(MockUploader::default());
+        let mut inner = ElasticLoggerInner::new(Arc::new(TestSettings));
+        inner.custom_uploader = Some(uploader.clone());
+
+        let elastic_logger = ElasticLogger {
+            inner: Arc::new(inner),
+            events_loop: EventsLoop::new("ElasticLogger".to_string()),
+            app_states: AppStates::create_initialized().into(),
+        };
+
+        elastic_logger.write_log(Arc::new(create_test_event(
+            LogLevel::Info,
+            "Orders",
+            "Created",
+        )));
+        elastic_logger.flush(Duration::from_secs(5));
+
+        let chunks = uploader.chunks.lock();
+        assert_eq!(1, chunks.len());
+        assert!(chunks[0].contains("Created"));
+    }
+}
@@ -0,0 +1,98 @@
+use std::sync::Arc;
+
+use my_seq_logger::{LogEventsQueue, LogsChunkUploader};
+use parking_lot::Mutex;
+use rust_extensions::events_loop::EventsLoopTick;
+
+use crate::{ElasticLoggerSettings, ElasticSettings, ElasticUploader, IndexPattern};
+
+pub struct ElasticLoggerInner {
+    pub(crate) log_events: LogEventsQueue,
+    settings: Arc<dyn ElasticSettings + Send + Sync + 'static>,
+    /// Replaces the HTTP uploader built from settings.
+    pub(crate) custom_uploader: Option<Arc<dyn LogsChunkUploader + Send + Sync + 'static>>,
+    cached_uploader: Mutex<Option<Arc<ElasticUploader>>>,
+    /// Held while a batch is uploaded, so a flush waits for the batch which is already in flight.
+    upload_lock: tokio::sync::Mutex<()>,
+}
+
+impl ElasticLoggerInner {
+    pub fn new(settings: Arc<dyn ElasticSettings + Send + Sync + 'static>) -> Self {
+        Self {
+            log_events: LogEventsQueue::new(),
+            settings,
+            custom_uploader: None,
+            cached_uploader: Mutex::new(None),
+            upload_lock: tokio::sync::Mutex::new(()),
+        }
+    }
+
+    pub fn configure(&mut self, queue_size: usize) {
+        self.log_events.configure_size(queue_size);
+    }
+
+    async fn get_uploader(
+        &self,
+    ) -> (
+        Arc<dyn LogsChunkUploader + Send + Sync + 'static>,
+        IndexPattern,
+    ) {
+        let settings = ElasticLoggerSettings::read(&self.settings).await;
+
+        if let Some(custom_uploader) = self.custom_uploader.as_ref() {
+            return (custom_uploader.clone(), settings.index_pattern);
+        }
+
+        let uploader = ElasticUploader::new(
+            settings.url,
+            settings.api_key,
+            settings.user,
+            settings.timeout,
+        );
+
+        let mut cached = self.cached_uploader.lock();
+        if let Some(existing) = cached.as_ref() {
+            if existing.matches(&uploader) {
+                return (existing.clone(), settings.index_pattern);
+            }
+        }
+
+        let uploader = Arc::new(uploader);
+        *cached = Some(uploader.clone());
+        (uploader, settings.index_pattern)
+    }
+
+    pub async fn upload_queued_events(&self) {
+        let _upload_lock = self.upload_lock.lock().await;
+
+        let events = match self.log_events.dequeue() {
+            Some(events) => events,
+            None => return,
+        };
+
+        let (uploader, index_pattern) = self.get_uploader().await;
+        let populated_params = my_logger_core::LOGGER.get_populated_params();
+
+        my_seq_logger::upload_serialized_log_events(
+            uploader.as_ref(),
+            events.as_slice(),
+            b"\n",
+            true,
+            |payload, log_event| {
+                crate::serialize_bulk_item(payload, log_event, &populated_params, &index_pattern)
+            },
+        )
+        .await;
+    }
+}
+
+#[async_trait::async_trait]
+impl EventsLoopTick<()> for ElasticLoggerInner {
+    async fn started(&self) {
+        println!("Elastic Logs writer is started");
+    }
+    async fn tick(&self, _: ()) {
+        self.upload_queued_events().await;
+    }
+    async fn finished(&self) {}
+}
@@ -0,0 +1,171 @@
+use my_logger_core::{MyLogEvent, PopulatedParams};
+
+const UNKNOWN_VALUE: &str = "unknown";
+
+#[derive(Debug, Clone)]
+enum IndexPatternToken {
+    Text(String),
+    Date(String),
+    Param(String),
+}
+
+/// Index name pattern such as `logs-{Application}-{yyyy.MM.dd}`.
+///
+/// `{...}` containing only `yyyy`, `MM`, `dd`, `HH` and separators is formatted from the event time (UTC),
+/// `{Process}` is the event process, anything else is looked up in populated params.
+/// The result is lowercased since index names can't contain uppercase characters.
+#[derive(Debug, Clone)]
+pub struct IndexPattern {
+    tokens: Vec<IndexPatternToken>,
+}
+
+impl IndexPattern {
+    pub fn parse(src: &str) -> Result<Self, String> {
+        let mut tokens = Vec::new();
+        let mut rest = src;
+
+        while let Some(start) = rest.find('{') {
+            if start > 0 {
+                tokens.push(IndexPatternToken::Text(rest[..start].to_string()));
+            }
+
+            let end = match rest[start..].find('}') {
+                Some(end) => start + end,
+                None => return Err(format!("Invalid index pattern {}. '}}' is missing", src)),
+            };
+
+            let placeholder = &rest[start + 1..end];
+            if is_date_format(placeholder) {
+                tokens.push(IndexPatternToken::Date(placeholder.to_string()));
+            } else {
+                tokens.push(IndexPatternToken::Param(placeholder.to_string()));
+            }
+
+            rest = &rest[end + 1..];
+        }
+
+        if !rest.is_empty() {
+            tokens.push(IndexPatternToken::Text(rest.to_string()));
+        }
+
+        Ok(Self { tokens })
+    }
+
+    pub fn render(&self, log_event: &MyLogEvent, populated_params: &PopulatedParams) -> String {
+        let mut result = String::new();
+        let mut rfc3339 = None;
+
+        for token in &self.tokens {
+            match token {
+                IndexPatternToken::Text(text) => result.push_str(text),
+                IndexPatternToken::Date(format) => {
+                    let rfc3339 = rfc3339.get_or_insert_with(|| log_event.dt.to_rfc3339());
+                    format_date(&mut result, format, rfc3339);
+                }
+                IndexPatternToken::Param(name) => {
+                    let value = if name == "Process" {
+                        Some(log_event.process.as_str())
+                    } else {
+                        populated_params.get(name)
+                    };
+
+                    push_index_name_part(&mut result, value.unwrap_or(UNKNOWN_VALUE));
+                }
+            }
+        }
+
+        result.to_lowercase()
+    }
+}
+
+fn is_date_format(src: &str) -> bool {
+    let mut rest = src;
+    let mut has_part = false;
+
+    while !rest.is_empty() {
+        if let Some(tail) = rest
+            .strip_prefix("yyyy")
+            .or_else(|| rest.strip_prefix("MM"))
+            .or_else(|| rest.strip_prefix("dd"))
+            .or_else(|| rest.strip_prefix("HH"))
+        {
+            has_part = true;
+            rest = tail;
+            continue;
+        }
+
+        if rest.starts_with(['.', '-', '_']) {
+            rest = &rest[1..];
+            continue;
+        }
+
+        return false;
+    }
+
+    has_part
+}
+
+fn format_date(out: &mut String, format: &str, rfc3339: &str) {
+    let mut rest = format;
+
+    while !rest.is_empty() {
+        let (part, len) = if rest.starts_with("yyyy") {
+            (rfc3339.get(0..4), 4)
+        } else if rest.starts_with("MM") {
+            (rfc3339.get(5..7), 2)
+        } else if rest.starts_with("dd") {
+            (rfc3339.get(8..10), 2)
+        } else if rest.starts_with("HH") {
+            (rfc3339.get(11..13), 2)
+        } else {
+            out.push_str(&rest[..1]);
+            rest = &rest[1..];
+            continue;
+        };
+
+        out.push_str(part.unwrap_or_default());
+        rest = &rest[len..];
+    }
+}
+
+/// Characters which are not allowed in index names are replaced with '_'.
+fn push_index_name_part(out: &mut String, value: &str) {
+    for c in value.chars() {
+        if matches!(
+            c,
+            '\\' | '/' | '*' | '?' | '"' | '<' | '>' | '|' | ' ' | ',' | '#' | ':'
+        ) {
+            out.push('_');
+        } else {
+            out.push(c);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use my_logger_core::{test_utils::create_test_event, LogLevel, PopulatedParams};
+    use rust_extensions::date_time::DateTimeAsMicroseconds;
+
+    use super::IndexPattern;
+
+    #[test]
+    fn test_render() {
+        let pattern = IndexPattern::parse("logs-{Application}-{Process}-{yyyy.MM.dd}").unwrap();
+
+        let mut log_event = create_test_event(LogLevel::Info, "Price Feed", "Test");
+        log_event.dt = DateTimeAsMicroseconds::new(1_700_000_000_000_000);
+
+        let populated_params = PopulatedParams::new(vec![("Application", "Pricing".into())]);
+
+        assert_eq!(
+            "logs-pricing-price_feed-2023.11.14",
+            pattern.render(&log_event, &populated_params)
+        );
+    }
+
+    #[test]
+    fn test_missing_brace() {
+        assert!(IndexPattern::parse("logs-{Application").is_err());
+    }
+}
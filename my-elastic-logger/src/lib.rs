@@ -0,0 +1,14 @@
+mod elastic_logger;
+pub use elastic_logger::ElasticLogger;
+mod elastic_logger_inner;
+pub use elastic_logger_inner::*;
+mod settings;
+pub use settings::*;
+mod index_pattern;
+pub use index_pattern::*;
+mod serializer;
+pub use serializer::*;
+mod uploader;
+pub use uploader::*;
+mod bulk_response;
+pub use bulk_response::*;
@@ -0,0 +1,48 @@
+use my_json::json_writer::{JsonObjectWriter, RawJsonObject};
+use my_logger_core::{MyLogEvent, PopulatedParams};
+use my_seq_logger::{format_seq_string, format_value};
+
+use crate::IndexPattern;
+
+/// Serializes one `_bulk` item: the `create` action line and the document line.
+/// `create` works for both regular indices and data streams.
+pub fn serialize_bulk_item(
+    mut compile_buffer: String,
+    log_event: &MyLogEvent,
+    populated_params: &PopulatedParams,
+    index_pattern: &IndexPattern,
+) -> String {
+    compile_buffer.clear();
+
+    let index = index_pattern.render(log_event, populated_params);
+    let action = JsonObjectWriter::from_string(String::new())
+        .write("_index", index.as_str())
+        .build();
+    let action = JsonObjectWriter::from_string(compile_buffer)
+        .write("create", RawJsonObject::AsStr(action.as_str()))
+        .build();
+
+    let mut document = JsonObjectWriter::from_string(String::new())
+        .write("@timestamp", log_event.dt.to_rfc3339())
+        .write("level", log_event.level.as_str())
+        .write("process", format_seq_string(log_event.process.as_str()).as_str())
+        .write("message", format_seq_string(log_event.message.as_str()).as_str());
+
+    for (key, value) in populated_params.iter() {
+        document = document.write(key, format_seq_string(value).as_str());
+    }
+
+    if let Some(ctx) = &log_event.context {
+        let mut ctx_writer = JsonObjectWriter::from_string(String::new());
+        for (key, value) in ctx {
+            ctx_writer = ctx_writer.write(key, format_value(value).as_str());
+        }
+        let ctx = ctx_writer.build();
+        document = document.write("context", RawJsonObject::AsStr(ctx.as_str()));
+    }
+
+    let mut result = action;
+    result.push('\n');
+    result.push_str(document.build().as_str());
+    result
+}
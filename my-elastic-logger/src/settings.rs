@@ -0,0 +1,110 @@
+use std::{sync::Arc, time::Duration};
+
+use my_seq_logger::{parse_number, split_key_value};
+
+use crate::IndexPattern;
+
+#[async_trait::async_trait]
+pub trait ElasticSettings {
+    async fn get_conn_string(&self) -> String;
+}
+
+const DEFAULT_FLUSH_SLEEP: u64 = 1;
+const DEFAULT_TIMEOUT: u64 = 10;
+const DEFAULT_INDEX_PATTERN: &str = "logs-{Application}-{yyyy.MM.dd}";
+
+pub struct ElasticLoggerSettings {
+    pub url: String,
+    pub index_pattern: IndexPattern,
+    pub api_key: Option<String>,
+    pub user: Option<(String, String)>,
+    /// Interval at which queued events are uploaded.
+    pub flush_delay: Duration,
+    pub queue_size: Option<usize>,
+    pub timeout: Duration,
+}
+
+impl ElasticLoggerSettings {
+    pub async fn read(settings: &Arc<dyn ElasticSettings + Send + Sync + 'static>) -> Self {
+        loop {
+            let conn_string = settings.get_conn_string().await;
+            let settings = ElasticLoggerSettings::try_parse(conn_string.as_str());
+
+            match settings {
+                Ok(result) => return result,
+                Err(err) => {
+                    eprintln!("Can not parse Elastic Logs settings. Err: {:?}", err);
+                    tokio::time::sleep(Duration::from_secs(5)).await;
+                }
+            };
+        }
+    }
+
+    /// `url=http://elastic:9200;index=logs-{Application}-{yyyy.MM.dd};apikey=xxx`
+    /// or `user=elastic;password=xxx` for basic authentication.
+    pub fn try_parse(conn_string: &str) -> Result<Self, String> {
+        let mut url = None;
+        let mut index_pattern = DEFAULT_INDEX_PATTERN;
+        let mut api_key = None;
+        let mut user = None;
+        let mut password = None;
+        let mut flush_delay = DEFAULT_FLUSH_SLEEP;
+        let mut queue_size = None;
+        let mut timeout = DEFAULT_TIMEOUT;
+
+        for item in conn_string.split(';') {
+            if item.is_empty() {
+                continue;
+            }
+
+            let (key, value) = split_key_value(item, "elastic connection string")?;
+
+            match key {
+                "url" => {
+                    url = Some(value);
+                }
+                "index" => {
+                    index_pattern = value;
+                }
+                "apikey" => {
+                    api_key = Some(value.to_string());
+                }
+                "user" => {
+                    user = Some(value.to_string());
+                }
+                "password" => {
+                    password = Some(value.to_string());
+                }
+                "flushdelay" => {
+                    flush_delay = parse_number(key, value)?;
+                }
+                "queuesize" => {
+                    queue_size = Some(parse_number(key, value)?);
+                }
+                "timeout" => {
+                    timeout = parse_number(key, value)?;
+                }
+                _ => {
+                    return Err(format!("Invalid key {} of elastic connection string", key));
+                }
+            }
+        }
+
+        let url = match url {
+            Some(url) => url,
+            None => {
+                return Err("There is no URL parameter in elastic connection string".to_string())
+            }
+        };
+
+        Ok(Self {
+            url: url.to_string(),
+            index_pattern: IndexPattern::parse(index_pattern)?,
+            api_key,
+            user: user.map(|user| (user, password.unwrap_or_default())),
+            flush_delay: Duration::from_secs(flush_delay),
+            queue_size,
+            timeout: Duration::from_secs(timeout),
+        })
+    }
+}
@@ -0,0 +1,163 @@
+use std::time::Duration;
+
+use base64::Engine;
+use flurl::{body::FlUrlBody, *};
+use my_seq_logger::LogsChunkUploader;
+
+pub struct ElasticUploader {
+    pub url: String,
+    pub authorization: Option<String>,
+    pub elastic_debug: bool,
+    pub timeout: Duration,
+}
+
+impl ElasticUploader {
+    pub fn new(
+        url: String,
+        api_key: Option<String>,
+        user: Option<(String, String)>,
+        timeout: Duration,
+    ) -> Self {
+        let authorization = match (api_key, user) {
+            (Some(api_key), _) => Some(format!("ApiKey {}", api_key)),
+            (None, Some((user, password))) => Some(format!(
+                "Basic {}",
+                base64::engine::general_purpose::STANDARD.encode(format!("{}:{}", user, password))
+            )),
+            (None, None) => None,
+        };
+
+        Self {
+            url,
+            authorization,
+            elastic_debug: std::env::var("ELASTIC_DEBUG").is_ok(),
+            timeout,
+        }
+    }
+
+    pub fn matches(&self, other: &ElasticUploader) -> bool {
+        self.url == other.url
+            && self.authorization == other.authorization
+            && self.timeout == other.timeout
+    }
+
+    async fn post_bulk(&self, data: Vec<u8>) -> Result<(u16, Vec<u8>), String> {
+        let mut fl_url = FlUrl::new(self.url.as_str())
+            .set_timeout(self.timeout)
+            .append_path_segment("_bulk")
+            .with_header("Content-Type", "application/x-ndjson")
+            .with_retries(3);
+
+        if let Some(authorization) = self.authorization.as_ref() {
+            fl_url = fl_url.with_header("Authorization", authorization.as_str());
+        }
+
+        let mut response = fl_url
+            .post(FlUrlBody::Raw {
+                data,
+                content_type: None,
+            })
+            .await
+            .map_err(|err| format!("{:?}", err))?;
+
+        let status_code = response.get_status_code();
+        let body = response
+            .get_body_as_slice()
+            .await
+            .map_err(|err| format!("{:?}", err))?;
+
+        Ok((status_code, body.to_vec()))
+    }
+}
+
+#[async_trait::async_trait]
+impl LogsChunkUploader for ElasticUploader {
+    /// Sends the chunk and then resends only the items which were rejected with a retryable status.
+    async fn upload_chunk(&self, chunk_to_upload: Vec<u8>) {
+        const MAX_ATTEMPTS: u32 = 4;
+
+        if self.elastic_debug {
+            println!("Sending bulk len={}", chunk_to_upload.len());
+        }
+
+        let mut attempt_no: u32 = 0;
+        let mut chunk = chunk_to_upload;
+        loop {
+            attempt_no += 1;
+
+            let (status_code, body) = match self.post_bulk(chunk.clone()).await {
+                Ok(result) => result,
+                Err(err) => {
+                    eprintln!(
+                        "Attempt: {} Error while uploading logs to elastic. Err: {}",
+                        attempt_no, err
+                    );
+
+                    if attempt_no >= MAX_ATTEMPTS {
+                        return;
+                    }
+
+                    tokio::time::sleep(Duration::from_secs(1)).await;
+                    continue;
+                }
+            };
+
+            if self.elastic_debug {
+                println!("Bulk result: {}", status_code);
+            }
+
+            if !(200..300).contains(&status_code) {
+                if !crate::is_retryable_status(status_code) {
+                    eprintln!(
+                        "Elastic rejected bulk request. Status code: {}. Body: {}",
+                        status_code,
+                        String::from_utf8_lossy(&body)
+                    );
+                    return;
+                }
+
+                if attempt_no >= MAX_ATTEMPTS {
+                    return;
+                }
+
+                tokio::time::sleep(Duration::from_secs(1)).await;
+                continue;
+            }
+
+            let item_errors = match crate::parse_bulk_response(&body) {
+                Ok(item_errors) => item_errors,
+                Err(err) => {
+                    eprintln!("{}", err);
+                    return;
+                }
+            };
+
+            if item_errors.is_empty() {
+                return;
+            }
+
+            let items = crate::split_bulk_items(&chunk);
+            let mut retry_chunk = Vec::new();
+
+            for item_error in item_errors {
+                if crate::is_retryable_status(item_error.status) {
+                    if let Some(item) = items.get(item_error.index) {
+                        retry_chunk.extend_from_slice(item);
+                    }
+                } else {
+                    eprintln!(
+                        "Elastic rejected log event. Status code: {}. Reason: {}",
+                        item_error.status, item_error.reason
+                    );
+                }
+            }
+
+            if retry_chunk.is_empty() || attempt_no >= MAX_ATTEMPTS {
+                return;
+            }
+
+            chunk = retry_chunk;
+            tokio::time::sleep(Duration::from_secs(1)).await;
+        }
+    }
+}
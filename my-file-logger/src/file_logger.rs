@@ -0,0 +1,100 @@
+use std::{sync::Arc, time::Duration};
+
+use crate::{FileLoggerInner, FileLoggerSettings};
+use my_logger_core::{MyLogEvent, MyLoggerReader};
+use rust_extensions::{events_loop::EventsLoop, AppStates};
+
+pub struct FileLogger {
+    inner: Arc<FileLoggerInner>,
+    events_loop: EventsLoop<()>,
+    app_states: Arc<AppStates>,
+}
+
+impl FileLogger {
+    /// Plugs the file writer into `LOGGER`. Must be called inside a tokio runtime.
+    pub fn enable(settings: FileLoggerSettings) -> Arc<Self> {
+        let flush_delay = settings.flush_delay;
+
+        let result = Self {
+            app_states: AppStates::create_initialized().into(),
+            inner: Arc::new(FileLoggerInner::new(&settings)),
+            events_loop: EventsLoop::new("FileLogger".to_string()),
+        };
+
+        result.events_loop.register_event_loop(result.inner.clone());
+
+        result
+            .events_loop
+            .start(result.app_states.clone(), my_logger_core::LOGGER.clone());
+
+        let file_logger = Arc::new(result);
+        my_seq_logger::start_flush_timer(Arc::downgrade(&file_logger), flush_delay, |logger| {
+            logger.events_loop.send(())
+        });
+        my_logger_core::LOGGER.plug_reader(file_logger.clone());
+        file_logger
+    }
+
+    pub fn enable_from_connection_string(conn_string: &str) -> Arc<Self> {
+        match FileLoggerSettings::try_parse(conn_string) {
+            Ok(settings) => Self::enable(settings),
+            Err(err) => panic!("Can not parse file logger settings. Err: {}", err),
+        }
+    }
+}
+
+impl MyLoggerReader for FileLogger {
+    fn write_log(&self, log_event: Arc<MyLogEvent>) {
+        self.inner.log_events.enqueue(log_event);
+    }
+
+    fn flush(&self, timeout: Duration) {
+        let inner = self.inner.clone();
+        my_seq_logger::flush_on_private_runtime("File", timeout, async move {
+            inner.write_queued_events().await
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{sync::Arc, time::Duration};
+
+    use my_logger_core::{test_utils::create_test_event, LogLevel, MyLoggerReader};
+    use rust_extensions::{events_loop::EventsLoop, AppStates};
+
+    use crate::{FileLoggerInner, FileLoggerSettings, LogFileFormat};
+
+    use super::FileLogger;
+
+    #[test]
+    fn test_flush_writes_queued_events() {
+        let path =
+            std::env::temp_dir().join(format!("my-file-logger-flush-test-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&path);
+
+        let mut settings = FileLoggerSettings::new(&path);
+        settings.format = LogFileFormat::PlainText;
+
+        let file_logger = FileLogger {
+            inner: Arc::new(FileLoggerInner::new(&settings)),
+            events_loop: EventsLoop::new("FileLogger".to_string()),
+            app_states: AppStates::create_initialized().into(),
+        };
+
+        file_logger.write_log(Arc::new(create_test_event(
+            LogLevel::Info,
+            "Orders",
+            "Created",
+        )));
+        file_logger.flush(Duration::from_secs(5));
+
+        let written: String = std::fs::read_dir(&path)
+            .unwrap()
+            .map(|itm| std::fs::read_to_string(itm.unwrap().path()).unwrap())
+            .collect();
+        assert!(written.contains("Created"));
+
+        std::fs::remove_dir_all(&path).unwrap();
+    }
+}
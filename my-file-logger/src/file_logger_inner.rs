@@ -0,0 +1,81 @@
+use std::sync::Arc;
+
+use my_seq_logger::LogEventsQueue;
+use parking_lot::Mutex;
+use rust_extensions::events_loop::EventsLoopTick;
+
+use crate::{FileLoggerSettings, LogFileFormat, RollingFile};
+
+const INITIAL_LINE_CAPACITY: usize = 1024;
+
+pub struct FileLoggerInner {
+    pub(crate) log_events: LogEventsQueue,
+    format: LogFileFormat,
+    rolling_file: Arc<Mutex<RollingFile>>,
+    /// Held while a batch is written, so a flush waits for the batch which is already in flight.
+    write_lock: tokio::sync::Mutex<()>,
+}
+
+impl FileLoggerInner {
+    pub fn new(settings: &FileLoggerSettings) -> Self {
+        let mut log_events = LogEventsQueue::new();
+        if let Some(queue_size) = settings.queue_size {
+            log_events.configure_size(queue_size);
+        }
+
+        Self {
+            log_events,
+            format: settings.format,
+            rolling_file: Arc::new(Mutex::new(RollingFile::new(settings))),
+            write_lock: tokio::sync::Mutex::new(()),
+        }
+    }
+
+    /// Writes the queued events to the file.
+    pub async fn write_queued_events(&self) {
+        let _write_lock = self.write_lock.lock().await;
+
+        let events = match self.log_events.dequeue() {
+            Some(events) => events,
+            None => return,
+        };
+
+        let populated_params = my_logger_core::LOGGER.get_populated_params();
+        let format = self.format;
+        let rolling_file = self.rolling_file.clone();
+
+        let result = tokio::task::spawn_blocking(move || {
+            let mut rolling_file = rolling_file.lock();
+            let mut line = String::with_capacity(INITIAL_LINE_CAPACITY);
+
+            for log_event in events.iter() {
+                line = format.serialize(line, log_event, &populated_params);
+                rolling_file.write_line(log_event.dt, line.as_bytes())?;
+            }
+
+            rolling_file.flush()
+        })
+        .await;
+
+        match result {
+            Ok(Ok(())) => {}
+            Ok(Err(err)) => {
+                eprintln!("Error while writing logs to file. Err: {:?}", err);
+            }
+            Err(err) => {
+                eprintln!("Error while writing logs to file. Err: {:?}", err);
+            }
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl EventsLoopTick<()> for FileLoggerInner {
+    async fn started(&self) {
+        println!("File Logs writer is started");
+    }
+    async fn tick(&self, _: ()) {
+        self.write_queued_events().await;
+    }
+    async fn finished(&self) {}
+}
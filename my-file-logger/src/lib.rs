@@ -0,0 +1,10 @@
+mod file_logger;
+pub use file_logger::FileLogger;
+mod file_logger_inner;
+pub use file_logger_inner::*;
+mod settings;
+pub use settings::*;
+mod log_file_format;
+pub use log_file_format::*;
+mod rolling_file;
+pub use rolling_file::*;
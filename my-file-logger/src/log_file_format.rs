@@ -0,0 +1,114 @@
+use std::fmt::Write;
+
+use my_json::json_writer::{JsonObjectWriter, RawJsonObject};
+use my_logger_core::{MyLogEvent, PopulatedParams};
+use my_seq_logger::{format_seq_string, format_value};
+
+#[derive(Debug, Clone, Copy)]
+pub enum LogFileFormat {
+    Clef,
+    JsonLines,
+    PlainText,
+}
+
+impl LogFileFormat {
+    pub fn try_parse(src: &str) -> Result<Self, String> {
+        match src {
+            "clef" => Ok(Self::Clef),
+            "json" | "jsonl" => Ok(Self::JsonLines),
+            "text" | "plain" => Ok(Self::PlainText),
+            _ => Err(format!("Invalid log file format {}", src)),
+        }
+    }
+
+    pub fn get_file_extension(&self) -> &'static str {
+        match self {
+            LogFileFormat::Clef => "clef",
+            LogFileFormat::JsonLines => "jsonl",
+            LogFileFormat::PlainText => "log",
+        }
+    }
+
+    /// Serializes one event as a single line without the trailing line break.
+    pub fn serialize(
+        &self,
+        compile_buffer: String,
+        log_event: &MyLogEvent,
+        populated_params: &PopulatedParams,
+    ) -> String {
+        match self {
+            LogFileFormat::Clef => {
+                my_seq_logger::serialize(compile_buffer, log_event, populated_params)
+            }
+            LogFileFormat::JsonLines => {
+                serialize_json_line(compile_buffer, log_event, populated_params)
+            }
+            LogFileFormat::PlainText => {
+                serialize_text_line(compile_buffer, log_event, populated_params)
+            }
+        }
+    }
+}
+
+fn serialize_json_line(
+    mut compile_buffer: String,
+    log_event: &MyLogEvent,
+    populated_params: &PopulatedParams,
+) -> String {
+    compile_buffer.clear();
+    let mut json_writer = JsonObjectWriter::from_string(compile_buffer)
+        .write("Timestamp", log_event.dt.to_rfc3339())
+        .write("Level", log_event.level.as_str())
+        .write(
+            "Process",
+            format_seq_string(log_event.process.as_str()).as_str(),
+        )
+        .write(
+            "Message",
+            format_seq_string(log_event.message.as_str()).as_str(),
+        );
+
+    for (key, value) in populated_params.iter() {
+        json_writer = json_writer.write(key, format_seq_string(value).as_str());
+    }
+
+    if let Some(ctx) = &log_event.context {
+        let mut ctx_writer = JsonObjectWriter::from_string(String::new());
+        for (key, value) in ctx {
+            ctx_writer = ctx_writer.write(key, format_value(value).as_str());
+        }
+        let ctx = ctx_writer.build();
+        json_writer = json_writer.write("Context", RawJsonObject::AsStr(ctx.as_str()));
+    }
+
+    json_writer.build()
+}
+
+fn serialize_text_line(
+    mut compile_buffer: String,
+    log_event: &MyLogEvent,
+    populated_params: &PopulatedParams,
+) -> String {
+    compile_buffer.clear();
+
+    let _ = write!(
+        &mut compile_buffer,
+        "{} {} [{}] {}",
+        log_event.dt.to_rfc3339(),
+        log_event.level.as_str(),
+        format_seq_string(log_event.process.as_str()).as_str(),
+        format_seq_string(log_event.message.as_str()).as_str(),
+    );
+
+    for (key, value) in populated_params.iter() {
+        let _ = write!(&mut compile_buffer, " {}={}", key, format_value(value).as_str());
+    }
+
+    if let Some(ctx) = &log_event.context {
+        for (key, value) in ctx {
+            let _ = write!(&mut compile_buffer, " {}={}", key, format_value(value).as_str());
+        }
+    }
+
+    compile_buffer
+}
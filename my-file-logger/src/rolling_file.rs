@@ -0,0 +1,366 @@
+use std::{
+    fs::{File, OpenOptions},
+    io::{BufWriter, Write},
+    path::PathBuf,
+};
+
+use rust_extensions::date_time::DateTimeAsMicroseconds;
+
+use crate::{FileLoggerSettings, FileRotation};
+
+const MICROSECONDS_IN_DAY: i64 = 86_400_000_000;
+
+pub struct RollingFile {
+    path: PathBuf,
+    file_name: String,
+    extension: &'static str,
+    rotation: FileRotation,
+    keep_files: Option<usize>,
+    keep_days: Option<u64>,
+    compress: bool,
+    file: Option<BufWriter<File>>,
+    file_size: u64,
+    file_date: String,
+}
+
+impl RollingFile {
+    pub fn new(settings: &FileLoggerSettings) -> Self {
+        Self {
+            path: settings.path.clone(),
+            file_name: settings.file_name.clone(),
+            extension: settings.format.get_file_extension(),
+            rotation: settings.rotation,
+            keep_files: settings.keep_files,
+            keep_days: settings.keep_days,
+            compress: settings.compress,
+            file: None,
+            file_size: 0,
+            file_date: String::new(),
+        }
+    }
+
+    pub fn get_active_file_path(&self) -> PathBuf {
+        self.path
+            .join(format!("{}.{}", self.file_name, self.extension))
+    }
+
+    /// Appends a line, rotating the active file first if the date changed or the size limit is reached.
+    pub fn write_line(&mut self, dt: DateTimeAsMicroseconds, line: &[u8]) -> std::io::Result<()> {
+        let date = get_date(dt);
+
+        if self.file.is_none() {
+            self.open(date.as_str())?;
+        }
+
+        if self.should_rotate(date.as_str(), line.len() as u64 + 1) {
+            self.rotate()?;
+            self.open(date.as_str())?;
+        }
+
+        let file = self.file.as_mut().unwrap();
+        file.write_all(line)?;
+        file.write_all(b"\n")?;
+        self.file_size += line.len() as u64 + 1;
+
+        Ok(())
+    }
+
+    pub fn flush(&mut self) -> std::io::Result<()> {
+        if let Some(file) = self.file.as_mut() {
+            file.flush()?;
+        }
+
+        Ok(())
+    }
+
+    /// An active file left by a previous run keeps the date of its last write,
+    /// so it is rotated under that date once a newer event arrives.
+    fn open(&mut self, date: &str) -> std::io::Result<()> {
+        std::fs::create_dir_all(&self.path)?;
+
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(self.get_active_file_path())?;
+
+        let metadata = file.metadata()?;
+        self.file_size = metadata.len();
+
+        self.file_date = match metadata.modified() {
+            Ok(modified) if self.file_size > 0 => get_date(get_date_time(modified)),
+            _ => date.to_string(),
+        };
+
+        self.file = Some(BufWriter::new(file));
+
+        Ok(())
+    }
+
+    fn should_rotate(&self, date: &str, line_len: u64) -> bool {
+        if self.rotation.is_daily() && self.file_date != date && self.file_size > 0 {
+            return true;
+        }
+
+        if let Some(max_size) = self.rotation.max_size() {
+            return self.file_size > 0 && self.file_size + line_len > max_size;
+        }
+
+        false
+    }
+
+    fn rotate(&mut self) -> std::io::Result<()> {
+        if let Some(mut file) = self.file.take() {
+            file.flush()?;
+        }
+
+        let rotated_path = self.get_next_rotated_path();
+        std::fs::rename(self.get_active_file_path(), &rotated_path)?;
+
+        if self.compress {
+            compress_file(&rotated_path)?;
+        }
+
+        self.apply_retention()
+    }
+
+    fn get_next_rotated_path(&self) -> PathBuf {
+        let mut index = 0;
+        loop {
+            let file_name = format!(
+                "{}.{}.{:03}.{}",
+                self.file_name, self.file_date, index, self.extension
+            );
+            let path = self.path.join(file_name.as_str());
+            let gz_path = self.path.join(format!("{}.gz", file_name));
+
+            if !path.exists() && !gz_path.exists() {
+                return path;
+            }
+
+            index += 1;
+        }
+    }
+
+    /// Rotated files of this logger sorted from the oldest. Other files in the directory,
+    /// including files of other loggers with the same prefix, are skipped.
+    fn get_rotated_files(&self) -> std::io::Result<Vec<(String, PathBuf)>> {
+        let mut result = Vec::new();
+        for entry in std::fs::read_dir(&self.path)? {
+            let entry = entry?;
+            let file_name = entry.file_name().to_string_lossy().to_string();
+
+            let parsed = parse_rotated_file_name(
+                file_name.as_str(),
+                self.file_name.as_str(),
+                self.extension,
+            );
+
+            if let Some((date, index)) = parsed {
+                result.push((date.to_string(), index, entry.path()));
+            }
+        }
+
+        result.sort_by(|a, b| (a.0.as_str(), a.1).cmp(&(b.0.as_str(), b.1)));
+        Ok(result
+            .into_iter()
+            .map(|(date, _, path)| (date, path))
+            .collect())
+    }
+
+    fn apply_retention(&self) -> std::io::Result<()> {
+        if self.keep_files.is_none() && self.keep_days.is_none() {
+            return Ok(());
+        }
+
+        let mut rotated_files = self.get_rotated_files()?;
+
+        if let Some(keep_days) = self.keep_days {
+            let now = DateTimeAsMicroseconds::now();
+            let oldest_date = get_date(DateTimeAsMicroseconds::new(
+                now.unix_microseconds - keep_days as i64 * MICROSECONDS_IN_DAY,
+            ));
+
+            rotated_files.retain(|(date, path)| {
+                if date.as_str() < oldest_date.as_str() {
+                    remove_file(path);
+                    return false;
+                }
+
+                true
+            });
+        }
+
+        if let Some(keep_files) = self.keep_files {
+            if rotated_files.len() > keep_files {
+                let to_remove = rotated_files.len() - keep_files;
+                for (_, path) in rotated_files.iter().take(to_remove) {
+                    remove_file(path);
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Parses `{file_name}.{yyyy-MM-dd}.{index:03}.{extension}[.gz]` into the date and the index.
+fn parse_rotated_file_name<'s>(
+    src: &'s str,
+    file_name: &str,
+    extension: &str,
+) -> Option<(&'s str, u32)> {
+    let src = src.strip_prefix(file_name)?.strip_prefix('.')?;
+    let src = src.strip_suffix(".gz").unwrap_or(src);
+    let src = src.strip_suffix(extension)?.strip_suffix('.')?;
+
+    let (date, index) = src.split_once('.')?;
+
+    if !is_date(date) || index.len() < 3 || !index.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+
+    Some((date, index.parse().ok()?))
+}
+
+fn is_date(src: &str) -> bool {
+    let bytes = src.as_bytes();
+
+    bytes.len() == 10
+        && bytes.iter().enumerate().all(|(index, b)| match index {
+            4 | 7 => *b == b'-',
+            _ => b.is_ascii_digit(),
+        })
+}
+
+fn get_date_time(src: std::time::SystemTime) -> DateTimeAsMicroseconds {
+    let microseconds = src
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|itm| itm.as_micros() as i64)
+        .unwrap_or_default();
+
+    DateTimeAsMicroseconds::new(microseconds)
+}
+
+fn get_date(dt: DateTimeAsMicroseconds) -> String {
+    let mut result = dt.to_rfc3339();
+    result.truncate(10);
+    result
+}
+
+fn compress_file(path: &PathBuf) -> std::io::Result<()> {
+    let mut gz_path = path.clone().into_os_string();
+    gz_path.push(".gz");
+
+    let mut src = File::open(path)?;
+    let dest = File::create(gz_path)?;
+    let mut encoder = flate2::write::GzEncoder::new(dest, flate2::Compression::default());
+    std::io::copy(&mut src, &mut encoder)?;
+    encoder.finish()?;
+
+    std::fs::remove_file(path)
+}
+
+fn remove_file(path: &PathBuf) {
+    if let Err(err) = std::fs::remove_file(path) {
+        eprintln!("Can not remove rotated log file {:?}. Err: {:?}", path, err);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_extensions::date_time::DateTimeAsMicroseconds;
+
+    use crate::{FileLoggerSettings, FileRotation, LogFileFormat};
+
+    use super::RollingFile;
+
+    #[test]
+    fn test_rotation_by_size_keeps_files() {
+        let path = std::env::temp_dir().join(format!("my-file-logger-test-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&path);
+
+        let mut settings = FileLoggerSettings::new(&path);
+        settings.format = LogFileFormat::PlainText;
+        settings.rotation = FileRotation::Size(16);
+        settings.keep_files = Some(2);
+
+        let mut rolling_file = RollingFile::new(&settings);
+        let now = DateTimeAsMicroseconds::now();
+        for _ in 0..5 {
+            rolling_file.write_line(now, b"0123456789").unwrap();
+        }
+        rolling_file.flush().unwrap();
+
+        let files = std::fs::read_dir(&path).unwrap().count();
+        assert_eq!(3, files);
+
+        let active = std::fs::read_to_string(rolling_file.get_active_file_path()).unwrap();
+        assert_eq!("0123456789\n", active);
+
+        std::fs::remove_dir_all(&path).unwrap();
+    }
+
+    #[test]
+    fn test_parse_rotated_file_name() {
+        assert_eq!(
+            Some(("2024-01-31", 2)),
+            super::parse_rotated_file_name("app.2024-01-31.002.clef", "app", "clef")
+        );
+        assert_eq!(
+            Some(("2024-01-31", 1000)),
+            super::parse_rotated_file_name("app.2024-01-31.1000.clef.gz", "app", "clef")
+        );
+
+        for other in [
+            "app.clef",
+            "app.audit.clef",
+            "app.audit-log-2024.clef",
+            "app.2024-01-31.002.log",
+            "app.2024-01-31.2.clef",
+            "app.2024-01-31.002.clef.zip",
+            "application.2024-01-31.002.clef",
+        ] {
+            assert!(
+                super::parse_rotated_file_name(other, "app", "clef").is_none(),
+                "{}",
+                other
+            );
+        }
+    }
+
+    #[test]
+    fn test_reopened_file_keeps_its_date() {
+        let path =
+            std::env::temp_dir().join(format!("my-file-logger-reopen-test-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&path);
+
+        let mut settings = FileLoggerSettings::new(&path);
+        settings.format = LogFileFormat::PlainText;
+        settings.rotation = FileRotation::Daily;
+
+        std::fs::create_dir_all(&path).unwrap();
+        let active_path = RollingFile::new(&settings).get_active_file_path();
+        std::fs::write(&active_path, "yesterday\n").unwrap();
+
+        let yesterday = std::time::SystemTime::now() - std::time::Duration::from_secs(86_400);
+        std::fs::File::options()
+            .append(true)
+            .open(&active_path)
+            .unwrap()
+            .set_modified(yesterday)
+            .unwrap();
+
+        let mut rolling_file = RollingFile::new(&settings);
+        rolling_file
+            .write_line(DateTimeAsMicroseconds::now(), b"today")
+            .unwrap();
+        rolling_file.flush().unwrap();
+
+        let yesterday_date = super::get_date(super::get_date_time(yesterday));
+        let rotated = path.join(format!("{}.{}.000.log", settings.file_name, yesterday_date));
+        assert_eq!("yesterday\n", std::fs::read_to_string(rotated).unwrap());
+        assert_eq!("today\n", std::fs::read_to_string(&active_path).unwrap());
+
+        std::fs::remove_dir_all(&path).unwrap();
+    }
+}
@@ -0,0 +1,171 @@
+use std::{path::PathBuf, time::Duration};
+
+use my_seq_logger::{parse_number, split_key_value};
+
+use crate::LogFileFormat;
+
+const DEFAULT_FILE_NAME: &str = "app";
+const DEFAULT_FLUSH_DELAY: u64 = 1;
+
+#[derive(Debug, Clone, Copy)]
+pub enum FileRotation {
+    Never,
+    Daily,
+    Size(u64),
+    DailyOrSize(u64),
+}
+
+impl FileRotation {
+    pub fn is_daily(&self) -> bool {
+        matches!(self, FileRotation::Daily | FileRotation::DailyOrSize(_))
+    }
+
+    pub fn max_size(&self) -> Option<u64> {
+        match self {
+            FileRotation::Size(size) => Some(*size),
+            FileRotation::DailyOrSize(size) => Some(*size),
+            _ => None,
+        }
+    }
+}
+
+pub struct FileLoggerSettings {
+    pub path: PathBuf,
+    pub file_name: String,
+    pub format: LogFileFormat,
+    pub rotation: FileRotation,
+    pub keep_files: Option<usize>,
+    pub keep_days: Option<u64>,
+    pub compress: bool,
+    pub queue_size: Option<usize>,
+    /// Interval at which queued events are written to the file and flushed.
+    pub flush_delay: Duration,
+}
+
+impl FileLoggerSettings {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            file_name: DEFAULT_FILE_NAME.to_string(),
+            format: LogFileFormat::Clef,
+            rotation: FileRotation::Daily,
+            keep_files: None,
+            keep_days: None,
+            compress: false,
+            queue_size: None,
+            flush_delay: Duration::from_secs(DEFAULT_FLUSH_DELAY),
+        }
+    }
+
+    /// Parses settings in the same `key=value;key=value` form as the seq connection string:
+    /// `path=/var/log/app;filename=app;format=clef;rotation=daily;maxsize=10485760;keepfiles=10;keepdays=7;compress=true`
+    pub fn try_parse(conn_string: &str) -> Result<Self, String> {
+        let mut path = None;
+        let mut file_name = DEFAULT_FILE_NAME.to_string();
+        let mut format = LogFileFormat::Clef;
+        let mut daily = true;
+        let mut max_size = None;
+        let mut keep_files = None;
+        let mut keep_days = None;
+        let mut compress = false;
+        let mut queue_size = None;
+        let mut flush_delay = DEFAULT_FLUSH_DELAY;
+
+        for item in conn_string.split(';') {
+            if item.is_empty() {
+                continue;
+            }
+
+            let (key, value) = split_key_value(item, "file logger settings")?;
+
+            match key {
+                "path" => {
+                    path = Some(value);
+                }
+                "filename" => {
+                    file_name = value.to_string();
+                }
+                "format" => {
+                    format = LogFileFormat::try_parse(value)?;
+                }
+                "rotation" => match value {
+                    "daily" => daily = true,
+                    "size" | "none" => daily = false,
+                    _ => return Err(format!("Invalid rotation value {}", value)),
+                },
+                "maxsize" => {
+                    max_size = Some(parse_number(key, value)?);
+                }
+                "keepfiles" => {
+                    keep_files = Some(parse_number(key, value)?);
+                }
+                "keepdays" => {
+                    keep_days = Some(parse_number(key, value)?);
+                }
+                "compress" => {
+                    compress = value == "true";
+                }
+                "queuesize" => {
+                    queue_size = Some(parse_number(key, value)?);
+                }
+                "flushdelay" => {
+                    flush_delay = parse_number(key, value)?;
+                }
+                _ => {
+                    return Err(format!("Invalid key {} of file logger settings", key));
+                }
+            }
+        }
+
+        let path = match path {
+            Some(path) => path,
+            None => return Err("There is no path parameter in file logger settings".to_string()),
+        };
+
+        let rotation = match (daily, max_size) {
+            (true, Some(max_size)) => FileRotation::DailyOrSize(max_size),
+            (true, None) => FileRotation::Daily,
+            (false, Some(max_size)) => FileRotation::Size(max_size),
+            (false, None) => FileRotation::Never,
+        };
+
+        Ok(Self {
+            path: path.into(),
+            file_name,
+            format,
+            rotation,
+            keep_files,
+            keep_days,
+            compress,
+            queue_size,
+            flush_delay: Duration::from_secs(flush_delay),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{FileLoggerSettings, FileRotation};
+    use crate::LogFileFormat;
+
+    #[test]
+    fn test_try_parse_correct_values() {
+        let str = "path=/var/log/app;filename=pricing;format=jsonl;maxsize=1024;keepfiles=10;keepdays=7;compress=true";
+
+        let result = FileLoggerSettings::try_parse(str).unwrap();
+
+        assert_eq!("/var/log/app", result.path.to_str().unwrap());
+        assert_eq!("pricing", result.file_name);
+        assert!(matches!(result.format, LogFileFormat::JsonLines));
+        assert!(matches!(result.rotation, FileRotation::DailyOrSize(1024)));
+        assert_eq!(Some(10), result.keep_files);
+        assert_eq!(Some(7), result.keep_days);
+        assert!(result.compress);
+    }
+
+    #[test]
+    fn test_try_parse_without_path() {
+        let result = FileLoggerSettings::try_parse("format=text;rotation=size");
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,103 @@
+use std::{sync::Arc, time::Duration};
+
+use crate::{FluentdLoggerInner, FluentdSettings};
+use my_logger_core::{MyLogEvent, MyLoggerReader};
+use rust_extensions::{events_loop::EventsLoop, AppStates};
+
+pub struct FluentdLogger {
+    inner: Arc<FluentdLoggerInner>,
+    events_loop: EventsLoop<()>,
+    app_states: Arc<AppStates>,
+}
+
+impl FluentdLogger {
+    /// Plugs the Fluentd writer into `LOGGER`. Must be called inside a tokio runtime.
+    pub fn enable(settings: FluentdSettings) -> Arc<Self> {
+        let flush_delay = settings.flush_delay;
+        let timeout = settings.timeout;
+
+        let result = Self {
+            app_states: AppStates::create_initialized().into(),
+            inner: Arc::new(FluentdLoggerInner::new(settings)),
+            events_loop: EventsLoop::new("FluentdLogger".to_string())
+                .set_iteration_timeout(timeout),
+        };
+
+        result.events_loop.register_event_loop(result.inner.clone());
+
+        result
+            .events_loop
+            .start(result.app_states.clone(), my_logger_core::LOGGER.clone());
+
+        let fluentd_logger = Arc::new(result);
+        my_seq_logger::start_flush_timer(Arc::downgrade(&fluentd_logger), flush_delay, |logger| {
+            logger.events_loop.send(())
+        });
+        my_logger_core::LOGGER.plug_reader(fluentd_logger.clone());
+        fluentd_logger
+    }
+
+    pub fn enable_from_connection_string(conn_string: &str) -> Arc<Self> {
+        match FluentdSettings::try_parse(conn_string) {
+            Ok(settings) => Self::enable(settings),
+            Err(err) => panic!("Can not parse Fluentd settings. Err: {}", err),
+        }
+    }
+}
+
+impl MyLoggerReader for FluentdLogger {
+    fn write_log(&self, log_event: Arc<MyLogEvent>) {
+        self.inner.log_events.enqueue(log_event);
+    }
+
+    fn flush(&self, timeout: Duration) {
+        let inner = self.inner.clone();
+        my_seq_logger::flush_on_private_runtime("Fluentd", timeout, async move {
+            inner.send_queued_events().await
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{io::Read, net::TcpListener, sync::Arc, time::Duration};
+
+    use my_logger_core::{test_utils::create_test_event, LogLevel, MyLoggerReader};
+    use rust_extensions::{events_loop::EventsLoop, AppStates};
+
+    use crate::{FluentdLoggerInner, FluentdSettings};
+
+    use super::FluentdLogger;
+
+    #[test]
+    fn test_flush_sends_queued_events() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let conn_string = format!("url=tcp://{};ack=false", listener.local_addr().unwrap());
+
+        let server = std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut received = Vec::new();
+            stream.read_to_end(&mut received).unwrap();
+            received
+        });
+
+        let fluentd_logger = FluentdLogger {
+            inner: Arc::new(FluentdLoggerInner::new(
+                FluentdSettings::try_parse(conn_string.as_str()).unwrap(),
+            )),
+            events_loop: EventsLoop::new("FluentdLogger".to_string()),
+            app_states: AppStates::create_initialized().into(),
+        };
+
+        fluentd_logger.write_log(Arc::new(create_test_event(
+            LogLevel::Info,
+            "Orders",
+            "Created",
+        )));
+        fluentd_logger.flush(Duration::from_secs(5));
+        drop(fluentd_logger);
+
+        let received = server.join().unwrap();
+        assert!(received.windows(7).any(|itm| itm == b"Created"));
+    }
+}
@@ -0,0 +1,254 @@
+use std::sync::Arc;
+
+use my_logger_core::{MyLogEvent, PopulatedParams};
+use my_seq_logger::{LogEventsQueue, PendingEvents};
+use rust_extensions::events_loop::EventsLoopTick;
+use tokio::sync::Mutex;
+
+use crate::{FluentdConnection, FluentdSettings};
+
+struct FluentdWriterState {
+    connection: Option<FluentdConnection>,
+    /// Events which are not acknowledged yet. Kept while the input is unreachable.
+    pending: PendingEvents,
+}
+
+pub struct FluentdLoggerInner {
+    pub(crate) log_events: LogEventsQueue,
+    settings: FluentdSettings,
+    state: Mutex<FluentdWriterState>,
+}
+
+impl FluentdLoggerInner {
+    pub fn new(settings: FluentdSettings) -> Self {
+        let mut log_events = LogEventsQueue::new();
+        log_events.configure_size(settings.queue_size);
+
+        let pending = PendingEvents::new(settings.queue_size);
+
+        Self {
+            log_events,
+            settings,
+            state: Mutex::new(FluentdWriterState {
+                connection: None,
+                pending,
+            }),
+        }
+    }
+
+    /// Sends one chunk per tag. On error returns the events of the chunks which were not acknowledged,
+    /// so the chunks delivered before the connection dropped are not sent twice.
+    async fn send_batch(
+        &self,
+        connection: &mut FluentdConnection,
+        batch: &[Arc<MyLogEvent>],
+    ) -> Result<(), (std::io::Error, Vec<Arc<MyLogEvent>>)> {
+        let populated_params = my_logger_core::LOGGER.get_populated_params();
+
+        let groups = crate::group_by_tag(
+            batch.iter(),
+            self.settings.tag_prefix.as_deref(),
+            &populated_params,
+        );
+
+        for (index, (tag, events)) in groups.iter().enumerate() {
+            if let Err(err) = self
+                .send_chunk(
+                    connection,
+                    tag.as_str(),
+                    events.as_slice(),
+                    &populated_params,
+                )
+                .await
+            {
+                let not_delivered = groups[index..]
+                    .iter()
+                    .flat_map(|(_, events)| events.iter().map(|itm| (*itm).clone()))
+                    .collect();
+
+                return Err((err, not_delivered));
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn send_chunk(
+        &self,
+        connection: &mut FluentdConnection,
+        tag: &str,
+        events: &[&Arc<MyLogEvent>],
+        populated_params: &PopulatedParams,
+    ) -> std::io::Result<()> {
+        let chunk_id = if self.settings.require_ack {
+            Some(crate::generate_chunk_id())
+        } else {
+            None
+        };
+
+        let payload = crate::serialize_forward_message(
+            tag,
+            events,
+            populated_params,
+            self.settings.compression,
+            chunk_id.as_deref(),
+        );
+
+        connection.send(payload.as_slice()).await?;
+
+        if let Some(chunk_id) = &chunk_id {
+            connection
+                .wait_ack(chunk_id.as_str(), self.settings.timeout)
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    /// Sends the queued events and the ones left from the previous attempts.
+    pub async fn send_queued_events(&self) {
+        let mut state = self.state.lock().await;
+
+        if let Some(events) = self.log_events.dequeue() {
+            state.pending.add(events);
+        }
+
+        state
+            .pending
+            .report_dropped(format_args!("Fluentd {}", self.settings.addr));
+
+        while !state.pending.is_empty() {
+            if state.connection.is_none() {
+                match FluentdConnection::connect(self.settings.addr.as_str(), self.settings.timeout)
+                    .await
+                {
+                    Ok(connection) => state.connection = Some(connection),
+                    Err(err) => {
+                        eprintln!(
+                            "Can not connect to Fluentd {}. Err: {:?}",
+                            self.settings.addr, err
+                        );
+                        return;
+                    }
+                }
+            }
+
+            let batch_size = self.settings.batch_size.min(state.pending.len());
+            let batch: Vec<Arc<MyLogEvent>> =
+                state.pending.iter().take(batch_size).cloned().collect();
+
+            let result = self
+                .send_batch(state.connection.as_mut().unwrap(), batch.as_slice())
+                .await;
+
+            state.pending.remove_front(batch_size);
+
+            if let Err((err, not_delivered)) = result {
+                // Chunks which were not acknowledged are resent on the next tick: delivery is at-least-once.
+                eprintln!(
+                    "Error while sending logs to Fluentd {}. Err: {:?}",
+                    self.settings.addr, err
+                );
+                state.pending.push_front(not_delivered);
+                state.connection = None;
+                return;
+            }
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl EventsLoopTick<()> for FluentdLoggerInner {
+    async fn started(&self) {
+        println!("Fluentd writer is started");
+    }
+    async fn tick(&self, _: ()) {
+        self.send_queued_events().await;
+    }
+    async fn finished(&self) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use my_logger_core::{test_utils::create_test_event, LogLevel};
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::{TcpListener, TcpStream},
+    };
+
+    use crate::{FluentdSettings, MsgPackWriter};
+
+    use super::FluentdLoggerInner;
+
+    const CHUNK_OPTION: &[u8] = b"chunk";
+    const CHUNK_ID_LEN: usize = 24;
+
+    /// Reads one PackedForward message and returns its chunk id.
+    async fn read_chunk_id(stream: &mut TcpStream) -> String {
+        let mut received = Vec::new();
+        let mut buffer = [0u8; 1024];
+
+        loop {
+            if let Some(index) = received
+                .windows(CHUNK_OPTION.len())
+                .position(|itm| itm == CHUNK_OPTION)
+            {
+                let start = index + CHUNK_OPTION.len() + 1;
+                if received.len() >= start + CHUNK_ID_LEN {
+                    return String::from_utf8(received[start..start + CHUNK_ID_LEN].to_vec())
+                        .unwrap();
+                }
+            }
+
+            let read = stream.read(&mut buffer).await.unwrap();
+            assert!(read > 0);
+            received.extend_from_slice(&buffer[..read]);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_acked_chunks_are_not_resent() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap().to_string();
+
+        let server = tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let chunk_id = read_chunk_id(&mut stream).await;
+
+            let mut writer = MsgPackWriter::new();
+            writer.write_map_len(1);
+            writer.write_str("ack");
+            writer.write_str(chunk_id.as_str());
+            stream.write_all(writer.build().as_slice()).await.unwrap();
+
+            // The second chunk is never acknowledged
+            read_chunk_id(&mut stream).await;
+        });
+
+        let inner = FluentdLoggerInner::new(FluentdSettings::new(addr));
+        inner.log_events.enqueue(Arc::new(create_test_event(
+            LogLevel::Info,
+            "Orders",
+            "Delivered",
+        )));
+        inner.log_events.enqueue(Arc::new(create_test_event(
+            LogLevel::Info,
+            "Payments",
+            "Not acknowledged",
+        )));
+
+        inner.send_queued_events().await;
+        server.await.unwrap();
+
+        let state = inner.state.lock().await;
+        let pending: Vec<&str> = state
+            .pending
+            .iter()
+            .map(|itm| itm.message.as_str())
+            .collect();
+        assert_eq!(vec!["Not acknowledged"], pending);
+        assert!(state.connection.is_none());
+    }
+}
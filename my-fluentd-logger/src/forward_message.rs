@@ -0,0 +1,268 @@
+use std::{
+    io::Write,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+};
+
+use base64::Engine;
+use my_logger_core::{MyLogEvent, PopulatedParams};
+
+use crate::{FluentdCompression, MsgPackReader, MsgPackWriter};
+
+const DEFAULT_APP_TAG: &str = "app";
+
+static CHUNK_ID_SEQUENCE: AtomicU64 = AtomicU64::new(0);
+
+/// `{tag_prefix}.{Application}.{process}`. Tag parts can not contain dots, so they are replaced with `_`.
+pub fn get_fluentd_tag(
+    tag_prefix: Option<&str>,
+    populated_params: &PopulatedParams,
+    process: &str,
+) -> String {
+    let mut result = String::new();
+
+    if let Some(tag_prefix) = tag_prefix {
+        result.push_str(tag_prefix);
+        result.push('.');
+    }
+
+    push_tag_part(
+        &mut result,
+        populated_params
+            .get("Application")
+            .unwrap_or(DEFAULT_APP_TAG),
+    );
+    result.push('.');
+    push_tag_part(&mut result, process);
+
+    result
+}
+
+fn push_tag_part(out: &mut String, src: &str) {
+    for c in src.chars() {
+        if c.is_ascii_alphanumeric() || matches!(c, '_' | '-') {
+            out.push(c);
+        } else {
+            out.push('_');
+        }
+    }
+}
+
+/// Groups events by tag keeping the order of events inside every tag.
+pub fn group_by_tag<'s>(
+    log_events: impl Iterator<Item = &'s Arc<MyLogEvent>>,
+    tag_prefix: Option<&str>,
+    populated_params: &PopulatedParams,
+) -> Vec<(String, Vec<&'s Arc<MyLogEvent>>)> {
+    let mut result: Vec<(String, Vec<&'s Arc<MyLogEvent>>)> = Vec::new();
+
+    for log_event in log_events {
+        let tag = get_fluentd_tag(tag_prefix, populated_params, log_event.process.as_str());
+
+        match result.iter_mut().find(|(itm, _)| itm == &tag) {
+            Some((_, events)) => events.push(log_event),
+            None => result.push((tag, vec![log_event])),
+        }
+    }
+
+    result
+}
+
+pub fn generate_chunk_id() -> String {
+    let sequence = CHUNK_ID_SEQUENCE.fetch_add(1, Ordering::Relaxed);
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|itm| itm.as_nanos() as u64)
+        .unwrap_or_default();
+
+    let mut id = [0u8; 16];
+    id[..8].copy_from_slice(&now.to_be_bytes());
+    id[8..].copy_from_slice(&(sequence ^ std::process::id() as u64).to_be_bytes());
+
+    base64::engine::general_purpose::STANDARD.encode(id)
+}
+
+/// PackedForward mode: `[tag, <entries>, {"size": n, "chunk": id, "compressed": "gzip"}]`,
+/// where entries are concatenated msgpack `[time, record]` arrays.
+pub fn serialize_forward_message(
+    tag: &str,
+    log_events: &[&Arc<MyLogEvent>],
+    populated_params: &PopulatedParams,
+    compression: FluentdCompression,
+    chunk_id: Option<&str>,
+) -> Vec<u8> {
+    let params: Vec<(&str, &str)> = populated_params.iter().collect();
+
+    let mut entries = MsgPackWriter::new();
+    for log_event in log_events {
+        write_entry(&mut entries, log_event, params.as_slice());
+    }
+    let mut entries = entries.build();
+
+    if compression == FluentdCompression::Gzip {
+        let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        match encoder.write_all(&entries).and_then(|_| encoder.finish()) {
+            Ok(compressed) => entries = compressed,
+            Err(err) => {
+                eprintln!("Can not compress Fluentd entries. Err: {:?}", err);
+                return serialize_forward_message(
+                    tag,
+                    log_events,
+                    populated_params,
+                    FluentdCompression::None,
+                    chunk_id,
+                );
+            }
+        }
+    }
+
+    let mut options_len = 1;
+    if chunk_id.is_some() {
+        options_len += 1;
+    }
+    if compression == FluentdCompression::Gzip {
+        options_len += 1;
+    }
+
+    let mut writer = MsgPackWriter::new();
+    writer.write_array_len(3);
+    writer.write_str(tag);
+    writer.write_bin(entries.as_slice());
+    writer.write_map_len(options_len);
+    writer.write_str("size");
+    writer.write_uint(log_events.len() as u64);
+
+    if let Some(chunk_id) = chunk_id {
+        writer.write_str("chunk");
+        writer.write_str(chunk_id);
+    }
+
+    if compression == FluentdCompression::Gzip {
+        writer.write_str("compressed");
+        writer.write_str("gzip");
+    }
+
+    writer.build()
+}
+
+fn write_entry(writer: &mut MsgPackWriter, log_event: &MyLogEvent, params: &[(&str, &str)]) {
+    writer.write_array_len(2);
+    writer.write_event_time(log_event.dt.unix_microseconds);
+
+    let mut record_len = 3 + params.len();
+    if log_event.context.is_some() {
+        record_len += 1;
+    }
+
+    writer.write_map_len(record_len);
+    writer.write_str("level");
+    writer.write_str(log_event.level.as_str());
+    writer.write_str("process");
+    writer.write_str(log_event.process.as_str());
+    writer.write_str("message");
+    writer.write_str(log_event.message.as_str());
+
+    for (key, value) in params {
+        writer.write_str(key);
+        writer.write_str(value);
+    }
+
+    if let Some(ctx) = &log_event.context {
+        writer.write_str("context");
+        writer.write_map_len(ctx.len());
+        for (key, value) in ctx {
+            writer.write_str(key);
+            writer.write_str(value);
+        }
+    }
+}
+
+/// Server responds with `{"ack": <chunk id>}`. Returns None while the response is incomplete.
+pub fn parse_ack_response(data: &[u8]) -> Option<String> {
+    let mut reader = MsgPackReader::new(data);
+    let len = reader.read_map_len()?;
+
+    let mut result = None;
+    for _ in 0..len {
+        let key = reader.read_str()?;
+        let value = reader.read_str()?;
+        if key == "ack" {
+            result = Some(value.to_string());
+        }
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use my_logger_core::{test_utils::create_test_event, LogLevel, PopulatedParams};
+    use rust_extensions::date_time::DateTimeAsMicroseconds;
+
+    use crate::{FluentdCompression, MsgPackWriter};
+
+    #[test]
+    fn test_tag() {
+        let populated_params = PopulatedParams::new(vec![("Application", "oms".into())]);
+
+        assert_eq!(
+            "k8s.oms.Orders_sync",
+            super::get_fluentd_tag(Some("k8s"), &populated_params, "Orders.sync")
+        );
+        assert_eq!(
+            "app.Orders",
+            super::get_fluentd_tag(None, &PopulatedParams::new_empty(), "Orders")
+        );
+    }
+
+    #[test]
+    fn test_packed_forward_message() {
+        let mut log_event = create_test_event(LogLevel::Info, "P", "M");
+        log_event.dt = DateTimeAsMicroseconds::new(1_000_000);
+        let log_event = Arc::new(log_event);
+
+        let payload = super::serialize_forward_message(
+            "a.P",
+            &[&log_event],
+            &PopulatedParams::new_empty(),
+            FluentdCompression::None,
+            Some("id"),
+        );
+
+        let mut entry = MsgPackWriter::new();
+        entry.write_array_len(2);
+        entry.write_event_time(1_000_000);
+        entry.write_map_len(3);
+        for value in ["level", "Info", "process", "P", "message", "M"] {
+            entry.write_str(value);
+        }
+
+        let mut expected = MsgPackWriter::new();
+        expected.write_array_len(3);
+        expected.write_str("a.P");
+        expected.write_bin(entry.build().as_slice());
+        expected.write_map_len(2);
+        expected.write_str("size");
+        expected.write_uint(1);
+        expected.write_str("chunk");
+        expected.write_str("id");
+
+        assert_eq!(expected.build(), payload);
+    }
+
+    #[test]
+    fn test_parse_ack_response() {
+        let mut writer = MsgPackWriter::new();
+        writer.write_map_len(1);
+        writer.write_str("ack");
+        writer.write_str("abc");
+        let data = writer.build();
+
+        assert_eq!(Some("abc".to_string()), super::parse_ack_response(&data));
+        assert_eq!(None, super::parse_ack_response(&data[..4]));
+    }
+}
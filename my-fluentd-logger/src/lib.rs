@@ -0,0 +1,12 @@
+mod fluentd_logger;
+pub use fluentd_logger::FluentdLogger;
+mod fluentd_logger_inner;
+pub use fluentd_logger_inner::*;
+mod settings;
+pub use settings::*;
+mod msgpack;
+pub use msgpack::*;
+mod forward_message;
+pub use forward_message::*;
+mod transport;
+pub use transport::*;
@@ -0,0 +1,223 @@
+/// Minimal MessagePack encoder covering the types used by the Forward protocol.
+pub struct MsgPackWriter {
+    buffer: Vec<u8>,
+}
+
+impl MsgPackWriter {
+    pub fn new() -> Self {
+        Self { buffer: Vec::new() }
+    }
+
+    pub fn from_vec(mut buffer: Vec<u8>) -> Self {
+        buffer.clear();
+        Self { buffer }
+    }
+
+    pub fn write_nil(&mut self) {
+        self.buffer.push(0xc0);
+    }
+
+    pub fn write_bool(&mut self, value: bool) {
+        self.buffer.push(if value { 0xc3 } else { 0xc2 });
+    }
+
+    pub fn write_uint(&mut self, value: u64) {
+        if value < 0x80 {
+            self.buffer.push(value as u8);
+        } else if value <= u8::MAX as u64 {
+            self.buffer.push(0xcc);
+            self.buffer.push(value as u8);
+        } else if value <= u16::MAX as u64 {
+            self.buffer.push(0xcd);
+            self.buffer.extend_from_slice(&(value as u16).to_be_bytes());
+        } else if value <= u32::MAX as u64 {
+            self.buffer.push(0xce);
+            self.buffer.extend_from_slice(&(value as u32).to_be_bytes());
+        } else {
+            self.buffer.push(0xcf);
+            self.buffer.extend_from_slice(&value.to_be_bytes());
+        }
+    }
+
+    pub fn write_str(&mut self, value: &str) {
+        let len = value.len();
+        if len < 32 {
+            self.buffer.push(0xa0 | len as u8);
+        } else if len <= u8::MAX as usize {
+            self.buffer.push(0xd9);
+            self.buffer.push(len as u8);
+        } else if len <= u16::MAX as usize {
+            self.buffer.push(0xda);
+            self.buffer.extend_from_slice(&(len as u16).to_be_bytes());
+        } else {
+            self.buffer.push(0xdb);
+            self.buffer.extend_from_slice(&(len as u32).to_be_bytes());
+        }
+        self.buffer.extend_from_slice(value.as_bytes());
+    }
+
+    pub fn write_bin(&mut self, value: &[u8]) {
+        let len = value.len();
+        if len <= u8::MAX as usize {
+            self.buffer.push(0xc4);
+            self.buffer.push(len as u8);
+        } else if len <= u16::MAX as usize {
+            self.buffer.push(0xc5);
+            self.buffer.extend_from_slice(&(len as u16).to_be_bytes());
+        } else {
+            self.buffer.push(0xc6);
+            self.buffer.extend_from_slice(&(len as u32).to_be_bytes());
+        }
+        self.buffer.extend_from_slice(value);
+    }
+
+    pub fn write_array_len(&mut self, len: usize) {
+        self.write_container_len(len, 0x90, 0xdc, 0xdd);
+    }
+
+    pub fn write_map_len(&mut self, len: usize) {
+        self.write_container_len(len, 0x80, 0xde, 0xdf);
+    }
+
+    /// Fluentd `EventTime`: ext type 0 with big-endian seconds and nanoseconds.
+    pub fn write_event_time(&mut self, unix_microseconds: i64) {
+        let seconds = unix_microseconds.div_euclid(1_000_000) as u32;
+        let nanos = (unix_microseconds.rem_euclid(1_000_000) * 1000) as u32;
+
+        self.buffer.push(0xd7);
+        self.buffer.push(0x00);
+        self.buffer.extend_from_slice(&seconds.to_be_bytes());
+        self.buffer.extend_from_slice(&nanos.to_be_bytes());
+    }
+
+    pub fn write_raw(&mut self, value: &[u8]) {
+        self.buffer.extend_from_slice(value);
+    }
+
+    pub fn len(&self) -> usize {
+        self.buffer.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.buffer.is_empty()
+    }
+
+    pub fn build(self) -> Vec<u8> {
+        self.buffer
+    }
+
+    fn write_container_len(&mut self, len: usize, fix_marker: u8, marker16: u8, marker32: u8) {
+        if len < 16 {
+            self.buffer.push(fix_marker | len as u8);
+        } else if len <= u16::MAX as usize {
+            self.buffer.push(marker16);
+            self.buffer.extend_from_slice(&(len as u16).to_be_bytes());
+        } else {
+            self.buffer.push(marker32);
+            self.buffer.extend_from_slice(&(len as u32).to_be_bytes());
+        }
+    }
+}
+
+impl Default for MsgPackWriter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Minimal MessagePack decoder used to read server responses.
+/// Every method returns None if data is incomplete or has an unexpected type.
+pub struct MsgPackReader<'s> {
+    data: &'s [u8],
+    pos: usize,
+}
+
+impl<'s> MsgPackReader<'s> {
+    pub fn new(data: &'s [u8]) -> Self {
+        Self { data, pos: 0 }
+    }
+
+    pub fn read_map_len(&mut self) -> Option<usize> {
+        let marker = self.read_u8()?;
+        match marker {
+            0x80..=0x8f => Some((marker & 0x0f) as usize),
+            0xde => Some(self.read_be(2)? as usize),
+            0xdf => Some(self.read_be(4)? as usize),
+            _ => None,
+        }
+    }
+
+    pub fn read_str(&mut self) -> Option<&'s str> {
+        let marker = self.read_u8()?;
+        let len = match marker {
+            0xa0..=0xbf => (marker & 0x1f) as usize,
+            0xd9 => self.read_be(1)? as usize,
+            0xda => self.read_be(2)? as usize,
+            0xdb => self.read_be(4)? as usize,
+            _ => return None,
+        };
+
+        let bytes = self.read_bytes(len)?;
+        std::str::from_utf8(bytes).ok()
+    }
+
+    fn read_u8(&mut self) -> Option<u8> {
+        let result = *self.data.get(self.pos)?;
+        self.pos += 1;
+        Some(result)
+    }
+
+    fn read_be(&mut self, len: usize) -> Option<u64> {
+        let bytes = self.read_bytes(len)?;
+        Some(bytes.iter().fold(0u64, |acc, b| (acc << 8) | *b as u64))
+    }
+
+    fn read_bytes(&mut self, len: usize) -> Option<&'s [u8]> {
+        let result = self.data.get(self.pos..self.pos + len)?;
+        self.pos += len;
+        Some(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{MsgPackReader, MsgPackWriter};
+
+    #[test]
+    fn test_write_primitives() {
+        let mut writer = MsgPackWriter::new();
+        writer.write_uint(1);
+        writer.write_uint(300);
+        writer.write_str("abc");
+        writer.write_array_len(2);
+        writer.write_map_len(20);
+        writer.write_event_time(1_500_000);
+
+        assert_eq!(
+            vec![
+                0x01, 0xcd, 0x01, 0x2c, 0xa3, b'a', b'b', b'c', 0x92, 0xde, 0x00, 0x14, 0xd7, 0x00,
+                0x00, 0x00, 0x00, 0x01, 0x1d, 0xcd, 0x65, 0x00,
+            ],
+            writer.build()
+        );
+    }
+
+    #[test]
+    fn test_read_map_of_strings() {
+        let mut writer = MsgPackWriter::new();
+        writer.write_map_len(1);
+        writer.write_str("ack");
+        writer.write_str("chunk-id");
+        let data = writer.build();
+
+        let mut reader = MsgPackReader::new(&data);
+        assert_eq!(Some(1), reader.read_map_len());
+        assert_eq!(Some("ack"), reader.read_str());
+        assert_eq!(Some("chunk-id"), reader.read_str());
+
+        let mut reader = MsgPackReader::new(&data[..5]);
+        reader.read_map_len();
+        reader.read_str();
+        assert_eq!(None, reader.read_str());
+    }
+}
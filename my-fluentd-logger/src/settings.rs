@@ -0,0 +1,122 @@
+use std::time::Duration;
+
+use my_seq_logger::{parse_number, split_key_value};
+
+const DEFAULT_FLUSH_DELAY: u64 = 1;
+const DEFAULT_TIMEOUT: u64 = 10;
+const DEFAULT_BATCH_SIZE: usize = 1000;
+const DEFAULT_QUEUE_SIZE: usize = 10_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FluentdCompression {
+    None,
+    Gzip,
+}
+
+pub struct FluentdSettings {
+    /// host:port of the forward input, Fluent Bit and Fluentd listen on 24224.
+    pub addr: String,
+    pub tag_prefix: Option<String>,
+    pub compression: FluentdCompression,
+    /// Wait for `ack` of every chunk and resend it on failure (at-least-once delivery).
+    pub require_ack: bool,
+    /// Max entries in one PackedForward message.
+    pub batch_size: usize,
+    /// Max events kept while the input is unreachable. The oldest events are dropped first.
+    pub queue_size: usize,
+    /// Interval at which queued events are sent and not acknowledged chunks are resent.
+    pub flush_delay: Duration,
+    pub timeout: Duration,
+}
+
+impl FluentdSettings {
+    pub fn new(addr: String) -> Self {
+        Self {
+            addr,
+            tag_prefix: None,
+            compression: FluentdCompression::None,
+            require_ack: true,
+            batch_size: DEFAULT_BATCH_SIZE,
+            queue_size: DEFAULT_QUEUE_SIZE,
+            flush_delay: Duration::from_secs(DEFAULT_FLUSH_DELAY),
+            timeout: Duration::from_secs(DEFAULT_TIMEOUT),
+        }
+    }
+
+    /// `url=tcp://fluent-bit:24224;compression=gzip;ack=true;tagprefix=k8s`
+    pub fn try_parse(conn_string: &str) -> Result<Self, String> {
+        let mut addr = None;
+        let mut tag_prefix = None;
+        let mut compression = FluentdCompression::None;
+        let mut require_ack = true;
+        let mut batch_size = DEFAULT_BATCH_SIZE;
+        let mut queue_size = DEFAULT_QUEUE_SIZE;
+        let mut flush_delay = DEFAULT_FLUSH_DELAY;
+        let mut timeout = DEFAULT_TIMEOUT;
+
+        for item in conn_string.split(';') {
+            if item.is_empty() {
+                continue;
+            }
+
+            let (key, value) = split_key_value(item, "Fluentd connection string")?;
+
+            match key {
+                "url" => match value.strip_prefix("tcp://") {
+                    Some(value) => addr = Some(value.to_string()),
+                    None => return Err(format!("Invalid Fluentd url {}", value)),
+                },
+                "tagprefix" => {
+                    tag_prefix = Some(value.to_string());
+                }
+                "compression" => {
+                    compression = match value {
+                        "none" => FluentdCompression::None,
+                        "gzip" => FluentdCompression::Gzip,
+                        _ => return Err(format!("Invalid Fluentd compression {}", value)),
+                    };
+                }
+                "ack" => {
+                    require_ack = match value {
+                        "true" => true,
+                        "false" => false,
+                        _ => return Err(format!("Invalid ack value {}", value)),
+                    };
+                }
+                "batchsize" => {
+                    batch_size = parse_number(key, value)?;
+                }
+                "queuesize" => {
+                    queue_size = parse_number(key, value)?;
+                }
+                "flushdelay" => {
+                    flush_delay = parse_number(key, value)?;
+                }
+                "timeout" => {
+                    timeout = parse_number(key, value)?;
+                }
+                _ => {
+                    return Err(format!("Invalid key {} of Fluentd connection string", key));
+                }
+            }
+        }
+
+        let addr = match addr {
+            Some(addr) => addr,
+            None => {
+                return Err("There is no URL parameter in Fluentd connection string".to_string())
+            }
+        };
+
+        Ok(Self {
+            addr,
+            tag_prefix,
+            compression,
+            require_ack,
+            batch_size: batch_size.max(1),
+            queue_size,
+            flush_delay: Duration::from_secs(flush_delay),
+            timeout: Duration::from_secs(timeout),
+        })
+    }
+}
@@ -0,0 +1,112 @@
+use std::time::Duration;
+
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::TcpStream,
+};
+
+const READ_BUFFER_SIZE: usize = 256;
+
+pub struct FluentdConnection {
+    stream: TcpStream,
+    response: Vec<u8>,
+}
+
+impl FluentdConnection {
+    pub async fn connect(addr: &str, timeout: Duration) -> std::io::Result<Self> {
+        match tokio::time::timeout(timeout, TcpStream::connect(addr)).await {
+            Ok(stream) => Ok(Self {
+                stream: stream?,
+                response: Vec::new(),
+            }),
+            Err(_) => Err(std::io::ErrorKind::TimedOut.into()),
+        }
+    }
+
+    pub async fn send(&mut self, payload: &[u8]) -> std::io::Result<()> {
+        self.stream.write_all(payload).await?;
+        self.stream.flush().await
+    }
+
+    /// Reads the `{"ack": <chunk id>}` response. Any other chunk id is treated as an error.
+    pub async fn wait_ack(&mut self, chunk_id: &str, timeout: Duration) -> std::io::Result<()> {
+        let future = async {
+            let mut buffer = [0u8; READ_BUFFER_SIZE];
+
+            loop {
+                if let Some(ack) = crate::parse_ack_response(self.response.as_slice()) {
+                    self.response.clear();
+
+                    if ack == chunk_id {
+                        return Ok(());
+                    }
+
+                    return Err(std::io::Error::new(
+                        std::io::ErrorKind::InvalidData,
+                        format!("Expected ack {}, got {}", chunk_id, ack),
+                    ));
+                }
+
+                let read = self.stream.read(&mut buffer).await?;
+                if read == 0 {
+                    return Err(std::io::ErrorKind::UnexpectedEof.into());
+                }
+
+                self.response.extend_from_slice(&buffer[..read]);
+            }
+        };
+
+        match tokio::time::timeout(timeout, future).await {
+            Ok(result) => result,
+            Err(_) => Err(std::io::ErrorKind::TimedOut.into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpListener,
+    };
+
+    use crate::MsgPackWriter;
+
+    use super::FluentdConnection;
+
+    #[tokio::test]
+    async fn test_wait_ack() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap().to_string();
+
+        let server = tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut buffer = [0u8; 4];
+            stream.read_exact(&mut buffer).await.unwrap();
+
+            let mut writer = MsgPackWriter::new();
+            writer.write_map_len(1);
+            writer.write_str("ack");
+            writer.write_str("chunk-1");
+            let response = writer.build();
+
+            stream.write_all(&response[..3]).await.unwrap();
+            stream.flush().await.unwrap();
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            stream.write_all(&response[3..]).await.unwrap();
+        });
+
+        let mut connection = FluentdConnection::connect(&addr, Duration::from_secs(1))
+            .await
+            .unwrap();
+        connection.send(b"test").await.unwrap();
+        connection
+            .wait_ack("chunk-1", Duration::from_secs(1))
+            .await
+            .unwrap();
+
+        server.await.unwrap();
+    }
+}
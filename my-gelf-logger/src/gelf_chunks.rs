@@ -0,0 +1,102 @@
+use std::{
+    io::Write,
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+use crate::GelfCompression;
+
+const CHUNK_MAGIC: [u8; 2] = [0x1e, 0x0f];
+const CHUNK_HEADER_SIZE: usize = 12;
+const MAX_CHUNKS: usize = 128;
+
+static MESSAGE_ID_SEQUENCE: AtomicU64 = AtomicU64::new(0);
+
+pub fn compress_gelf_message(
+    compression: GelfCompression,
+    payload: &[u8],
+) -> std::io::Result<Vec<u8>> {
+    match compression {
+        GelfCompression::None => Ok(payload.to_vec()),
+        GelfCompression::Zlib => {
+            let mut encoder =
+                flate2::write::ZlibEncoder::new(Vec::new(), flate2::Compression::default());
+            encoder.write_all(payload)?;
+            encoder.finish()
+        }
+        GelfCompression::Gzip => {
+            let mut encoder =
+                flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+            encoder.write_all(payload)?;
+            encoder.finish()
+        }
+    }
+}
+
+fn next_message_id() -> [u8; 8] {
+    let sequence = MESSAGE_ID_SEQUENCE.fetch_add(1, Ordering::Relaxed);
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|itm| itm.as_nanos() as u64)
+        .unwrap_or_default();
+
+    (now ^ sequence.rotate_left(48)).to_be_bytes()
+}
+
+/// Splits a message into GELF UDP chunks. A message which fits into one datagram is sent as is.
+/// Returns None if the message needs more than 128 chunks, which Graylog would drop.
+pub fn split_gelf_chunks(message: Vec<u8>, chunk_size: usize) -> Option<Vec<Vec<u8>>> {
+    if message.len() <= chunk_size {
+        return Some(vec![message]);
+    }
+
+    let payload_size = chunk_size.saturating_sub(CHUNK_HEADER_SIZE).max(1);
+    let chunks_count = message.len().div_ceil(payload_size);
+
+    if chunks_count > MAX_CHUNKS {
+        return None;
+    }
+
+    let message_id = next_message_id();
+    let mut result = Vec::with_capacity(chunks_count);
+
+    for (index, payload) in message.chunks(payload_size).enumerate() {
+        let mut chunk = Vec::with_capacity(CHUNK_HEADER_SIZE + payload.len());
+        chunk.extend_from_slice(&CHUNK_MAGIC);
+        chunk.extend_from_slice(&message_id);
+        chunk.push(index as u8);
+        chunk.push(chunks_count as u8);
+        chunk.extend_from_slice(payload);
+        result.push(chunk);
+    }
+
+    Some(result)
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn test_small_message_is_not_chunked() {
+        let chunks = super::split_gelf_chunks(vec![1, 2, 3], 100).unwrap();
+        assert_eq!(vec![vec![1, 2, 3]], chunks);
+    }
+
+    #[test]
+    fn test_chunk_headers() {
+        let message: Vec<u8> = (0..30).collect();
+        let chunks = super::split_gelf_chunks(message, 22).unwrap();
+
+        assert_eq!(3, chunks.len());
+        for (index, chunk) in chunks.iter().enumerate() {
+            assert_eq!(&[0x1e, 0x0f], &chunk[..2]);
+            assert_eq!(&chunks[0][2..10], &chunk[2..10]);
+            assert_eq!(index as u8, chunk[10]);
+            assert_eq!(3, chunk[11]);
+        }
+        assert_eq!(12 + 10, chunks[2].len());
+    }
+
+    #[test]
+    fn test_too_many_chunks() {
+        assert!(super::split_gelf_chunks(vec![0; 200], 13).is_none());
+    }
+}
@@ -0,0 +1,101 @@
+use std::{sync::Arc, time::Duration};
+
+use crate::{GelfLoggerInner, GelfSettings};
+use my_logger_core::{MyLogEvent, MyLoggerReader};
+use rust_extensions::{events_loop::EventsLoop, AppStates};
+
+pub struct GelfLogger {
+    inner: Arc<GelfLoggerInner>,
+    events_loop: EventsLoop<()>,
+    app_states: Arc<AppStates>,
+}
+
+impl GelfLogger {
+    /// Plugs the GELF writer into `LOGGER`. Must be called inside a tokio runtime.
+    pub fn enable(settings: GelfSettings) -> Arc<Self> {
+        let flush_delay = settings.flush_delay;
+        let timeout = settings.timeout;
+
+        let result = Self {
+            app_states: AppStates::create_initialized().into(),
+            inner: Arc::new(GelfLoggerInner::new(settings)),
+            events_loop: EventsLoop::new("GelfLogger".to_string()).set_iteration_timeout(timeout),
+        };
+
+        result.events_loop.register_event_loop(result.inner.clone());
+
+        result
+            .events_loop
+            .start(result.app_states.clone(), my_logger_core::LOGGER.clone());
+
+        let gelf_logger = Arc::new(result);
+        my_seq_logger::start_flush_timer(Arc::downgrade(&gelf_logger), flush_delay, |logger| {
+            logger.events_loop.send(())
+        });
+        my_logger_core::LOGGER.plug_reader(gelf_logger.clone());
+        gelf_logger
+    }
+
+    pub fn enable_from_connection_string(conn_string: &str) -> Arc<Self> {
+        match GelfSettings::try_parse(conn_string) {
+            Ok(settings) => Self::enable(settings),
+            Err(err) => panic!("Can not parse GELF settings. Err: {}", err),
+        }
+    }
+}
+
+impl MyLoggerReader for GelfLogger {
+    fn write_log(&self, log_event: Arc<MyLogEvent>) {
+        self.inner.log_events.enqueue(log_event);
+    }
+
+    fn flush(&self, timeout: Duration) {
+        let inner = self.inner.clone();
+        my_seq_logger::flush_on_private_runtime("GELF", timeout, async move {
+            inner.send_queued_events().await
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{net::UdpSocket, sync::Arc, time::Duration};
+
+    use my_logger_core::{test_utils::create_test_event, LogLevel, MyLoggerReader};
+    use rust_extensions::{events_loop::EventsLoop, AppStates};
+
+    use crate::{GelfLoggerInner, GelfSettings};
+
+    use super::GelfLogger;
+
+    #[test]
+    fn test_flush_sends_queued_events() {
+        let server = UdpSocket::bind("127.0.0.1:0").unwrap();
+        server
+            .set_read_timeout(Some(Duration::from_secs(5)))
+            .unwrap();
+
+        let conn_string = format!(
+            "url=udp://{};compression=none",
+            server.local_addr().unwrap()
+        );
+        let gelf_logger = GelfLogger {
+            inner: Arc::new(GelfLoggerInner::new(
+                GelfSettings::try_parse(conn_string.as_str()).unwrap(),
+            )),
+            events_loop: EventsLoop::new("GelfLogger".to_string()),
+            app_states: AppStates::create_initialized().into(),
+        };
+
+        gelf_logger.write_log(Arc::new(create_test_event(
+            LogLevel::Info,
+            "Orders",
+            "Created",
+        )));
+        gelf_logger.flush(Duration::from_secs(5));
+
+        let mut buffer = [0u8; 2048];
+        let received = server.recv(&mut buffer).unwrap();
+        assert!(String::from_utf8_lossy(&buffer[..received]).contains("Created"));
+    }
+}
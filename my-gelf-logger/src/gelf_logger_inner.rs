@@ -0,0 +1,113 @@
+use my_seq_logger::{LogEventsQueue, PendingEvents, DEFAULT_PENDING_SIZE};
+use rust_extensions::events_loop::EventsLoopTick;
+use tokio::sync::Mutex;
+
+use crate::{GelfConnection, GelfSettings};
+
+const INITIAL_PAYLOAD_CAPACITY: usize = 1024;
+
+struct GelfWriterState {
+    connection: Option<GelfConnection>,
+    /// Events which are not delivered yet. Kept while the input is unreachable.
+    pending: PendingEvents,
+}
+
+pub struct GelfLoggerInner {
+    pub(crate) log_events: LogEventsQueue,
+    settings: GelfSettings,
+    state: Mutex<GelfWriterState>,
+}
+
+impl GelfLoggerInner {
+    pub fn new(settings: GelfSettings) -> Self {
+        let mut log_events = LogEventsQueue::new();
+        if let Some(queue_size) = settings.queue_size {
+            log_events.configure_size(queue_size);
+        }
+
+        let pending = PendingEvents::new(settings.queue_size.unwrap_or(DEFAULT_PENDING_SIZE));
+
+        Self {
+            log_events,
+            settings,
+            state: Mutex::new(GelfWriterState {
+                connection: None,
+                pending,
+            }),
+        }
+    }
+
+    /// Sends the queued events and the ones left from the previous attempts.
+    pub async fn send_queued_events(&self) {
+        let mut state = self.state.lock().await;
+
+        if let Some(events) = self.log_events.dequeue() {
+            state.pending.add(events);
+        }
+
+        if state.pending.is_empty() {
+            return;
+        }
+
+        if state.connection.is_none() {
+            match GelfConnection::connect(&self.settings.transport, self.settings.timeout).await {
+                Ok(result) => state.connection = Some(result),
+                Err(err) => {
+                    eprintln!(
+                        "Can not connect to GELF input {:?}. Err: {:?}",
+                        self.settings.transport, err
+                    );
+                    return;
+                }
+            }
+        }
+
+        state
+            .pending
+            .report_dropped(format_args!("GELF input {:?}", self.settings.transport));
+
+        let populated_params = my_logger_core::LOGGER.get_populated_params();
+        let mut payload = String::with_capacity(INITIAL_PAYLOAD_CAPACITY);
+        let state = &mut *state;
+
+        while let Some(log_event) = state.pending.front() {
+            payload = crate::serialize_gelf_message(
+                payload,
+                log_event,
+                &populated_params,
+                self.settings.hostname.as_str(),
+            );
+
+            let result = state
+                .connection
+                .as_mut()
+                .unwrap()
+                .send(payload.as_bytes(), &self.settings)
+                .await;
+
+            if let Err(err) = result {
+                eprintln!("Error while sending logs to GELF input. Err: {:?}", err);
+                state.connection = None;
+                return;
+            }
+
+            state.pending.pop_front();
+        }
+
+        if let Err(err) = state.connection.as_mut().unwrap().flush().await {
+            eprintln!("Error while sending logs to GELF input. Err: {:?}", err);
+            state.connection = None;
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl EventsLoopTick<()> for GelfLoggerInner {
+    async fn started(&self) {
+        println!("GELF writer is started");
+    }
+    async fn tick(&self, _: ()) {
+        self.send_queued_events().await;
+    }
+    async fn finished(&self) {}
+}
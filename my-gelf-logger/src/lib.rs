@@ -0,0 +1,12 @@
+mod gelf_logger;
+pub use gelf_logger::GelfLogger;
+mod gelf_logger_inner;
+pub use gelf_logger_inner::*;
+mod settings;
+pub use settings::*;
+mod serializer;
+pub use serializer::*;
+mod gelf_chunks;
+pub use gelf_chunks::*;
+mod transport;
+pub use transport::*;
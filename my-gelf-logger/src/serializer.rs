@@ -0,0 +1,123 @@
+use my_json::json_writer::{JsonObjectWriter, RawJsonObject};
+use my_logger_core::{LogLevel, MyLogEvent, PopulatedParams};
+use my_seq_logger::{format_seq_string, format_value};
+
+const MAX_SHORT_MESSAGE_LEN: usize = 250;
+
+pub fn get_gelf_level(level: LogLevel) -> u8 {
+    match level {
+        LogLevel::FatalError => 2,
+        LogLevel::Error => 3,
+        LogLevel::Warning => 4,
+        LogLevel::Info => 6,
+        LogLevel::Debug => 7,
+        LogLevel::Trace => 7,
+    }
+}
+
+/// Additional field names must match `^[\w\.\-]*$` and `_id` is reserved.
+pub fn get_additional_field_name(src: &str) -> String {
+    let mut result = String::with_capacity(src.len() + 1);
+    result.push('_');
+
+    for c in src.chars() {
+        if c.is_alphanumeric() || matches!(c, '_' | '.' | '-') {
+            result.push(c);
+        } else {
+            result.push('_');
+        }
+    }
+
+    if result == "_id" {
+        result.push('_');
+    }
+
+    result
+}
+
+/// `short_message` is the first line of the message, the whole message goes to `full_message`
+/// when it is multi-line or too long.
+fn get_short_message(message: &str) -> (&str, bool) {
+    let first_line = message.lines().next().unwrap_or_default();
+
+    if first_line.len() <= MAX_SHORT_MESSAGE_LEN {
+        return (first_line, first_line.len() != message.len());
+    }
+
+    let mut end = MAX_SHORT_MESSAGE_LEN;
+    while !first_line.is_char_boundary(end) {
+        end -= 1;
+    }
+
+    (&first_line[..end], true)
+}
+
+pub fn serialize_gelf_message(
+    mut compile_buffer: String,
+    log_event: &MyLogEvent,
+    populated_params: &PopulatedParams,
+    hostname: &str,
+) -> String {
+    compile_buffer.clear();
+
+    let (short_message, has_full_message) = get_short_message(log_event.message.as_str());
+    let timestamp = format!(
+        "{}.{:06}",
+        log_event.dt.unix_microseconds.div_euclid(1_000_000),
+        log_event.dt.unix_microseconds.rem_euclid(1_000_000)
+    );
+    let level = get_gelf_level(log_event.level).to_string();
+
+    let mut json_writer = JsonObjectWriter::from_string(compile_buffer)
+        .write("version", "1.1")
+        .write("host", hostname)
+        .write("short_message", format_seq_string(short_message).as_str());
+
+    if has_full_message {
+        json_writer = json_writer.write(
+            "full_message",
+            format_seq_string(log_event.message.as_str()).as_str(),
+        );
+    }
+
+    json_writer = json_writer
+        .write("timestamp", RawJsonObject::AsStr(timestamp.as_str()))
+        .write("level", RawJsonObject::AsStr(level.as_str()))
+        .write(
+            "_process",
+            format_seq_string(log_event.process.as_str()).as_str(),
+        );
+
+    for (key, value) in populated_params.iter() {
+        json_writer = json_writer.write(
+            get_additional_field_name(key).as_str(),
+            format_seq_string(value).as_str(),
+        );
+    }
+
+    if let Some(ctx) = &log_event.context {
+        for (key, value) in ctx {
+            json_writer = json_writer.write(
+                get_additional_field_name(key).as_str(),
+                format_value(value).as_str(),
+            );
+        }
+    }
+
+    json_writer.build()
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn test_additional_field_name() {
+        assert_eq!("_Client_Id", super::get_additional_field_name("Client Id"));
+        assert_eq!("_id_", super::get_additional_field_name("id"));
+    }
+
+    #[test]
+    fn test_short_message() {
+        assert_eq!(("one line", false), super::get_short_message("one line"));
+        assert_eq!(("first", true), super::get_short_message("first\nsecond"));
+    }
+}
@@ -0,0 +1,123 @@
+use std::time::Duration;
+
+use my_seq_logger::{parse_number, split_key_value};
+
+const DEFAULT_FLUSH_DELAY: u64 = 1;
+const DEFAULT_TIMEOUT: u64 = 10;
+/// Chunk size which fits into a datagram on most networks (WAN friendly value recommended by Graylog).
+pub const DEFAULT_CHUNK_SIZE: usize = 1420;
+
+#[derive(Debug, Clone)]
+pub enum GelfTransport {
+    Udp(String),
+    Tcp(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GelfCompression {
+    None,
+    Zlib,
+    Gzip,
+}
+
+pub struct GelfSettings {
+    pub transport: GelfTransport,
+    /// Applied to UDP only, TCP messages are always sent uncompressed.
+    pub compression: GelfCompression,
+    pub chunk_size: usize,
+    pub hostname: String,
+    pub queue_size: Option<usize>,
+    /// Interval at which queued events are sent.
+    pub flush_delay: Duration,
+    pub timeout: Duration,
+}
+
+impl GelfSettings {
+    pub fn new(transport: GelfTransport) -> Self {
+        Self {
+            transport,
+            compression: GelfCompression::Gzip,
+            chunk_size: DEFAULT_CHUNK_SIZE,
+            hostname: get_default_hostname(),
+            queue_size: None,
+            flush_delay: Duration::from_secs(DEFAULT_FLUSH_DELAY),
+            timeout: Duration::from_secs(DEFAULT_TIMEOUT),
+        }
+    }
+
+    /// `url=udp://graylog:12201;compression=gzip;chunksize=1420` or `url=tcp://graylog:12201`
+    pub fn try_parse(conn_string: &str) -> Result<Self, String> {
+        let mut transport = None;
+        let mut compression = GelfCompression::Gzip;
+        let mut chunk_size = DEFAULT_CHUNK_SIZE;
+        let mut hostname = None;
+        let mut queue_size = None;
+        let mut flush_delay = DEFAULT_FLUSH_DELAY;
+        let mut timeout = DEFAULT_TIMEOUT;
+
+        for item in conn_string.split(';') {
+            if item.is_empty() {
+                continue;
+            }
+
+            let (key, value) = split_key_value(item, "GELF connection string")?;
+
+            match key {
+                "url" => {
+                    transport = if let Some(addr) = value.strip_prefix("udp://") {
+                        Some(GelfTransport::Udp(addr.to_string()))
+                    } else if let Some(addr) = value.strip_prefix("tcp://") {
+                        Some(GelfTransport::Tcp(addr.to_string()))
+                    } else {
+                        return Err(format!("Invalid GELF url {}", value));
+                    };
+                }
+                "compression" => {
+                    compression = match value {
+                        "none" => GelfCompression::None,
+                        "zlib" => GelfCompression::Zlib,
+                        "gzip" => GelfCompression::Gzip,
+                        _ => return Err(format!("Invalid GELF compression {}", value)),
+                    };
+                }
+                "chunksize" => {
+                    chunk_size = parse_number(key, value)?;
+                }
+                "hostname" => {
+                    hostname = Some(value.to_string());
+                }
+                "queuesize" => {
+                    queue_size = Some(parse_number(key, value)?);
+                }
+                "flushdelay" => {
+                    flush_delay = parse_number(key, value)?;
+                }
+                "timeout" => {
+                    timeout = parse_number(key, value)?;
+                }
+                _ => {
+                    return Err(format!("Invalid key {} of GELF connection string", key));
+                }
+            }
+        }
+
+        let transport = match transport {
+            Some(transport) => transport,
+            None => return Err("There is no URL parameter in GELF connection string".to_string()),
+        };
+
+        Ok(Self {
+            transport,
+            compression,
+            chunk_size,
+            hostname: hostname.unwrap_or_else(get_default_hostname),
+            queue_size,
+            flush_delay: Duration::from_secs(flush_delay),
+            timeout: Duration::from_secs(timeout),
+        })
+    }
+}
+
+fn get_default_hostname() -> String {
+    my_logger_core::get_hostname().unwrap_or_else(|| "unknown".to_string())
+}
@@ -0,0 +1,135 @@
+use std::time::Duration;
+
+use tokio::{
+    io::AsyncWriteExt,
+    net::{TcpStream, UdpSocket},
+};
+
+use crate::{GelfSettings, GelfTransport};
+
+pub enum GelfConnection {
+    Udp(UdpSocket),
+    Tcp(TcpStream),
+}
+
+impl GelfConnection {
+    pub async fn connect(transport: &GelfTransport, timeout: Duration) -> std::io::Result<Self> {
+        let future = async {
+            match transport {
+                GelfTransport::Udp(addr) => {
+                    let socket = UdpSocket::bind("0.0.0.0:0").await?;
+                    socket.connect(addr.as_str()).await?;
+                    Ok(Self::Udp(socket))
+                }
+                GelfTransport::Tcp(addr) => {
+                    let stream = TcpStream::connect(addr.as_str()).await?;
+                    Ok(Self::Tcp(stream))
+                }
+            }
+        };
+
+        match tokio::time::timeout(timeout, future).await {
+            Ok(result) => result,
+            Err(_) => Err(std::io::ErrorKind::TimedOut.into()),
+        }
+    }
+
+    /// UDP messages are compressed and chunked, TCP messages are null-byte delimited.
+    pub async fn send(&mut self, message: &[u8], settings: &GelfSettings) -> std::io::Result<()> {
+        match self {
+            GelfConnection::Udp(socket) => {
+                let payload = crate::compress_gelf_message(settings.compression, message)?;
+
+                let chunks = match crate::split_gelf_chunks(payload, settings.chunk_size) {
+                    Some(chunks) => chunks,
+                    None => {
+                        eprintln!(
+                            "GELF message is too big to be sent over UDP. Len: {}",
+                            message.len()
+                        );
+                        return Ok(());
+                    }
+                };
+
+                for chunk in chunks {
+                    socket.send(chunk.as_slice()).await?;
+                }
+            }
+            GelfConnection::Tcp(stream) => {
+                stream.write_all(message).await?;
+                stream.write_all(&[0]).await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    pub async fn flush(&mut self) -> std::io::Result<()> {
+        if let GelfConnection::Tcp(stream) = self {
+            stream.flush().await?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{io::Read, time::Duration};
+
+    use tokio::{
+        io::AsyncReadExt,
+        net::{TcpListener, UdpSocket},
+    };
+
+    use crate::{GelfCompression, GelfSettings, GelfTransport};
+
+    use super::GelfConnection;
+
+    #[tokio::test]
+    async fn test_udp_message_is_compressed() {
+        let listener = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let mut settings = GelfSettings::new(GelfTransport::Udp(
+            listener.local_addr().unwrap().to_string(),
+        ));
+        settings.compression = GelfCompression::Zlib;
+
+        let mut connection = GelfConnection::connect(&settings.transport, Duration::from_secs(1))
+            .await
+            .unwrap();
+        connection
+            .send(b"{\"version\":\"1.1\"}", &settings)
+            .await
+            .unwrap();
+
+        let mut buffer = [0u8; 1024];
+        let len = listener.recv(&mut buffer).await.unwrap();
+
+        let mut decoder = flate2::read::ZlibDecoder::new(&buffer[..len]);
+        let mut result = String::new();
+        decoder.read_to_string(&mut result).unwrap();
+        assert_eq!("{\"version\":\"1.1\"}", result);
+    }
+
+    #[tokio::test]
+    async fn test_tcp_messages_are_null_delimited() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let settings = GelfSettings::new(GelfTransport::Tcp(
+            listener.local_addr().unwrap().to_string(),
+        ));
+
+        let mut connection = GelfConnection::connect(&settings.transport, Duration::from_secs(1))
+            .await
+            .unwrap();
+        let (mut stream, _) = listener.accept().await.unwrap();
+
+        connection.send(b"{}", &settings).await.unwrap();
+        connection.send(b"{}", &settings).await.unwrap();
+        connection.flush().await.unwrap();
+        drop(connection);
+
+        let mut result = Vec::new();
+        stream.read_to_end(&mut result).await.unwrap();
+        assert_eq!(b"{}\0{}\0".to_vec(), result);
+    }
+}
@@ -0,0 +1,172 @@
+use my_logger_core::{LogLevel, MyLogEvent, PopulatedParams};
+
+const MAX_FIELD_NAME_LEN: usize = 64;
+const APPLICATION_KEY: &str = "Application";
+
+/// Fields written from the event itself. Populated params and context keys mapping to one of them
+/// get [`USER_FIELD_PREFIX`], so the journal never has two values of a reserved field.
+const RESERVED_FIELDS: [&str; 5] = [
+    "PRIORITY",
+    "SYSLOG_IDENTIFIER",
+    "MESSAGE",
+    "PROCESS",
+    "LEVEL",
+];
+const USER_FIELD_PREFIX: &str = "USER_";
+
+pub fn get_journal_priority(level: LogLevel) -> u8 {
+    match level {
+        LogLevel::FatalError => 2,
+        LogLevel::Error => 3,
+        LogLevel::Warning => 4,
+        LogLevel::Info => 6,
+        LogLevel::Debug => 7,
+        LogLevel::Trace => 7,
+    }
+}
+
+/// Journal field names are uppercase ASCII letters, digits and underscores,
+/// must not start with a digit or underscore (those are trusted fields) and are at most 64 bytes.
+pub fn get_journal_field_name(src: &str) -> Option<String> {
+    let mut result = String::with_capacity(src.len());
+
+    for c in src.chars() {
+        if c.is_ascii_alphanumeric() {
+            result.push(c.to_ascii_uppercase());
+        } else {
+            result.push('_');
+        }
+    }
+
+    let result = result.trim_start_matches(|c: char| c == '_' || c.is_ascii_digit());
+
+    if result.is_empty() {
+        return None;
+    }
+
+    Some(result.chars().take(MAX_FIELD_NAME_LEN).collect())
+}
+
+/// Field name of a populated param or context key.
+pub fn get_user_field_name(src: &str) -> Option<String> {
+    let name = get_journal_field_name(src)?;
+
+    if !RESERVED_FIELDS.contains(&name.as_str()) {
+        return Some(name);
+    }
+
+    Some(
+        format!("{}{}", USER_FIELD_PREFIX, name)
+            .chars()
+            .take(MAX_FIELD_NAME_LEN)
+            .collect(),
+    )
+}
+
+/// Values without line breaks are written as `NAME=value\n`, others in the binary-safe form
+/// `NAME\n<u64 LE length><value>\n`.
+pub fn write_journal_field(out: &mut Vec<u8>, name: &str, value: &[u8]) {
+    out.extend_from_slice(name.as_bytes());
+
+    if value.contains(&b'\n') {
+        out.push(b'\n');
+        out.extend_from_slice(&(value.len() as u64).to_le_bytes());
+    } else {
+        out.push(b'=');
+    }
+
+    out.extend_from_slice(value);
+    out.push(b'\n');
+}
+
+pub fn serialize_journal_entry(
+    mut out: Vec<u8>,
+    log_event: &MyLogEvent,
+    populated_params: &PopulatedParams,
+    syslog_identifier: Option<&str>,
+) -> Vec<u8> {
+    out.clear();
+
+    let priority = get_journal_priority(log_event.level).to_string();
+    write_journal_field(&mut out, "PRIORITY", priority.as_bytes());
+
+    let syslog_identifier = match syslog_identifier {
+        Some(value) => value,
+        None => populated_params
+            .get(APPLICATION_KEY)
+            .unwrap_or(log_event.process.as_str()),
+    };
+    write_journal_field(&mut out, "SYSLOG_IDENTIFIER", syslog_identifier.as_bytes());
+    write_journal_field(&mut out, "MESSAGE", log_event.message.as_bytes());
+    write_journal_field(&mut out, "PROCESS", log_event.process.as_bytes());
+    write_journal_field(&mut out, "LEVEL", log_event.level.as_str().as_bytes());
+
+    for (key, value) in populated_params.iter() {
+        if let Some(name) = get_user_field_name(key) {
+            write_journal_field(&mut out, name.as_str(), value.as_bytes());
+        }
+    }
+
+    if let Some(ctx) = &log_event.context {
+        for (key, value) in ctx {
+            if let Some(name) = get_user_field_name(key) {
+                write_journal_field(&mut out, name.as_str(), value.as_bytes());
+            }
+        }
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use my_logger_core::{test_utils::create_test_event, LogLevel, PopulatedParams};
+
+    #[test]
+    fn test_field_name() {
+        assert_eq!(Some("CLIENT_ID".to_string()), super::get_journal_field_name("client-id"));
+        assert_eq!(Some("TRACE".to_string()), super::get_journal_field_name("_1trace"));
+        assert_eq!(None, super::get_journal_field_name("__"));
+    }
+
+    #[test]
+    fn test_reserved_field_names_are_prefixed() {
+        let mut log_event = create_test_event(LogLevel::Info, "Orders", "Created");
+        for key in ["message", "priority", "syslog_identifier", "OrderId"] {
+            log_event.add_context_if_missing(key, || "user value".to_string());
+        }
+
+        let populated_params = PopulatedParams::new(vec![("Level", "prod".into())]);
+        let entry = super::serialize_journal_entry(Vec::new(), &log_event, &populated_params, None);
+        let entry = String::from_utf8(entry).unwrap();
+        let names: Vec<&str> = entry
+            .lines()
+            .map(|line| line.split('=').next().unwrap())
+            .collect();
+
+        for name in ["MESSAGE", "PRIORITY", "SYSLOG_IDENTIFIER", "LEVEL"] {
+            assert_eq!(1, names.iter().filter(|item| **item == name).count());
+        }
+
+        assert!(entry.contains("MESSAGE=Created\n"));
+        assert!(entry.contains("LEVEL=Info\n"));
+        assert!(entry.contains("USER_MESSAGE=user value\n"));
+        assert!(entry.contains("USER_PRIORITY=user value\n"));
+        assert!(entry.contains("USER_SYSLOG_IDENTIFIER=user value\n"));
+        assert!(entry.contains("USER_LEVEL=prod\n"));
+        assert!(entry.contains("ORDERID=user value\n"));
+    }
+
+    #[test]
+    fn test_multiline_value_is_length_prefixed() {
+        let mut out = Vec::new();
+        super::write_journal_field(&mut out, "MESSAGE", b"a\nb");
+        super::write_journal_field(&mut out, "PRIORITY", b"3");
+
+        let mut expected = b"MESSAGE\n".to_vec();
+        expected.extend_from_slice(&3u64.to_le_bytes());
+        expected.extend_from_slice(b"a\nb\nPRIORITY=3\n");
+
+        assert_eq!(expected, out);
+    }
+}
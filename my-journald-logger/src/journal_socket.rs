@@ -0,0 +1,106 @@
+use std::{
+    fs::File,
+    io::Write,
+    os::{
+        fd::{AsRawFd, FromRawFd, RawFd},
+        unix::net::UnixDatagram,
+    },
+};
+
+pub struct JournalSocket {
+    socket: UnixDatagram,
+}
+
+impl JournalSocket {
+    pub fn connect(socket_path: &str) -> std::io::Result<Self> {
+        let socket = UnixDatagram::unbound()?;
+        socket.connect(socket_path)?;
+        Ok(Self { socket })
+    }
+
+    /// Sends an entry as a single datagram. Entries that don't fit into a datagram
+    /// are written into a sealed memfd which is passed to journald instead.
+    pub fn send(&self, entry: &[u8]) -> std::io::Result<()> {
+        match self.socket.send(entry) {
+            Ok(_) => Ok(()),
+            Err(err) => match err.raw_os_error() {
+                Some(libc::EMSGSIZE) | Some(libc::ENOBUFS) => self.send_via_memfd(entry),
+                _ => Err(err),
+            },
+        }
+    }
+
+    fn send_via_memfd(&self, entry: &[u8]) -> std::io::Result<()> {
+        let memfd = create_sealed_memfd(entry)?;
+        send_fd(self.socket.as_raw_fd(), memfd.as_raw_fd())
+    }
+}
+
+fn create_sealed_memfd(data: &[u8]) -> std::io::Result<File> {
+    let fd = unsafe {
+        libc::memfd_create(
+            c"my-logger-journal".as_ptr(),
+            libc::MFD_ALLOW_SEALING | libc::MFD_CLOEXEC,
+        )
+    };
+
+    if fd < 0 {
+        return Err(std::io::Error::last_os_error());
+    }
+
+    let mut file = unsafe { File::from_raw_fd(fd) };
+    file.write_all(data)?;
+
+    let seals = libc::F_SEAL_SHRINK | libc::F_SEAL_GROW | libc::F_SEAL_WRITE | libc::F_SEAL_SEAL;
+    if unsafe { libc::fcntl(fd, libc::F_ADD_SEALS, seals) } < 0 {
+        return Err(std::io::Error::last_os_error());
+    }
+
+    Ok(file)
+}
+
+fn send_fd(socket: RawFd, fd: RawFd) -> std::io::Result<()> {
+    let space = unsafe { libc::CMSG_SPACE(std::mem::size_of::<RawFd>() as u32) } as usize;
+    let mut control = vec![0u8; space];
+
+    let mut msg: libc::msghdr = unsafe { std::mem::zeroed() };
+    msg.msg_control = control.as_mut_ptr() as *mut libc::c_void;
+    msg.msg_controllen = space as _;
+
+    unsafe {
+        let cmsg = libc::CMSG_FIRSTHDR(&msg);
+        (*cmsg).cmsg_level = libc::SOL_SOCKET;
+        (*cmsg).cmsg_type = libc::SCM_RIGHTS;
+        (*cmsg).cmsg_len = libc::CMSG_LEN(std::mem::size_of::<RawFd>() as u32) as _;
+        std::ptr::write_unaligned(libc::CMSG_DATA(cmsg) as *mut RawFd, fd);
+    }
+
+    if unsafe { libc::sendmsg(socket, &msg, 0) } < 0 {
+        return Err(std::io::Error::last_os_error());
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::os::unix::net::UnixDatagram;
+
+    use super::JournalSocket;
+
+    #[test]
+    fn test_send_datagram() {
+        let path = std::env::temp_dir().join(format!("my-journald-test-{}", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let listener = UnixDatagram::bind(&path).unwrap();
+
+        let socket = JournalSocket::connect(path.to_str().unwrap()).unwrap();
+        socket.send(b"MESSAGE=test\n").unwrap();
+
+        let mut buffer = [0u8; 64];
+        let len = listener.recv(&mut buffer).unwrap();
+        assert_eq!(b"MESSAGE=test\n", &buffer[..len]);
+
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,104 @@
+use std::{sync::Arc, time::Duration};
+
+use crate::{JournaldLoggerInner, JournaldSettings};
+use my_logger_core::{MyLogEvent, MyLoggerReader};
+use rust_extensions::{events_loop::EventsLoop, AppStates};
+
+pub struct JournaldLogger {
+    inner: Arc<JournaldLoggerInner>,
+    events_loop: EventsLoop<()>,
+    app_states: Arc<AppStates>,
+}
+
+impl JournaldLogger {
+    /// Plugs the journald writer into `LOGGER`. Must be called inside a tokio runtime.
+    pub fn enable(settings: JournaldSettings) -> Arc<Self> {
+        let flush_delay = settings.flush_delay;
+
+        let result = Self {
+            app_states: AppStates::create_initialized().into(),
+            inner: Arc::new(JournaldLoggerInner::new(settings)),
+            events_loop: EventsLoop::new("JournaldLogger".to_string()),
+        };
+
+        result.events_loop.register_event_loop(result.inner.clone());
+
+        result
+            .events_loop
+            .start(result.app_states.clone(), my_logger_core::LOGGER.clone());
+
+        let journald_logger = Arc::new(result);
+        my_seq_logger::start_flush_timer(Arc::downgrade(&journald_logger), flush_delay, |logger| {
+            logger.events_loop.send(())
+        });
+        my_logger_core::LOGGER.plug_reader(journald_logger.clone());
+        journald_logger
+    }
+
+    /// systemd sets JOURNAL_STREAM for services whose stdout/stderr are connected to the journal.
+    pub fn is_running_under_journald() -> bool {
+        std::env::var("JOURNAL_STREAM").is_ok()
+            && std::path::Path::new(crate::DEFAULT_JOURNAL_SOCKET_PATH).exists()
+    }
+}
+
+impl MyLoggerReader for JournaldLogger {
+    fn write_log(&self, log_event: Arc<MyLogEvent>) {
+        self.inner.log_events.enqueue(log_event);
+    }
+
+    fn flush(&self, timeout: Duration) {
+        let inner = self.inner.clone();
+        my_seq_logger::flush_on_private_runtime("Journald", timeout, async move {
+            inner.send_queued_events().await
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{os::unix::net::UnixDatagram, sync::Arc, time::Duration};
+
+    use my_logger_core::{test_utils::create_test_event, LogLevel, MyLoggerReader};
+    use rust_extensions::{events_loop::EventsLoop, AppStates};
+
+    use crate::{JournaldLoggerInner, JournaldSettings};
+
+    use super::JournaldLogger;
+
+    #[test]
+    fn test_flush_sends_queued_events() {
+        let socket_path = std::env::temp_dir().join(format!(
+            "my-journald-logger-flush-test-{}.socket",
+            std::process::id()
+        ));
+        let _ = std::fs::remove_file(&socket_path);
+
+        let server = UnixDatagram::bind(&socket_path).unwrap();
+        server
+            .set_read_timeout(Some(Duration::from_secs(5)))
+            .unwrap();
+
+        let mut settings = JournaldSettings::new();
+        settings.socket_path = socket_path.to_str().unwrap().to_string();
+
+        let journald_logger = JournaldLogger {
+            inner: Arc::new(JournaldLoggerInner::new(settings)),
+            events_loop: EventsLoop::new("JournaldLogger".to_string()),
+            app_states: AppStates::create_initialized().into(),
+        };
+
+        journald_logger.write_log(Arc::new(create_test_event(
+            LogLevel::Info,
+            "Orders",
+            "Created",
+        )));
+        journald_logger.flush(Duration::from_secs(5));
+
+        let mut buffer = [0u8; 2048];
+        let received = server.recv(&mut buffer).unwrap();
+        assert!(String::from_utf8_lossy(&buffer[..received]).contains("MESSAGE=Created\n"));
+
+        std::fs::remove_file(&socket_path).unwrap();
+    }
+}
@@ -0,0 +1,119 @@
+use std::sync::Arc;
+
+use my_seq_logger::{LogEventsQueue, PendingEvents, DEFAULT_PENDING_SIZE};
+use parking_lot::Mutex;
+use rust_extensions::events_loop::EventsLoopTick;
+
+use crate::{JournalSocket, JournaldSettings};
+
+const INITIAL_ENTRY_CAPACITY: usize = 1024;
+
+struct JournaldWriterState {
+    socket: Option<JournalSocket>,
+    /// Events which are not delivered yet. Kept while the journal socket is unreachable.
+    pending: PendingEvents,
+}
+
+pub struct JournaldLoggerInner {
+    pub(crate) log_events: LogEventsQueue,
+    socket_path: String,
+    syslog_identifier: Option<String>,
+    state: Arc<Mutex<JournaldWriterState>>,
+    /// Held while a batch is sent, so a flush waits for the batch which is already in flight.
+    send_lock: tokio::sync::Mutex<()>,
+}
+
+impl JournaldLoggerInner {
+    pub fn new(settings: JournaldSettings) -> Self {
+        let mut log_events = LogEventsQueue::new();
+        if let Some(queue_size) = settings.queue_size {
+            log_events.configure_size(queue_size);
+        }
+
+        Self {
+            log_events,
+            socket_path: settings.socket_path,
+            syslog_identifier: settings.syslog_identifier,
+            state: Arc::new(Mutex::new(JournaldWriterState {
+                socket: None,
+                pending: PendingEvents::new(settings.queue_size.unwrap_or(DEFAULT_PENDING_SIZE)),
+            })),
+            send_lock: tokio::sync::Mutex::new(()),
+        }
+    }
+
+    /// Sends the queued events and the ones left from the previous attempts.
+    pub async fn send_queued_events(&self) {
+        let _send_lock = self.send_lock.lock().await;
+
+        let events = self.log_events.dequeue();
+
+        let populated_params = my_logger_core::LOGGER.get_populated_params();
+        let socket_path = self.socket_path.clone();
+        let syslog_identifier = self.syslog_identifier.clone();
+        let state = self.state.clone();
+
+        let result = tokio::task::spawn_blocking(move || {
+            let mut state = state.lock();
+
+            if let Some(events) = events {
+                state.pending.add(events);
+            }
+
+            if state.pending.is_empty() {
+                return Ok(());
+            }
+
+            if state.socket.is_none() {
+                state.socket = Some(JournalSocket::connect(socket_path.as_str())?);
+            }
+
+            state
+                .pending
+                .report_dropped(format_args!("journald {}", socket_path));
+
+            let state = &mut *state;
+            let mut entry = Vec::with_capacity(INITIAL_ENTRY_CAPACITY);
+
+            while let Some(log_event) = state.pending.front() {
+                entry = crate::serialize_journal_entry(
+                    entry,
+                    log_event,
+                    &populated_params,
+                    syslog_identifier.as_deref(),
+                );
+
+                if let Err(err) = state.socket.as_ref().unwrap().send(entry.as_slice()) {
+                    state.socket = None;
+                    return Err(err);
+                }
+
+                state.pending.pop_front();
+            }
+
+            Ok(())
+        })
+        .await;
+
+        match result {
+            Ok(Ok(())) => {}
+            Ok(Err(err)) => {
+                eprintln!("Error while sending logs to journald. Err: {:?}", err);
+            }
+            Err(err) => {
+                eprintln!("Error while sending logs to journald. Err: {:?}", err);
+            }
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl EventsLoopTick<()> for JournaldLoggerInner {
+    async fn started(&self) {
+        println!("Journald writer is started");
+    }
+    async fn tick(&self, _: ()) {
+        self.send_queued_events().await;
+    }
+    async fn finished(&self) {}
+}
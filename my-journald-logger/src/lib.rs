@@ -0,0 +1,10 @@
+mod journald_logger;
+pub use journald_logger::JournaldLogger;
+mod journald_logger_inner;
+pub use journald_logger_inner::*;
+mod settings;
+pub use settings::*;
+mod journal_fields;
+pub use journal_fields::*;
+mod journal_socket;
+pub use journal_socket::*;
@@ -0,0 +1,30 @@
+use std::time::Duration;
+
+pub const DEFAULT_JOURNAL_SOCKET_PATH: &str = "/run/systemd/journal/socket";
+const DEFAULT_FLUSH_DELAY: u64 = 1;
+
+pub struct JournaldSettings {
+    pub socket_path: String,
+    /// Overrides SYSLOG_IDENTIFIER. By default the `Application` populated param is used.
+    pub syslog_identifier: Option<String>,
+    pub queue_size: Option<usize>,
+    /// Interval at which queued events are sent.
+    pub flush_delay: Duration,
+}
+
+impl JournaldSettings {
+    pub fn new() -> Self {
+        Self {
+            socket_path: DEFAULT_JOURNAL_SOCKET_PATH.to_string(),
+            syslog_identifier: None,
+            queue_size: None,
+            flush_delay: Duration::from_secs(DEFAULT_FLUSH_DELAY),
+        }
+    }
+}
+
+impl Default for JournaldSettings {
+    fn default() -> Self {
+        Self::new()
+    }
+}
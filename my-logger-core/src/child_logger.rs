@@ -0,0 +1,199 @@
+use std::{collections::HashMap, sync::Arc};
+
+use rust_extensions::StrOrString;
+
+use crate::{LogEventCtx, LogLevel, MyLogger};
+
+/// Logger returned by [`MyLogger::child`]. Every event gets the bound process name and context;
+/// context passed to `*_with` methods is added on top and wins on key conflicts.
+///
+/// Cloning is cheap, so a child can be stored in every component instance.
+#[derive(Clone)]
+pub struct MyChildLogger {
+    logger: MyLogger,
+    process: Arc<str>,
+    context: Option<Arc<HashMap<String, String>>>,
+}
+
+impl MyChildLogger {
+    pub(crate) fn new(
+        logger: MyLogger,
+        process: String,
+        context: Option<HashMap<String, String>>,
+    ) -> Self {
+        Self {
+            logger,
+            process: process.into(),
+            context: context.map(Arc::new),
+        }
+    }
+
+    pub fn get_process(&self) -> &str {
+        &self.process
+    }
+
+    /// Child with the same process and the context extended by `ctx`.
+    pub fn with_context(&self, ctx: LogEventCtx) -> Self {
+        Self {
+            logger: self.logger.clone(),
+            process: self.process.clone(),
+            context: self.merge_context(ctx).map(Arc::new),
+        }
+    }
+
+    #[track_caller]
+    pub fn write<'s>(
+        &self,
+        level: LogLevel,
+        message: impl Into<StrOrString<'s>>,
+        ctx: LogEventCtx,
+    ) {
+        self.logger.write_log(
+            level,
+            self.process.to_string(),
+            message.into().to_string(),
+            self.merge_context(ctx),
+        );
+    }
+
+    #[track_caller]
+    pub fn info<'s>(&self, message: impl Into<StrOrString<'s>>) {
+        self.write(LogLevel::Info, message, LogEventCtx::new());
+    }
+
+    #[track_caller]
+    pub fn warning<'s>(&self, message: impl Into<StrOrString<'s>>) {
+        self.write(LogLevel::Warning, message, LogEventCtx::new());
+    }
+
+    #[track_caller]
+    pub fn error<'s>(&self, message: impl Into<StrOrString<'s>>) {
+        self.write(LogLevel::Error, message, LogEventCtx::new());
+    }
+
+    #[track_caller]
+    pub fn fatal_error<'s>(&self, message: impl Into<StrOrString<'s>>) {
+        self.write(LogLevel::FatalError, message, LogEventCtx::new());
+    }
+
+    #[track_caller]
+    pub fn debug<'s>(&self, message: impl Into<StrOrString<'s>>) {
+        self.write(LogLevel::Debug, message, LogEventCtx::new());
+    }
+
+    #[track_caller]
+    pub fn trace<'s>(&self, message: impl Into<StrOrString<'s>>) {
+        self.write(LogLevel::Trace, message, LogEventCtx::new());
+    }
+
+    #[track_caller]
+    pub fn info_with<'s>(&self, message: impl Into<StrOrString<'s>>, ctx: LogEventCtx) {
+        self.write(LogLevel::Info, message, ctx);
+    }
+
+    #[track_caller]
+    pub fn warning_with<'s>(&self, message: impl Into<StrOrString<'s>>, ctx: LogEventCtx) {
+        self.write(LogLevel::Warning, message, ctx);
+    }
+
+    #[track_caller]
+    pub fn error_with<'s>(&self, message: impl Into<StrOrString<'s>>, ctx: LogEventCtx) {
+        self.write(LogLevel::Error, message, ctx);
+    }
+
+    #[track_caller]
+    pub fn fatal_error_with<'s>(&self, message: impl Into<StrOrString<'s>>, ctx: LogEventCtx) {
+        self.write(LogLevel::FatalError, message, ctx);
+    }
+
+    #[track_caller]
+    pub fn debug_with<'s>(&self, message: impl Into<StrOrString<'s>>, ctx: LogEventCtx) {
+        self.write(LogLevel::Debug, message, ctx);
+    }
+
+    #[track_caller]
+    pub fn trace_with<'s>(&self, message: impl Into<StrOrString<'s>>, ctx: LogEventCtx) {
+        self.write(LogLevel::Trace, message, ctx);
+    }
+
+    fn merge_context(&self, ctx: LogEventCtx) -> Option<HashMap<String, String>> {
+        let ctx = ctx.get_result();
+
+        match (self.context.as_ref(), ctx) {
+            (None, ctx) => ctx,
+            (Some(bound), None) => Some(bound.as_ref().clone()),
+            (Some(bound), Some(ctx)) => {
+                let mut result = bound.as_ref().clone();
+                result.extend(ctx);
+                Some(result)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::test_utils::TestReader;
+    use crate::{LogEventCtx, LogLevel, MyLogger};
+
+    fn create_logger() -> (MyLogger, Arc<TestReader>) {
+        let logger = MyLogger::new();
+        logger.get_console_filter().set_print_infos(false);
+        logger.get_console_filter().set_print_warnings(false);
+
+        let reader = Arc::new(TestReader::default());
+        logger.plug_reader(reader.clone());
+        (logger, reader)
+    }
+
+    #[test]
+    fn test_child_binds_process_and_context() {
+        let (logger, reader) = create_logger();
+
+        let child = logger.child(
+            "Component",
+            LogEventCtx::new().add("ClientId", "1").add("Region", "eu"),
+        );
+        child.info("Started");
+        child.warning_with("Slow", LogEventCtx::new().add("RequestId", "42"));
+
+        let events = reader.get_events();
+        assert_eq!(2, events.len());
+
+        assert_eq!(LogLevel::Info, events[0].level);
+        assert_eq!("Component", events[0].process);
+        assert_eq!("Started", events[0].message);
+        let context = events[0].context.as_ref().unwrap();
+        assert_eq!(2, context.len());
+        assert_eq!("1", context["ClientId"]);
+
+        assert_eq!(LogLevel::Warning, events[1].level);
+        let context = events[1].context.as_ref().unwrap();
+        assert_eq!(3, context.len());
+        assert_eq!("eu", context["Region"]);
+        assert_eq!("42", context["RequestId"]);
+    }
+
+    #[test]
+    fn test_call_context_overrides_bound_context() {
+        let (logger, reader) = create_logger();
+
+        let child = logger.child("Component", LogEventCtx::new().add("ClientId", "1"));
+        let nested = child.with_context(LogEventCtx::new().add("ClientId", "2").add("Step", "a"));
+
+        nested.info_with("Override", LogEventCtx::new().add("Step", "b"));
+        child.info("Original");
+
+        let events = reader.get_events();
+
+        let context = events[0].context.as_ref().unwrap();
+        assert_eq!("2", context["ClientId"]);
+        assert_eq!("b", context["Step"]);
+
+        let context = events[1].context.as_ref().unwrap();
+        assert_eq!(1, context.len());
+        assert_eq!("1", context["ClientId"]);
+    }
+}
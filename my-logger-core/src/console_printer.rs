@@ -3,6 +3,7 @@ use std::sync::atomic::AtomicBool;
 use crate::{LogLevel, MyLogEvent};
 
 pub struct ConsoleFilter {
+    pub print_trace: AtomicBool,
     pub print_debug: AtomicBool,
     pub print_fatal_errors: AtomicBool,
     pub print_errors: AtomicBool,
@@ -13,6 +14,7 @@ pub struct ConsoleFilter {
 impl ConsoleFilter {
     pub fn new() -> Self {
         Self {
+            print_trace: true.into(),
             print_fatal_errors: true.into(),
             print_errors: true.into(),
             print_warnings: true.into(),
@@ -46,6 +48,21 @@ impl ConsoleFilter {
             .store(value, std::sync::atomic::Ordering::Relaxed);
     }
 
+    pub fn set_print_trace(&self, value: bool) {
+        self.print_trace
+            .store(value, std::sync::atomic::Ordering::Relaxed);
+    }
+
+    /// Prints only levels at least as severe as `level`.
+    pub fn set_min_level(&self, level: LogLevel) {
+        self.set_print_trace(LogLevel::Trace >= level);
+        self.set_print_debug(LogLevel::Debug >= level);
+        self.set_print_infos(LogLevel::Info >= level);
+        self.set_print_warnings(LogLevel::Warning >= level);
+        self.set_print_errors(LogLevel::Error >= level);
+        self.set_print_fatal_errors(LogLevel::FatalError >= level);
+    }
+
     pub fn print_to_console(&self, log_event: &MyLogEvent) {
         match log_event.level {
             LogLevel::Info => {
@@ -79,6 +96,11 @@ impl ConsoleFilter {
                     write_log(log_event);
                 }
             }
+            LogLevel::Trace => {
+                if self.print_trace.load(std::sync::atomic::Ordering::Relaxed) {
+                    write_log(log_event);
+                }
+            }
         }
     }
 }
@@ -98,6 +120,18 @@ fn write_log(log_event: &MyLogEvent) {
     );
     let _ = writeln!(&mut buf, "Process: {}", log_event.process);
     let _ = writeln!(&mut buf, "Message: {}", log_event.message);
+    match (log_event.module_path, log_event.get_source_location()) {
+        (Some(module_path), Some(location)) => {
+            let _ = writeln!(&mut buf, "Source: {} ({})", module_path, location);
+        }
+        (None, Some(location)) => {
+            let _ = writeln!(&mut buf, "Source: {}", location);
+        }
+        (Some(module_path), None) => {
+            let _ = writeln!(&mut buf, "Source: {}", module_path);
+        }
+        (None, None) => {}
+    }
     if let Some(ctx) = &log_event.context {
         let _ = writeln!(&mut buf, "Context: {:?}", ctx);
     }
@@ -0,0 +1,234 @@
+use std::{
+    sync::atomic::{AtomicU64, Ordering},
+    time::{Duration, Instant},
+};
+
+use parking_lot::Mutex;
+
+use crate::MyLogEvent;
+
+/// Adds context to every event written through the logger it is registered on
+/// ([`crate::MyLogger::add_enricher`]).
+///
+/// Enrichers run once per event, after filters and before the console and readers,
+/// so every reader gets the same enriched event.
+pub trait Enricher {
+    fn enrich(&self, log_event: &mut MyLogEvent);
+}
+
+/// `ThreadName` and `ThreadId` of the thread which wrote the event.
+pub struct ThreadEnricher;
+
+impl Enricher for ThreadEnricher {
+    fn enrich(&self, log_event: &mut MyLogEvent) {
+        let thread = std::thread::current();
+
+        if let Some(name) = thread.name() {
+            log_event.add_context_if_missing("ThreadName", || name.to_string());
+        }
+
+        // ThreadId only exposes its number through Debug: ThreadId(5)
+        log_event.add_context_if_missing("ThreadId", || {
+            format!("{:?}", thread.id())
+                .trim_start_matches("ThreadId(")
+                .trim_end_matches(')')
+                .to_string()
+        });
+    }
+}
+
+/// `TaskId` of the tokio task which wrote the event. Events written outside of a task are not changed.
+pub struct TokioTaskEnricher;
+
+impl Enricher for TokioTaskEnricher {
+    fn enrich(&self, log_event: &mut MyLogEvent) {
+        if let Some(task_id) = tokio::task::try_id() {
+            log_event.add_context_if_missing("TaskId", || task_id.to_string());
+        }
+    }
+}
+
+/// Host name from the `HOSTNAME`/`COMPUTERNAME` variables or `/etc/hostname`.
+pub fn get_hostname() -> Option<String> {
+    std::env::var("HOSTNAME")
+        .or_else(|_| std::env::var("COMPUTERNAME"))
+        .ok()
+        .or_else(|| std::fs::read_to_string("/etc/hostname").ok())
+        .map(|itm| itm.trim().to_string())
+        .filter(|itm| !itm.is_empty())
+}
+
+/// `Hostname` resolved once from the `HOSTNAME`/`COMPUTERNAME` variables or `/etc/hostname`.
+pub struct HostnameEnricher {
+    hostname: Option<String>,
+}
+
+impl HostnameEnricher {
+    pub fn new() -> Self {
+        Self {
+            hostname: get_hostname(),
+        }
+    }
+}
+
+impl Default for HostnameEnricher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Enricher for HostnameEnricher {
+    fn enrich(&self, log_event: &mut MyLogEvent) {
+        if let Some(hostname) = &self.hostname {
+            log_event.add_context_if_missing("Hostname", || hostname.clone());
+        }
+    }
+}
+
+/// `ProcessId` of the application.
+pub struct ProcessIdEnricher;
+
+impl Enricher for ProcessIdEnricher {
+    fn enrich(&self, log_event: &mut MyLogEvent) {
+        log_event.add_context_if_missing("ProcessId", || std::process::id().to_string());
+    }
+}
+
+/// `SequenceNumber` increasing by one with every event, to restore the order of events with the same timestamp.
+pub struct SequenceNumberEnricher {
+    sequence: AtomicU64,
+}
+
+impl SequenceNumberEnricher {
+    pub fn new() -> Self {
+        Self {
+            sequence: AtomicU64::new(0),
+        }
+    }
+}
+
+impl Default for SequenceNumberEnricher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Enricher for SequenceNumberEnricher {
+    fn enrich(&self, log_event: &mut MyLogEvent) {
+        let sequence = self.sequence.fetch_add(1, Ordering::Relaxed);
+        log_event.add_context_if_missing("SequenceNumber", || sequence.to_string());
+    }
+}
+
+const MEMORY_REFRESH_INTERVAL: Duration = Duration::from_secs(1);
+
+/// `ProcessRssKb` - resident memory of the process read from `/proc/self/status`,
+/// refreshed at most once a second. Does nothing on systems without procfs.
+pub struct ProcessMemoryEnricher {
+    last_read: Mutex<Option<(Instant, Option<u64>)>>,
+}
+
+impl ProcessMemoryEnricher {
+    pub fn new() -> Self {
+        Self {
+            last_read: Mutex::new(None),
+        }
+    }
+
+    fn get_rss_kb(&self) -> Option<u64> {
+        let mut last_read = self.last_read.lock();
+
+        if let Some((read_at, rss_kb)) = *last_read {
+            if read_at.elapsed() < MEMORY_REFRESH_INTERVAL {
+                return rss_kb;
+            }
+        }
+
+        let rss_kb = read_rss_kb();
+        *last_read = Some((Instant::now(), rss_kb));
+        rss_kb
+    }
+}
+
+impl Default for ProcessMemoryEnricher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Enricher for ProcessMemoryEnricher {
+    fn enrich(&self, log_event: &mut MyLogEvent) {
+        if let Some(rss_kb) = self.get_rss_kb() {
+            log_event.add_context_if_missing("ProcessRssKb", || rss_kb.to_string());
+        }
+    }
+}
+
+fn read_rss_kb() -> Option<u64> {
+    let status = std::fs::read_to_string("/proc/self/status").ok()?;
+    parse_rss_kb(status.as_str())
+}
+
+fn parse_rss_kb(status: &str) -> Option<u64> {
+    let line = status.lines().find(|line| line.starts_with("VmRSS:"))?;
+
+    line["VmRSS:".len()..]
+        .trim()
+        .trim_end_matches("kB")
+        .trim()
+        .parse()
+        .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::{Enricher, ProcessIdEnricher, SequenceNumberEnricher, ThreadEnricher};
+    use crate::test_utils::{create_test_event, TestReader};
+    use crate::{LogEventCtx, LogLevel, MyLogger};
+
+    #[test]
+    fn test_parse_rss() {
+        let status = "Name:\ttest\nVmPeak:\t  10000 kB\nVmRSS:\t    2048 kB\n";
+        assert_eq!(Some(2048), super::parse_rss_kb(status));
+        assert_eq!(None, super::parse_rss_kb("Name:\ttest\n"));
+    }
+
+    #[test]
+    fn test_enrichers_do_not_overwrite_context() {
+        let sequence = SequenceNumberEnricher::new();
+
+        let mut log_event = create_test_event(LogLevel::Info, "Test", "Test");
+        log_event.context = Some([("ThreadId".to_string(), "Custom".to_string())].into());
+
+        ThreadEnricher.enrich(&mut log_event);
+        sequence.enrich(&mut log_event);
+        sequence.enrich(&mut log_event);
+
+        let context = log_event.context.unwrap();
+        assert_eq!("Custom", context["ThreadId"]);
+        assert_eq!("0", context["SequenceNumber"]);
+    }
+
+    #[test]
+    fn test_readers_get_enriched_event() {
+        let logger = MyLogger::new();
+        logger.get_console_filter().set_print_infos(false);
+
+        let reader = Arc::new(TestReader::default());
+        logger.plug_reader(reader.clone());
+        logger.plug_reader(reader.clone());
+        logger.add_enricher(ProcessIdEnricher);
+
+        logger.write_info("Test", "Test", LogEventCtx::new());
+
+        let events = reader.get_events();
+        assert_eq!(2, events.len());
+        assert!(Arc::ptr_eq(&events[0], &events[1]));
+        assert_eq!(
+            std::process::id().to_string(),
+            events[0].context.as_ref().unwrap()["ProcessId"]
+        );
+    }
+}
@@ -1,21 +1,35 @@
+mod child_logger;
 mod console_printer;
+mod enrichers;
 mod log_event;
 mod log_event_ctx;
 mod log_readers;
 mod log_statistics;
 mod logger;
 mod logger_reader;
+mod measure_guard;
 mod my_logger_inner;
+mod panic_hook;
+mod span_guard;
+mod trace_context;
+pub use child_logger::*;
 pub use console_printer::*;
+pub use enrichers::*;
 pub use log_event::{LogLevel, MyLogEvent};
 pub use log_event_ctx::*;
 pub use log_readers::*;
 pub use log_statistics::*;
 pub use logger::MyLogger;
 pub use logger_reader::MyLoggerReader;
+pub use measure_guard::*;
 pub use my_logger_inner::*;
+pub use panic_hook::*;
+pub use span_guard::*;
+pub use trace_context::*;
 mod populated_params;
 pub use populated_params::*;
+#[cfg(any(test, feature = "test-utils"))]
+pub mod test_utils;
 use std::sync::{Arc, LazyLock};
 
 pub static LOGGER: LazyLock<Arc<MyLogger>> = LazyLock::new(|| Arc::new(MyLogger::new()));
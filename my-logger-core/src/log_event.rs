@@ -2,19 +2,35 @@ use std::collections::HashMap;
 
 use rust_extensions::date_time::DateTimeAsMicroseconds;
 
-#[derive(Debug, Clone, Copy)]
+use crate::{SpanInfo, TraceContext};
+
+/// Variants are declared from the least to the most severe, so levels can be compared
+/// with thresholds: `level >= LogLevel::Warning`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum LogLevel {
+    Trace,
+    Debug,
     Info,
     Warning,
     Error,
     FatalError,
-    Debug,
 }
 
 impl LogLevel {
+    pub const ALL: [LogLevel; 6] = [
+        LogLevel::Trace,
+        LogLevel::Debug,
+        LogLevel::Info,
+        LogLevel::Warning,
+        LogLevel::Error,
+        LogLevel::FatalError,
+    ];
+
     pub fn eq_to(&self, other: &Self) -> bool {
-        *self as u8 == *other as u8
+        self == other
     }
+
+    /// Stable numeric id of the level. It is not the severity order; compare levels with `<` and `>=` instead.
     pub fn to_u8(&self) -> u8 {
         match self {
             LogLevel::Info => 0,
@@ -22,6 +38,7 @@ impl LogLevel {
             LogLevel::Error => 2,
             LogLevel::FatalError => 3,
             LogLevel::Debug => 4,
+            LogLevel::Trace => 5,
         }
     }
 
@@ -31,35 +48,79 @@ impl LogLevel {
             2 => Self::Error,
             3 => Self::FatalError,
             4 => Self::Debug,
+            5 => Self::Trace,
             _ => Self::Info,
         }
     }
 
+    /// Case insensitive. Unknown values are parsed as Info; use `str::parse` to get an error instead.
+    #[allow(clippy::should_implement_trait)]
     pub fn from_str(src: &str) -> Self {
-        match src {
-            "Warning" => Self::Warning,
-            "Error" => Self::Error,
-            "FatalError" => Self::FatalError,
-            "Debug" => Self::Debug,
-            _ => Self::Info,
-        }
+        src.parse().unwrap_or(Self::Info)
     }
+
     pub fn as_str(&self) -> &'static str {
         match self {
+            LogLevel::Trace => "Trace",
+            LogLevel::Debug => "Debug",
             LogLevel::Info => "Info",
             LogLevel::Warning => "Warning",
             LogLevel::Error => "Error",
             LogLevel::FatalError => "FatalError",
-            LogLevel::Debug => "Debug",
         }
     }
 
     pub fn is_error_or_fatal_error(&self) -> bool {
-        match self {
-            LogLevel::Error => true,
-            LogLevel::FatalError => true,
-            _ => false,
-        }
+        *self >= LogLevel::Error
+    }
+}
+
+impl std::fmt::Display for LogLevel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Accepts level names in any case together with common aliases (`Verbose`, `Information`, `Warn`, `Fatal`).
+impl std::str::FromStr for LogLevel {
+    type Err = String;
+
+    fn from_str(src: &str) -> Result<Self, Self::Err> {
+        let src = src.trim();
+
+        let aliases: [(&str, LogLevel); 10] = [
+            ("Trace", LogLevel::Trace),
+            ("Verbose", LogLevel::Trace),
+            ("Debug", LogLevel::Debug),
+            ("Info", LogLevel::Info),
+            ("Information", LogLevel::Info),
+            ("Warning", LogLevel::Warning),
+            ("Warn", LogLevel::Warning),
+            ("Error", LogLevel::Error),
+            ("FatalError", LogLevel::FatalError),
+            ("Fatal", LogLevel::FatalError),
+        ];
+
+        aliases
+            .into_iter()
+            .find(|(alias, _)| alias.eq_ignore_ascii_case(src))
+            .map(|(_, level)| level)
+            .ok_or_else(|| format!("Invalid log level {}", src))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for LogLevel {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for LogLevel {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let src = <std::borrow::Cow<'de, str>>::deserialize(deserializer)?;
+        src.parse().map_err(serde::de::Error::custom)
     }
 }
 
@@ -70,4 +131,62 @@ pub struct MyLogEvent {
     pub process: String,
     pub message: String,
     pub context: Option<HashMap<String, String>>,
+    /// Source location of the call which wrote the event, if known.
+    pub file: Option<&'static str>,
+    pub line: Option<u32>,
+    pub module_path: Option<&'static str>,
+    pub trace_context: Option<TraceContext>,
+    /// Set on events written by a `SpanGuard`.
+    pub span: Option<SpanInfo>,
+}
+
+impl MyLogEvent {
+    /// Adds a context field unless the event already has it, so enrichers do not overwrite values written by the caller.
+    pub fn add_context_if_missing(&mut self, key: &str, value: impl FnOnce() -> String) {
+        let context = self.context.get_or_insert_with(Default::default);
+
+        if !context.contains_key(key) {
+            context.insert(key.to_string(), value());
+        }
+    }
+
+    /// `file:line` of the call which wrote the event.
+    pub fn get_source_location(&self) -> Option<String> {
+        let file = self.file?;
+
+        match self.line {
+            Some(line) => Some(format!("{}:{}", file, line)),
+            None => Some(file.to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::LogLevel;
+
+    #[test]
+    fn test_severity_order() {
+        assert!(LogLevel::Trace < LogLevel::Debug);
+        assert!(LogLevel::Debug < LogLevel::Info);
+        assert!(LogLevel::FatalError > LogLevel::Error);
+        assert!(LogLevel::Warning >= LogLevel::Warning);
+    }
+
+    #[test]
+    fn test_parse_is_case_insensitive() {
+        assert_eq!(Ok(LogLevel::Trace), "verbose".parse());
+        assert_eq!(Ok(LogLevel::Warning), "WARN".parse());
+        assert_eq!(Ok(LogLevel::FatalError), "fatalerror".parse());
+        assert!("unknown".parse::<LogLevel>().is_err());
+        assert_eq!(LogLevel::Info, LogLevel::from_str("unknown"));
+    }
+
+    #[test]
+    fn test_u8_and_display_round_trip() {
+        for level in LogLevel::ALL {
+            assert_eq!(level, LogLevel::from_u8(level.to_u8()));
+            assert_eq!(Ok(level), level.to_string().parse());
+        }
+    }
 }
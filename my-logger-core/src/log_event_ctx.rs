@@ -41,6 +41,20 @@ impl LogEventCtx {
         self.add(key, format!("{:?}", value))
     }
 
+    /// Adds every field of `other`. Fields of `other` win on key conflicts.
+    pub fn merge(mut self, other: impl Into<LogEventCtx>) -> Self {
+        let other: LogEventCtx = other.into();
+
+        if let Some(other) = other.0 {
+            match self.0.as_mut() {
+                Some(result) => result.extend(other),
+                None => self.0 = Some(other),
+            }
+        }
+
+        self
+    }
+
     pub fn get_result(self) -> Option<HashMap<String, String>> {
         self.0
     }
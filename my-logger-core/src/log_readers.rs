@@ -2,10 +2,17 @@ use std::sync::Arc;
 
 use rust_extensions::StrOrString;
 
-use crate::{MyLoggerReader, PopulatedParams};
+use crate::{Enricher, LogLevel, MyLogEvent, MyLoggerReader, PopulatedParams};
+
+pub type LogEventFilter = Arc<dyn Fn(&MyLogEvent) -> bool + Send + Sync + 'static>;
+pub type LogProcessFilter = Arc<dyn Fn(LogLevel, &str) -> bool + Send + Sync + 'static>;
+pub type LogEventEnricher = Arc<dyn Enricher + Send + Sync + 'static>;
 
 pub struct LogReaders {
     readers: Vec<Arc<dyn MyLoggerReader + Send + Sync + 'static>>,
+    filters: Vec<LogEventFilter>,
+    process_filters: Vec<LogProcessFilter>,
+    enrichers: Vec<LogEventEnricher>,
     populated_params: PopulatedParams,
 }
 
@@ -13,6 +20,9 @@ impl LogReaders {
     pub fn new(populated_params: Vec<(&'static str, StrOrString<'static>)>) -> Self {
         Self {
             readers: Vec::new(),
+            filters: Vec::new(),
+            process_filters: Vec::new(),
+            enrichers: Vec::new(),
             populated_params: PopulatedParams::new(populated_params),
         }
     }
@@ -20,6 +30,9 @@ impl LogReaders {
     fn clone(&self) -> Self {
         LogReaders {
             readers: self.readers.clone(),
+            filters: self.filters.clone(),
+            process_filters: self.process_filters.clone(),
+            enrichers: self.enrichers.clone(),
             populated_params: self.populated_params.clone(),
         }
     }
@@ -42,6 +55,55 @@ impl LogReaders {
         result
     }
 
+    pub fn unregister_reader(
+        &self,
+        reader: &Arc<dyn MyLoggerReader + Send + Sync + 'static>,
+    ) -> Self {
+        let mut result = self.clone();
+        result
+            .readers
+            .retain(|itm| !std::ptr::addr_eq(Arc::as_ptr(itm), Arc::as_ptr(reader)));
+        result
+    }
+
+    pub fn register_filter(&self, filter: LogEventFilter) -> Self {
+        let mut result = self.clone();
+        result.filters.push(filter);
+        result
+    }
+
+    pub fn register_process_filter(&self, filter: LogProcessFilter) -> Self {
+        let mut result = self.clone();
+        result.process_filters.push(filter);
+        result
+    }
+
+    /// Process filters only see the level and the process, so they are checked before an event is built.
+    pub fn is_process_accepted(&self, level: LogLevel, process: &str) -> bool {
+        self.process_filters
+            .iter()
+            .all(|filter| filter(level, process))
+    }
+
+    pub fn register_enricher(&self, enricher: LogEventEnricher) -> Self {
+        let mut result = self.clone();
+        result.enrichers.push(enricher);
+        result
+    }
+
+    /// Enrichers are applied in the order they were added.
+    pub fn enrich(&self, log_event: &mut MyLogEvent) {
+        for enricher in &self.enrichers {
+            enricher.enrich(log_event);
+        }
+    }
+
+    /// Event is written only if every filter returns true.
+    pub fn is_accepted(&self, log_event: &MyLogEvent) -> bool {
+        self.is_process_accepted(log_event.level, log_event.process.as_str())
+            && self.filters.iter().all(|filter| filter(log_event))
+    }
+
     pub fn get_readers(&self) -> &[Arc<dyn MyLoggerReader + Send + Sync + 'static>] {
         self.readers.as_slice()
     }
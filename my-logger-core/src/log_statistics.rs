@@ -1,6 +1,7 @@
 use std::sync::atomic::AtomicU64;
 
 pub struct LogsStatistics {
+    pub traces: AtomicU64,
     pub debugs: AtomicU64,
     pub fatal_errors: AtomicU64,
     pub errors: AtomicU64,
@@ -11,6 +12,7 @@ pub struct LogsStatistics {
 impl LogsStatistics {
     pub fn new() -> Self {
         Self {
+            traces: AtomicU64::new(0),
             debugs: AtomicU64::new(0),
             fatal_errors: AtomicU64::new(0),
             errors: AtomicU64::new(0),
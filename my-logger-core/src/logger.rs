@@ -1,68 +1,198 @@
-use std::{collections::HashMap, sync::Arc};
+use std::{
+    collections::HashMap,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
 use rust_extensions::{date_time::DateTimeAsMicroseconds, Logger, StrOrString};
 
-use crate::{LogEventCtx, MyLogEvent, MyLoggerInner, MyLoggerReader, PopulatedParams};
+use crate::{
+    ConsoleFilter, Enricher, LogEventCtx, LogEventEnricher, LogEventFilter, LogProcessFilter,
+    MeasureGuard, MyChildLogger, MyLogEvent, MyLoggerInner, MyLoggerReader, PopulatedParams,
+    SpanGuard, TraceContext,
+};
 
 use super::LogLevel;
 
+/// Handle to a logger. Clones share readers, filters, populated params and statistics;
+/// [`MyLogger::new`] creates an independent logger.
+#[derive(Clone)]
 pub struct MyLogger {
     inner: Arc<MyLoggerInner>,
 }
 
+impl Default for MyLogger {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl MyLogger {
     pub fn new() -> Self {
         let inner = Arc::new(MyLoggerInner::new(Vec::new()));
         Self { inner }
     }
 
+    /// Logger which writes to this one with a bound process name and context.
+    pub fn child(
+        &self,
+        process: impl Into<StrOrString<'static>>,
+        ctx: LogEventCtx,
+    ) -> MyChildLogger {
+        MyChildLogger::new(self.clone(), process.into().to_string(), ctx.get_result())
+    }
+
+    /// Starts measuring; the event is written when the guard is dropped:
+    /// `let _guard = LOGGER.measure("Snapshot", "Loading snapshot", LogEventCtx::new());`
+    #[track_caller]
+    pub fn measure<'s>(
+        &self,
+        process: impl Into<StrOrString<'static>>,
+        message: impl Into<StrOrString<'s>>,
+        ctx: LogEventCtx,
+    ) -> MeasureGuard {
+        MeasureGuard::new(
+            self.clone(),
+            process.into().to_string(),
+            message.into().to_string(),
+            ctx.get_result(),
+            std::panic::Location::caller(),
+        )
+    }
+
+    /// Starts a span which is written as one event when the guard is dropped:
+    /// `let span = LOGGER.start_span("Orders", "LoadOrder", LogEventCtx::new().add("OrderId", id));`
+    #[track_caller]
+    pub fn start_span<'s>(
+        &self,
+        process: impl Into<StrOrString<'static>>,
+        name: impl Into<StrOrString<'s>>,
+        ctx: LogEventCtx,
+    ) -> SpanGuard {
+        SpanGuard::new(
+            self.clone(),
+            process.into().to_string(),
+            name.into().to_string(),
+            ctx.get_result(),
+            std::panic::Location::caller(),
+        )
+    }
+
+    pub fn set_level_enabled(&self, level: LogLevel, enabled: bool) {
+        let bit = 1u8 << level.to_u8();
+        if enabled {
+            self.inner
+                .enabled_levels
+                .fetch_or(bit, std::sync::atomic::Ordering::Relaxed);
+        } else {
+            self.inner
+                .enabled_levels
+                .fetch_and(!bit, std::sync::atomic::Ordering::Relaxed);
+        }
+    }
+
+    /// Checked by the `write_log_*` macros before the message and the context are formatted.
+    pub fn is_level_enabled(&self, level: LogLevel) -> bool {
+        let bit = 1u8 << level.to_u8();
+        self.inner
+            .enabled_levels
+            .load(std::sync::atomic::Ordering::Relaxed)
+            & bit
+            != 0
+    }
+
+    /// `true` if an event of the level and process would pass the level switches and the process filters.
+    pub fn is_enabled(&self, level: LogLevel, process: &str) -> bool {
+        self.is_level_enabled(level)
+            && self
+                .inner
+                .log_readers
+                .load()
+                .is_process_accepted(level, process)
+    }
+
+    pub fn get_console_filter(&self) -> &ConsoleFilter {
+        &self.inner.console_printer
+    }
+
     pub async fn populate_app_and_version(
         &self,
         app_name: &'static str,
         app_version: &'static str,
     ) {
-        let inner = {
-            let write_access = self.inner.log_readers.load();
+        let env_info = std::env::var("ENV_INFO").ok();
 
-            if let Ok(env_info) = std::env::var("ENV_INFO") {
+        self.inner.log_readers.rcu(|current| {
+            if let Some(env_info) = env_info.as_ref() {
                 let params: [(&'static str, StrOrString<'static>); 3] = [
                     ("Application", app_name.into()),
                     ("Version", app_version.into()),
-                    ("EnvInfo", env_info.into()),
+                    ("EnvInfo", env_info.clone().into()),
                 ];
-                write_access.populate_params(params.into_iter())
+                current.populate_params(params.into_iter())
             } else {
                 let params = [
                     ("Application", app_name.into()),
                     ("Version", app_version.into()),
                 ];
-                write_access.populate_params(params.into_iter())
+                current.populate_params(params.into_iter())
             }
-        };
-
-        self.inner.log_readers.store(inner.into());
+        });
     }
 
     pub fn plug_reader(&self, reader: Arc<dyn MyLoggerReader + Send + Sync + 'static>) {
-        let inner = {
-            let write_access = self.inner.log_readers.load();
-            write_access.register_reader(reader)
-        };
+        self.inner
+            .log_readers
+            .rcu(|current| current.register_reader(reader.clone()));
+    }
 
-        self.inner.log_readers.store(inner.into());
+    /// Removes a reader plugged with [`MyLogger::plug_reader`]. Readers are compared by pointer.
+    pub fn unplug_reader(&self, reader: &Arc<dyn MyLoggerReader + Send + Sync + 'static>) {
+        self.inner
+            .log_readers
+            .rcu(|current| current.unregister_reader(reader));
     }
 
-    pub fn populate_params(&self, key: &'static str, value: impl Into<StrOrString<'static>>) {
-        let value: StrOrString<'static> = value.into();
+    /// Events rejected by any filter are not counted, printed or passed to readers.
+    /// The filter sees the built event, so filters which only need the level and the process
+    /// should be added with [`MyLogger::add_process_filter`] to skip formatting.
+    pub fn add_filter(&self, filter: impl Fn(&MyLogEvent) -> bool + Send + Sync + 'static) {
+        let filter: LogEventFilter = Arc::new(filter);
 
-        let inner = {
-            let write_access = self.inner.log_readers.load();
+        self.inner
+            .log_readers
+            .rcu(|current| current.register_filter(filter.clone()));
+    }
 
-            let items = [(key, value)];
-            write_access.populate_params(items.into_iter())
-        };
+    /// Enrichers add context to every event before it is printed and passed to readers.
+    pub fn add_enricher(&self, enricher: impl Enricher + Send + Sync + 'static) {
+        let enricher: LogEventEnricher = Arc::new(enricher);
 
-        self.inner.log_readers.store(inner.into());
+        self.inner
+            .log_readers
+            .rcu(|current| current.register_enricher(enricher.clone()));
+    }
+
+    /// Filter by level and process. Unlike [`MyLogger::add_filter`] it is checked by the `write_log_*`
+    /// macros before the message and the context are formatted.
+    pub fn add_process_filter(
+        &self,
+        filter: impl Fn(LogLevel, &str) -> bool + Send + Sync + 'static,
+    ) {
+        let filter: LogProcessFilter = Arc::new(filter);
+
+        self.inner
+            .log_readers
+            .rcu(|current| current.register_process_filter(filter.clone()));
+    }
+
+    pub fn populate_params(&self, key: &'static str, value: impl Into<StrOrString<'static>>) {
+        let value = value.into().to_string();
+
+        self.inner.log_readers.rcu(|current| {
+            let items = [(key, StrOrString::from(value.clone()))];
+            current.populate_params(items.into_iter())
+        });
     }
 
     pub fn get_populated_params(&self) -> PopulatedParams {
@@ -70,6 +200,8 @@ impl MyLogger {
         read_access.get_populated_params().clone()
     }
 
+    /// Source location of the event is the caller of this method.
+    #[track_caller]
     pub fn write_log(
         &self,
         level: LogLevel,
@@ -77,25 +209,85 @@ impl MyLogger {
         message: String,
         context: Option<HashMap<String, String>>,
     ) {
-        let log_event = MyLogEvent {
+        let location = std::panic::Location::caller();
+
+        self.write_log_event(MyLogEvent {
             dt: DateTimeAsMicroseconds::now(),
             context,
             level,
             message,
             process,
-        };
+            file: Some(location.file()),
+            line: Some(location.line()),
+            module_path: None,
+            trace_context: TraceContext::current(),
+            span: None,
+        });
+    }
 
-        self.inner.update_statistics(log_event.level);
-        self.inner.console_printer.print_to_console(&log_event);
+    /// Used by the `write_log_*` macros to add `module_path!()` to the caller location.
+    #[track_caller]
+    pub fn write_log_from_module(
+        &self,
+        level: LogLevel,
+        process: String,
+        message: String,
+        context: Option<HashMap<String, String>>,
+        module_path: &'static str,
+    ) {
+        let location = std::panic::Location::caller();
+
+        self.write_log_event(MyLogEvent {
+            dt: DateTimeAsMicroseconds::now(),
+            context,
+            level,
+            message,
+            process,
+            file: Some(location.file()),
+            line: Some(location.line()),
+            module_path: Some(module_path),
+            trace_context: TraceContext::current(),
+            span: None,
+        });
+    }
+
+    pub fn write_log_event(&self, mut log_event: MyLogEvent) {
+        if !self.is_level_enabled(log_event.level) {
+            return;
+        }
 
         let readers = self.inner.log_readers.load();
 
+        if !readers.is_accepted(&log_event) {
+            return;
+        }
+
+        readers.enrich(&mut log_event);
+
+        self.inner.update_statistics(log_event.level);
+        self.inner.console_printer.print_to_console(&log_event);
+
         let log_event = Arc::new(log_event);
         for reader in readers.get_readers() {
             reader.write_log(log_event.clone());
         }
     }
 
+    /// Flushes plugged readers one by one. `timeout` is shared between all of them.
+    pub fn flush(&self, timeout: Duration) {
+        let started = Instant::now();
+        let readers = self.inner.log_readers.load();
+
+        for reader in readers.get_readers() {
+            let remaining = timeout.saturating_sub(started.elapsed());
+            if remaining.is_zero() {
+                return;
+            }
+
+            reader.flush(remaining);
+        }
+    }
+
     #[deprecated(note = "Use write_log instead")]
     pub async fn write_log_async(&self, log_event: Arc<MyLogEvent>) {
         let inner = self.inner.clone();
@@ -108,6 +300,7 @@ impl MyLogger {
         }
     }
 
+    #[track_caller]
     pub fn write_info<'s>(
         &self,
         process: impl Into<StrOrString<'static>>,
@@ -122,6 +315,7 @@ impl MyLogger {
         );
     }
 
+    #[track_caller]
     pub fn write_warning<'s>(
         &self,
         process: impl Into<StrOrString<'static>>,
@@ -136,6 +330,7 @@ impl MyLogger {
         );
     }
 
+    #[track_caller]
     pub fn write_error<'s>(
         &self,
         process: impl Into<StrOrString<'static>>,
@@ -150,6 +345,7 @@ impl MyLogger {
         );
     }
 
+    #[track_caller]
     pub fn write_fatal_error<'s>(
         &self,
         process: impl Into<StrOrString<'static>>,
@@ -165,6 +361,7 @@ impl MyLogger {
     }
 
     #[deprecated(note = "Use write_debug instead")]
+    #[track_caller]
     pub fn write_fatal_debug<'s>(
         &self,
         process: impl Into<StrOrString<'static>>,
@@ -179,6 +376,7 @@ impl MyLogger {
         );
     }
 
+    #[track_caller]
     pub fn write_debug<'s>(
         &self,
         process: impl Into<StrOrString<'static>>,
@@ -193,6 +391,21 @@ impl MyLogger {
         );
     }
 
+    #[track_caller]
+    pub fn write_trace<'s>(
+        &self,
+        process: impl Into<StrOrString<'static>>,
+        message: impl Into<StrOrString<'s>>,
+        ctx: LogEventCtx,
+    ) {
+        self.write_log(
+            LogLevel::Trace,
+            process.into().to_string(),
+            message.into().to_string(),
+            ctx.get_result(),
+        );
+    }
+
     pub fn get_errors_amount(&self) -> u64 {
         self.inner
             .statistics
@@ -221,6 +434,13 @@ impl MyLogger {
             .load(std::sync::atomic::Ordering::Relaxed)
     }
 
+    pub fn get_traces_amount(&self) -> u64 {
+        self.inner
+            .statistics
+            .traces
+            .load(std::sync::atomic::Ordering::Relaxed)
+    }
+
     pub fn get_infos_amount(&self) -> u64 {
         self.inner
             .statistics
@@ -230,10 +450,12 @@ impl MyLogger {
 }
 
 impl Logger for MyLogger {
+    #[track_caller]
     fn write_info(&self, process: String, message: String, ctx: Option<HashMap<String, String>>) {
         self.write_log(LogLevel::Info, process, message, ctx);
     }
 
+    #[track_caller]
     fn write_warning(
         &self,
         process: String,
@@ -243,10 +465,12 @@ impl Logger for MyLogger {
         self.write_log(LogLevel::Warning, process, message, ctx);
     }
 
+    #[track_caller]
     fn write_error(&self, process: String, message: String, ctx: Option<HashMap<String, String>>) {
         self.write_log(LogLevel::Error, process, message, ctx);
     }
 
+    #[track_caller]
     fn write_fatal_error(
         &self,
         process: String,
@@ -256,6 +480,7 @@ impl Logger for MyLogger {
         self.write_log(LogLevel::FatalError, process, message, ctx);
     }
 
+    #[track_caller]
     fn write_debug_info(
         &self,
         process: String,
@@ -265,3 +490,81 @@ impl Logger for MyLogger {
         self.write_log(LogLevel::Debug, process, message, ctx);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::test_utils::TestReader;
+    use crate::{LogEventCtx, LogLevel, MyLogger};
+
+    fn create_logger() -> (MyLogger, Arc<TestReader>) {
+        let logger = MyLogger::new();
+        logger.get_console_filter().set_print_infos(false);
+
+        let reader = Arc::new(TestReader::default());
+        logger.plug_reader(reader.clone());
+        (logger, reader)
+    }
+
+    #[test]
+    fn test_rejected_events_are_not_written_or_counted() {
+        let (logger, reader) = create_logger();
+        logger.add_filter(|log_event| !log_event.message.contains("secret"));
+        logger.add_process_filter(|level, process| level > LogLevel::Info || process != "Noisy");
+
+        logger.write_info("Test", "A secret", LogEventCtx::new());
+        logger.write_info("Noisy", "Noise", LogEventCtx::new());
+        logger.write_info("Test", "Written", LogEventCtx::new());
+
+        assert!(!logger.is_enabled(LogLevel::Info, "Noisy"));
+        assert!(logger.is_enabled(LogLevel::Info, "Test"));
+
+        let events = reader.get_events();
+        assert_eq!(1, events.len());
+        assert_eq!("Written", events[0].message);
+        assert_eq!(1, logger.get_infos_amount());
+    }
+
+    #[test]
+    fn test_filters_and_readers_belong_to_their_logger() {
+        let (first, first_reader) = create_logger();
+        let (second, second_reader) = create_logger();
+
+        first.add_filter(|log_event| log_event.process != "Skipped");
+
+        first.write_info("Skipped", "First", LogEventCtx::new());
+        second.write_info("Skipped", "Second", LogEventCtx::new());
+
+        assert!(first_reader.get_events().is_empty());
+
+        let events = second_reader.get_events();
+        assert_eq!(1, events.len());
+        assert_eq!("Second", events[0].message);
+    }
+
+    #[test]
+    fn test_filters_added_concurrently_are_not_lost() {
+        let (logger, reader) = create_logger();
+
+        let threads: Vec<_> = (0..8)
+            .map(|index| {
+                let logger = logger.clone();
+                std::thread::spawn(move || {
+                    let process = format!("Process{}", index);
+                    logger.add_filter(move |log_event| log_event.process != process);
+                })
+            })
+            .collect();
+
+        for thread in threads {
+            thread.join().unwrap();
+        }
+
+        for index in 0..8 {
+            logger.write_info(format!("Process{}", index), "Filtered", LogEventCtx::new());
+        }
+
+        assert!(reader.get_events().is_empty());
+    }
+}
@@ -1,7 +1,11 @@
-use std::sync::Arc;
+use std::{sync::Arc, time::Duration};
 
 use super::MyLogEvent;
 
 pub trait MyLoggerReader {
     fn write_log(&self, log_event: Arc<MyLogEvent>);
+
+    /// Delivers queued events before returning. Called from the panic hook, so it must not rely on
+    /// the caller being inside (or outside) a tokio runtime and must give up after `timeout`.
+    fn flush(&self, _timeout: Duration) {}
 }
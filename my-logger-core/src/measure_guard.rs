@@ -0,0 +1,178 @@
+use std::{
+    collections::HashMap,
+    panic::Location,
+    time::{Duration, Instant},
+};
+
+use rust_extensions::date_time::DateTimeAsMicroseconds;
+
+use crate::{LogEventCtx, LogLevel, MyLogEvent, MyLogger, TraceContext};
+
+pub const DURATION_MS_KEY: &str = "DurationMs";
+
+/// Returned by [`MyLogger::measure`]. Writes the event with the elapsed milliseconds in the
+/// `DurationMs` field when dropped or finished.
+///
+/// The level is Info unless changed with [`MeasureGuard::level`]; `warn_over` and `error_over`
+/// raise it when the elapsed time exceeds the threshold, but never lower a higher base level.
+#[must_use = "the duration is logged when the guard is dropped"]
+pub struct MeasureGuard {
+    logger: MyLogger,
+    process: String,
+    message: String,
+    context: Option<HashMap<String, String>>,
+    level: LogLevel,
+    warn_over: Option<Duration>,
+    error_over: Option<Duration>,
+    location: &'static Location<'static>,
+    module_path: Option<&'static str>,
+    started: Instant,
+}
+
+impl MeasureGuard {
+    pub(crate) fn new(
+        logger: MyLogger,
+        process: String,
+        message: String,
+        context: Option<HashMap<String, String>>,
+        location: &'static Location<'static>,
+    ) -> Self {
+        Self {
+            logger,
+            process,
+            message,
+            context,
+            level: LogLevel::Info,
+            warn_over: None,
+            error_over: None,
+            location,
+            module_path: None,
+            started: Instant::now(),
+        }
+    }
+
+    pub fn level(mut self, level: LogLevel) -> Self {
+        self.level = level;
+        self
+    }
+
+    pub fn warn_over(mut self, threshold: Duration) -> Self {
+        self.warn_over = Some(threshold);
+        self
+    }
+
+    pub fn error_over(mut self, threshold: Duration) -> Self {
+        self.error_over = Some(threshold);
+        self
+    }
+
+    #[doc(hidden)]
+    pub fn module_path(mut self, module_path: &'static str) -> Self {
+        self.module_path = Some(module_path);
+        self
+    }
+
+    pub fn elapsed(&self) -> Duration {
+        self.started.elapsed()
+    }
+
+    /// Writes the event now, with `ctx` (for example the outcome) added to the context.
+    pub fn finish_with(mut self, ctx: LogEventCtx) {
+        let ctx = match self.context.take() {
+            Some(context) => {
+                let bound_ctx: LogEventCtx = context.into();
+                bound_ctx.merge(ctx)
+            }
+            None => ctx,
+        };
+
+        self.context = ctx.get_result();
+    }
+
+    pub fn finish(self) {}
+
+    fn get_level(&self, elapsed: Duration) -> LogLevel {
+        if let Some(error_over) = self.error_over {
+            if elapsed > error_over {
+                return self.level.max(LogLevel::Error);
+            }
+        }
+
+        if let Some(warn_over) = self.warn_over {
+            if elapsed > warn_over {
+                return self.level.max(LogLevel::Warning);
+            }
+        }
+
+        self.level
+    }
+}
+
+impl Drop for MeasureGuard {
+    fn drop(&mut self) {
+        let elapsed = self.started.elapsed();
+        let level = self.get_level(elapsed);
+
+        if !self.logger.is_level_enabled(level) {
+            return;
+        }
+
+        let mut context = self.context.take().unwrap_or_default();
+        context.insert(
+            DURATION_MS_KEY.to_string(),
+            format!("{:.3}", elapsed.as_secs_f64() * 1000.0),
+        );
+
+        self.logger.write_log_event(MyLogEvent {
+            dt: DateTimeAsMicroseconds::now(),
+            level,
+            process: std::mem::take(&mut self.process),
+            message: std::mem::take(&mut self.message),
+            context: Some(context),
+            file: Some(self.location.file()),
+            line: Some(self.location.line()),
+            module_path: self.module_path,
+            trace_context: TraceContext::current(),
+            span: None,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::{LogEventCtx, LogLevel, MyLogger};
+
+    #[test]
+    fn test_thresholds_do_not_lower_the_level() {
+        let logger = MyLogger::new();
+
+        let guard = logger
+            .measure("Process", "Measured", LogEventCtx::new())
+            .warn_over(Duration::from_millis(10))
+            .error_over(Duration::from_millis(20));
+
+        assert_eq!(LogLevel::Info, guard.get_level(Duration::from_millis(5)));
+        assert_eq!(
+            LogLevel::Warning,
+            guard.get_level(Duration::from_millis(15))
+        );
+        assert_eq!(LogLevel::Error, guard.get_level(Duration::from_millis(25)));
+
+        let guard = guard.level(LogLevel::FatalError);
+
+        assert_eq!(
+            LogLevel::FatalError,
+            guard.get_level(Duration::from_millis(15))
+        );
+        assert_eq!(
+            LogLevel::FatalError,
+            guard.get_level(Duration::from_millis(25))
+        );
+
+        let guard = guard.level(LogLevel::Error);
+
+        assert_eq!(LogLevel::Error, guard.get_level(Duration::from_millis(15)));
+    }
+}
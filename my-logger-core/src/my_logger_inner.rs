@@ -1,3 +1,5 @@
+use std::sync::atomic::AtomicU8;
+
 use arc_swap::ArcSwap;
 use rust_extensions::{date_time::DateTimeAsMicroseconds, StrOrString};
 
@@ -7,6 +9,8 @@ pub struct MyLoggerInner {
     pub console_printer: ConsoleFilter,
     pub statistics: LogsStatistics,
     pub log_readers: ArcSwap<LogReaders>,
+    /// Bit per `LogLevel::to_u8`. Events of disabled levels are discarded before they are built.
+    pub enabled_levels: AtomicU8,
 
     pub start_time: DateTimeAsMicroseconds,
 }
@@ -18,6 +22,7 @@ impl MyLoggerInner {
             statistics: LogsStatistics::new(),
             start_time: DateTimeAsMicroseconds::now(),
             log_readers: ArcSwap::new(LogReaders::new(populated_params).into()),
+            enabled_levels: AtomicU8::new(u8::MAX),
         }
     }
 
@@ -48,6 +53,11 @@ impl MyLoggerInner {
                     .debugs
                     .fetch_add(1, std::sync::atomic::Ordering::Relaxed);
             }
+            LogLevel::Trace => {
+                self.statistics
+                    .traces
+                    .fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+            }
         }
     }
 }
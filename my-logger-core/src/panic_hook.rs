@@ -0,0 +1,84 @@
+use std::{
+    panic::PanicHookInfo,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Once,
+    },
+    time::Duration,
+};
+
+use rust_extensions::date_time::DateTimeAsMicroseconds;
+
+use crate::{LogEventCtx, LogLevel, MyLogEvent, TraceContext};
+
+const PANIC_PROCESS: &str = "Panic Handler";
+const PANIC_FLUSH_TIMEOUT: Duration = Duration::from_secs(5);
+
+static INSTALL_PANIC_HOOK: Once = Once::new();
+static FLUSHING: AtomicBool = AtomicBool::new(false);
+
+/// Logs every panic as a FatalError with location, thread name and a backtrace
+/// (when enabled by `RUST_BACKTRACE`), flushes readers and then calls the previously installed hook.
+///
+/// The event is written to and the readers are flushed on the global `LOGGER` only;
+/// loggers created with `MyLogger::new` are neither written to nor flushed.
+/// Installing it more than once has no effect.
+pub fn install_panic_hook() {
+    INSTALL_PANIC_HOOK.call_once(|| {
+        let previous_hook = std::panic::take_hook();
+
+        std::panic::set_hook(Box::new(move |panic_info| {
+            write_panic(panic_info);
+
+            // A panic inside a reader flush must not start another flush
+            if !FLUSHING.swap(true, Ordering::SeqCst) {
+                crate::LOGGER.flush(PANIC_FLUSH_TIMEOUT);
+                FLUSHING.store(false, Ordering::SeqCst);
+            }
+
+            previous_hook(panic_info);
+        }));
+    });
+}
+
+fn write_panic(panic_info: &PanicHookInfo) {
+    let mut ctx = if let Some(location) = panic_info.location() {
+        LogEventCtx::new().add("Location", format!("{}", location))
+    } else {
+        LogEventCtx::new()
+    };
+
+    ctx = ctx.add("PanicInfo", format!("{}", panic_info));
+
+    let thread = std::thread::current();
+    ctx = ctx.add("ThreadName", thread.name().unwrap_or("<unnamed>"));
+
+    let backtrace = std::backtrace::Backtrace::capture();
+    if backtrace.status() == std::backtrace::BacktraceStatus::Captured {
+        ctx = ctx.add("Backtrace", format!("{}", backtrace));
+    }
+
+    let payload = panic_info.payload();
+
+    let panic_message = if let Some(s) = payload.downcast_ref::<&str>() {
+        format!("{s:?}")
+    } else if let Some(s) = payload.downcast_ref::<String>() {
+        format!("{s:?}")
+    } else {
+        format!("{:?}", payload)
+    };
+
+    // The hook location is useless here; the panic location is in the context
+    crate::LOGGER.write_log_event(MyLogEvent {
+        dt: DateTimeAsMicroseconds::now(),
+        level: LogLevel::FatalError,
+        process: PANIC_PROCESS.to_string(),
+        message: panic_message,
+        context: ctx.get_result(),
+        file: None,
+        line: None,
+        module_path: None,
+        trace_context: TraceContext::current(),
+        span: None,
+    });
+}
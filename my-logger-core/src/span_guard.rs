@@ -0,0 +1,193 @@
+use std::{collections::HashMap, future::Future, panic::Location, time::Instant};
+
+use rust_extensions::{date_time::DateTimeAsMicroseconds, StrOrString};
+
+use crate::{
+    LogEventCtx, LogLevel, MyLogEvent, MyLogger, TraceContext, TraceContextGuard, DURATION_MS_KEY,
+};
+
+pub const SPAN_STATUS_KEY: &str = "SpanStatus";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpanKind {
+    Internal,
+    Server,
+    Client,
+    Producer,
+    Consumer,
+}
+
+impl SpanKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SpanKind::Internal => "Internal",
+            SpanKind::Server => "Server",
+            SpanKind::Client => "Client",
+            SpanKind::Producer => "Producer",
+            SpanKind::Consumer => "Consumer",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpanStatus {
+    Unset,
+    Ok,
+    Error,
+}
+
+impl SpanStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SpanStatus::Unset => "Unset",
+            SpanStatus::Ok => "Ok",
+            SpanStatus::Error => "Error",
+        }
+    }
+}
+
+/// Span part of a [`MyLogEvent`] written by a [`SpanGuard`]. Trace, span and parent ids are in the trace context.
+#[derive(Debug, Clone)]
+pub struct SpanInfo {
+    pub started: DateTimeAsMicroseconds,
+    pub kind: SpanKind,
+    pub status: SpanStatus,
+}
+
+/// Returned by [`MyLogger::start_span`]. When dropped it writes one event describing the span:
+/// the name as the message, attributes as the context, start time, kind, status and `DurationMs`.
+///
+/// The span is a child of the trace context in scope, or starts a new trace.
+/// Operations inside the span should run in [`SpanGuard::scope`] or [`SpanGuard::enter`],
+/// so their events and child spans belong to it.
+#[must_use = "the span is written when the guard is dropped"]
+pub struct SpanGuard {
+    logger: MyLogger,
+    process: String,
+    name: String,
+    attributes: Option<HashMap<String, String>>,
+    trace_context: TraceContext,
+    kind: SpanKind,
+    status: SpanStatus,
+    location: &'static Location<'static>,
+    started: DateTimeAsMicroseconds,
+    started_instant: Instant,
+}
+
+impl SpanGuard {
+    pub(crate) fn new(
+        logger: MyLogger,
+        process: String,
+        name: String,
+        attributes: Option<HashMap<String, String>>,
+        location: &'static Location<'static>,
+    ) -> Self {
+        let trace_context = match TraceContext::current() {
+            Some(parent) => parent.child(),
+            None => TraceContext::new_root(),
+        };
+
+        Self {
+            logger,
+            process,
+            name,
+            attributes,
+            trace_context,
+            kind: SpanKind::Internal,
+            status: SpanStatus::Unset,
+            location,
+            started: DateTimeAsMicroseconds::now(),
+            started_instant: Instant::now(),
+        }
+    }
+
+    pub fn kind(mut self, kind: SpanKind) -> Self {
+        self.kind = kind;
+        self
+    }
+
+    pub fn get_trace_context(&self) -> &TraceContext {
+        &self.trace_context
+    }
+
+    pub fn set_attribute<'s>(
+        &mut self,
+        key: impl Into<StrOrString<'static>>,
+        value: impl Into<StrOrString<'s>>,
+    ) {
+        self.attributes
+            .get_or_insert_with(HashMap::new)
+            .insert(key.into().to_string(), value.into().to_string());
+    }
+
+    pub fn set_status(&mut self, status: SpanStatus) {
+        self.status = status;
+    }
+
+    /// Marks the span as failed. The span event is written as Error with the message in the `Error` attribute.
+    pub fn set_error<'s>(&mut self, message: impl Into<StrOrString<'s>>) {
+        self.status = SpanStatus::Error;
+        self.set_attribute("Error", message);
+    }
+
+    /// Runs `future` with the span as the current trace context.
+    pub async fn scope<TFuture: Future>(&self, future: TFuture) -> TFuture::Output {
+        self.trace_context.clone().scope(future).await
+    }
+
+    /// Makes the span the current trace context on the thread until the returned guard is dropped.
+    pub fn enter(&self) -> TraceContextGuard {
+        self.trace_context.clone().enter()
+    }
+
+    /// Writes the span now, with `ctx` added to the attributes.
+    pub fn finish_with(mut self, ctx: LogEventCtx) {
+        if let Some(ctx) = ctx.get_result() {
+            self.attributes.get_or_insert_with(HashMap::new).extend(ctx);
+        }
+    }
+
+    pub fn finish(self) {}
+}
+
+impl Drop for SpanGuard {
+    fn drop(&mut self) {
+        let level = match self.status {
+            SpanStatus::Error => LogLevel::Error,
+            _ => LogLevel::Info,
+        };
+
+        if !self.logger.is_level_enabled(level) {
+            return;
+        }
+
+        let elapsed = self.started_instant.elapsed();
+
+        let mut context = self.attributes.take().unwrap_or_default();
+        context.insert(
+            DURATION_MS_KEY.to_string(),
+            format!("{:.3}", elapsed.as_secs_f64() * 1000.0),
+        );
+        context.insert(
+            SPAN_STATUS_KEY.to_string(),
+            self.status.as_str().to_string(),
+        );
+
+        self.logger.write_log_event(MyLogEvent {
+            dt: DateTimeAsMicroseconds::now(),
+            level,
+            process: std::mem::take(&mut self.process),
+            message: std::mem::take(&mut self.name),
+            context: Some(context),
+            file: Some(self.location.file()),
+            line: Some(self.location.line()),
+            module_path: None,
+            trace_context: Some(self.trace_context.clone()),
+            span: Some(SpanInfo {
+                started: self.started,
+                kind: self.kind,
+                status: self.status,
+            }),
+        });
+    }
+}
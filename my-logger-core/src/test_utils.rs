@@ -0,0 +1,40 @@
+use std::sync::Arc;
+
+use parking_lot::Mutex;
+use rust_extensions::date_time::DateTimeAsMicroseconds;
+
+use crate::{LogLevel, MyLogEvent, MyLoggerReader};
+
+/// Event written now, without context and source location.
+pub fn create_test_event(level: LogLevel, process: &str, message: &str) -> MyLogEvent {
+    MyLogEvent {
+        dt: DateTimeAsMicroseconds::now(),
+        level,
+        process: process.to_string(),
+        message: message.to_string(),
+        context: None,
+        file: None,
+        line: None,
+        module_path: None,
+        trace_context: None,
+        span: None,
+    }
+}
+
+/// Reader which keeps every received event.
+#[derive(Default)]
+pub struct TestReader {
+    events: Mutex<Vec<Arc<MyLogEvent>>>,
+}
+
+impl TestReader {
+    pub fn get_events(&self) -> Vec<Arc<MyLogEvent>> {
+        self.events.lock().clone()
+    }
+}
+
+impl MyLoggerReader for TestReader {
+    fn write_log(&self, log_event: Arc<MyLogEvent>) {
+        self.events.lock().push(log_event);
+    }
+}
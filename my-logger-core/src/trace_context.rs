@@ -0,0 +1,248 @@
+use std::{
+    cell::RefCell,
+    future::Future,
+    hash::{BuildHasher, Hasher},
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+const TRACEPARENT_VERSION: &str = "00";
+const TRACE_ID_LEN: usize = 32;
+const SPAN_ID_LEN: usize = 16;
+const SAMPLED_FLAG: u8 = 0x01;
+
+static ID_SEQUENCE: AtomicU64 = AtomicU64::new(0);
+static GUARD_ID_SEQUENCE: AtomicU64 = AtomicU64::new(0);
+
+thread_local! {
+    /// Entered contexts with the id of the guard which owns the entry.
+    static THREAD_TRACE_CONTEXTS: RefCell<Vec<(u64, TraceContext)>> = const { RefCell::new(Vec::new()) };
+}
+
+tokio::task_local! {
+    static TASK_TRACE_CONTEXT: TraceContext;
+}
+
+/// W3C trace context of the current operation. Ids are lowercase hex strings.
+///
+/// Events written while a context is in scope ([`TraceContext::scope`] or [`TraceContext::enter`])
+/// carry it, and Seq receives it as `@tr`, `@sp` and `@ps`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TraceContext {
+    pub trace_id: String,
+    pub span_id: String,
+    pub parent_span_id: Option<String>,
+    pub sampled: bool,
+}
+
+impl TraceContext {
+    /// Starts a new trace.
+    pub fn new_root() -> Self {
+        Self {
+            trace_id: format!("{:016x}{:016x}", generate_id(), generate_id()),
+            span_id: generate_span_id(),
+            parent_span_id: None,
+            sampled: true,
+        }
+    }
+
+    /// Context of an operation started inside this one.
+    pub fn child(&self) -> Self {
+        Self {
+            trace_id: self.trace_id.clone(),
+            span_id: generate_span_id(),
+            parent_span_id: Some(self.span_id.clone()),
+            sampled: self.sampled,
+        }
+    }
+
+    /// Parses a `traceparent` header: `00-<trace-id>-<parent-id>-<flags>`.
+    /// The returned context is the remote caller's span; use [`TraceContext::child`] for the local one.
+    pub fn parse_traceparent(header: &str) -> Option<Self> {
+        let mut parts = header.trim().split('-');
+
+        let version = parts.next()?;
+        let trace_id = parts.next()?;
+        let span_id = parts.next()?;
+        let flags = parts.next()?;
+
+        if version != TRACEPARENT_VERSION || parts.next().is_some() {
+            return None;
+        }
+
+        if !is_valid_id(trace_id, TRACE_ID_LEN) || !is_valid_id(span_id, SPAN_ID_LEN) {
+            return None;
+        }
+
+        if flags.len() != 2 {
+            return None;
+        }
+
+        let flags = u8::from_str_radix(flags, 16).ok()?;
+
+        Some(Self {
+            trace_id: trace_id.to_string(),
+            span_id: span_id.to_string(),
+            parent_span_id: None,
+            sampled: flags & SAMPLED_FLAG != 0,
+        })
+    }
+
+    /// Context for handling an incoming request: a child of the `traceparent` header,
+    /// or a new trace if the header is missing or invalid.
+    pub fn from_traceparent_or_new(header: Option<&str>) -> Self {
+        match header.and_then(Self::parse_traceparent) {
+            Some(remote) => remote.child(),
+            None => Self::new_root(),
+        }
+    }
+
+    /// Value of the `traceparent` header for outgoing requests.
+    pub fn to_traceparent(&self) -> String {
+        let flags = if self.sampled { SAMPLED_FLAG } else { 0 };
+
+        format!(
+            "{}-{}-{}-{:02x}",
+            TRACEPARENT_VERSION, self.trace_id, self.span_id, flags
+        )
+    }
+
+    /// Context of the running task, or the innermost entered one on the current thread.
+    pub fn current() -> Option<TraceContext> {
+        if let Ok(trace_context) = TASK_TRACE_CONTEXT.try_with(|itm| itm.clone()) {
+            return Some(trace_context);
+        }
+
+        THREAD_TRACE_CONTEXTS.with(|contexts| {
+            contexts
+                .borrow()
+                .last()
+                .map(|(_, trace_context)| trace_context.clone())
+        })
+    }
+
+    /// Runs `future` with this context. Tasks spawned from inside the future are not part of the scope.
+    pub async fn scope<TFuture: Future>(self, future: TFuture) -> TFuture::Output {
+        TASK_TRACE_CONTEXT.scope(self, future).await
+    }
+
+    /// Makes this context current on the thread until the guard is dropped. Use [`TraceContext::scope`]
+    /// in async code, since a task can move between threads.
+    pub fn enter(self) -> TraceContextGuard {
+        let id = GUARD_ID_SEQUENCE.fetch_add(1, Ordering::Relaxed);
+        THREAD_TRACE_CONTEXTS.with(|contexts| contexts.borrow_mut().push((id, self)));
+        TraceContextGuard {
+            id,
+            _not_send: std::marker::PhantomData,
+        }
+    }
+}
+
+#[must_use = "the trace context is left when the guard is dropped"]
+pub struct TraceContextGuard {
+    id: u64,
+    _not_send: std::marker::PhantomData<*const ()>,
+}
+
+impl Drop for TraceContextGuard {
+    fn drop(&mut self) {
+        // Guards can be dropped out of order, so only the own entry is removed
+        THREAD_TRACE_CONTEXTS.with(|contexts| {
+            let mut contexts = contexts.borrow_mut();
+            if let Some(index) = contexts.iter().rposition(|(id, _)| *id == self.id) {
+                contexts.remove(index);
+            }
+        });
+    }
+}
+
+fn is_valid_id(src: &str, len: usize) -> bool {
+    src.len() == len
+        && src
+            .bytes()
+            .all(|b| b.is_ascii_digit() || (b'a'..=b'f').contains(&b))
+        && src.bytes().any(|b| b != b'0')
+}
+
+fn generate_span_id() -> String {
+    format!("{:016x}", generate_id())
+}
+
+fn generate_id() -> u64 {
+    let mut hasher = std::collections::hash_map::RandomState::new().build_hasher();
+    hasher.write_u64(ID_SEQUENCE.fetch_add(1, Ordering::Relaxed));
+
+    if let Ok(now) = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH) {
+        hasher.write_u128(now.as_nanos());
+    }
+
+    match hasher.finish() {
+        0 => 1,
+        id => id,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TraceContext;
+
+    #[test]
+    fn test_traceparent_round_trip() {
+        let header = "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01";
+        let remote = TraceContext::parse_traceparent(header).unwrap();
+
+        assert_eq!("4bf92f3577b34da6a3ce929d0e0e4736", remote.trace_id);
+        assert_eq!("00f067aa0ba902b7", remote.span_id);
+        assert!(remote.sampled);
+        assert_eq!(header, remote.to_traceparent());
+
+        let local = TraceContext::from_traceparent_or_new(Some(header));
+        assert_eq!(remote.trace_id, local.trace_id);
+        assert_eq!(Some(remote.span_id), local.parent_span_id);
+        assert_eq!(16, local.span_id.len());
+    }
+
+    #[test]
+    fn test_invalid_traceparent() {
+        for header in [
+            "",
+            "01-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01",
+            "00-00000000000000000000000000000000-00f067aa0ba902b7-01",
+            "00-4BF92F3577B34DA6A3CE929D0E0E4736-00f067aa0ba902b7-01",
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7",
+        ] {
+            assert!(
+                TraceContext::parse_traceparent(header).is_none(),
+                "{}",
+                header
+            );
+        }
+    }
+
+    #[test]
+    fn test_enter_is_scoped_to_guard() {
+        let root = TraceContext::new_root();
+        assert_eq!(32, root.trace_id.len());
+
+        {
+            let _guard = root.clone().enter();
+            assert_eq!(Some(root.clone()), TraceContext::current());
+        }
+
+        assert!(TraceContext::current().is_none());
+    }
+
+    #[test]
+    fn test_guards_dropped_out_of_order() {
+        let outer = TraceContext::new_root();
+        let inner = outer.child();
+
+        let outer_guard = outer.clone().enter();
+        let inner_guard = inner.clone().enter();
+
+        drop(outer_guard);
+        assert_eq!(Some(inner), TraceContext::current());
+
+        drop(inner_guard);
+        assert!(TraceContext::current().is_none());
+    }
+}
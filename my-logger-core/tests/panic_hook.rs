@@ -0,0 +1,66 @@
+// The panic hook is process wide, so it is tested in its own binary
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+    time::Duration,
+};
+
+use my_logger_core::{LogLevel, MyLogEvent, MyLoggerReader, LOGGER};
+
+const THREAD_NAME: &str = "panicking-thread";
+
+static PREVIOUS_HOOK_CALLS: AtomicUsize = AtomicUsize::new(0);
+
+#[derive(Default)]
+struct TestReader {
+    events: Mutex<Vec<Arc<MyLogEvent>>>,
+    flushes: AtomicUsize,
+}
+
+impl MyLoggerReader for TestReader {
+    fn write_log(&self, log_event: Arc<MyLogEvent>) {
+        self.events.lock().unwrap().push(log_event);
+    }
+
+    fn flush(&self, _: Duration) {
+        self.flushes.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+#[test]
+fn test_panic_is_written_and_previous_hook_is_called() {
+    std::panic::set_hook(Box::new(|_| {
+        PREVIOUS_HOOK_CALLS.fetch_add(1, Ordering::SeqCst);
+    }));
+
+    my_logger_core::install_panic_hook();
+
+    let reader = Arc::new(TestReader::default());
+    LOGGER.plug_reader(reader.clone());
+
+    let result = std::thread::Builder::new()
+        .name(THREAD_NAME.to_string())
+        .spawn(|| std::panic::catch_unwind(|| panic!("Order {} is broken", 7)))
+        .unwrap()
+        .join()
+        .unwrap();
+
+    assert!(result.is_err());
+    assert_eq!(1, PREVIOUS_HOOK_CALLS.load(Ordering::SeqCst));
+    assert_eq!(1, reader.flushes.load(Ordering::SeqCst));
+
+    let events = reader.events.lock().unwrap();
+    let event = events
+        .iter()
+        .find(|event| event.level == LogLevel::FatalError)
+        .unwrap();
+
+    assert_eq!("Panic Handler", event.process);
+    assert_eq!("\"Order 7 is broken\"", event.message);
+
+    let context = event.context.as_ref().unwrap();
+    assert_eq!(THREAD_NAME, context["ThreadName"]);
+    assert!(context["Location"].starts_with(concat!(file!(), ":")));
+}
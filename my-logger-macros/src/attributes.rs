@@ -0,0 +1,35 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Ident, ItemFn, LitStr};
+
+/// `process = "..."` or the function path.
+pub fn get_process(process: Option<LitStr>, item_fn: &ItemFn) -> TokenStream {
+    match process {
+        Some(process) => quote!(#process),
+        None => {
+            let fn_name = item_fn.sig.ident.to_string();
+            quote!(concat!(module_path!(), "::", #fn_name))
+        }
+    }
+}
+
+pub fn check_args_exist(args: &[Ident], item_fn: &ItemFn) -> syn::Result<()> {
+    for arg in args {
+        let exists = item_fn.sig.inputs.iter().any(|input| match input {
+            syn::FnArg::Receiver(_) => arg == "self",
+            syn::FnArg::Typed(pat_type) => match pat_type.pat.as_ref() {
+                syn::Pat::Ident(pat_ident) => pat_ident.ident == *arg,
+                _ => false,
+            },
+        });
+
+        if !exists {
+            return Err(syn::Error::new(
+                arg.span(),
+                format!("{} is not an argument of the function", arg),
+            ));
+        }
+    }
+
+    Ok(())
+}
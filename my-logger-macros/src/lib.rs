@@ -0,0 +1,32 @@
+use proc_macro::TokenStream;
+
+mod attributes;
+mod log_duration;
+mod log_errors;
+
+/// Logs the `Err` returned by a sync or async function through `my_logger::LOGGER`.
+///
+/// `#[log_errors(process = "Orders", args(order_id, client_id))]`
+/// * **process** - the function path (`module::fn_name`) by default;
+/// * **args** - arguments written to the context with Debug. They are formatted before the call.
+///
+/// `return` and `?` in the body are logged as well. A `return` produced by another macro
+/// (e.g. `bail!`) leaves the function without logging.
+#[proc_macro_attribute]
+pub fn log_errors(attr: TokenStream, item: TokenStream) -> TokenStream {
+    log_errors::generate(attr.into(), item.into())
+        .unwrap_or_else(|err| err.to_compile_error())
+        .into()
+}
+
+/// Logs the execution time of a sync or async function through `my_logger::LOGGER`
+/// as Debug, or as Warning if it exceeds **warn_over_ms**.
+///
+/// `#[log_duration(process = "Orders", warn_over_ms = 500)]`
+/// * **process** - the function path (`module::fn_name`) by default.
+#[proc_macro_attribute]
+pub fn log_duration(attr: TokenStream, item: TokenStream) -> TokenStream {
+    log_duration::generate(attr.into(), item.into())
+        .unwrap_or_else(|err| err.to_compile_error())
+        .into()
+}
@@ -0,0 +1,47 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{parse::Parser, ItemFn, LitInt, LitStr};
+
+pub fn generate(attr: TokenStream, item: TokenStream) -> syn::Result<TokenStream> {
+    let mut process: Option<LitStr> = None;
+    let mut warn_over_ms: Option<LitInt> = None;
+
+    let parser = syn::meta::parser(|meta| {
+        if meta.path.is_ident("process") {
+            process = Some(meta.value()?.parse()?);
+            Ok(())
+        } else if meta.path.is_ident("warn_over_ms") {
+            warn_over_ms = Some(meta.value()?.parse()?);
+            Ok(())
+        } else {
+            Err(meta.error("supported properties are process and warn_over_ms"))
+        }
+    });
+    parser.parse2(attr)?;
+
+    let mut item_fn: ItemFn = syn::parse2(item)?;
+
+    let process = crate::attributes::get_process(process, &item_fn);
+    let fn_name = item_fn.sig.ident.to_string();
+
+    let warn_over = match warn_over_ms {
+        Some(warn_over_ms) => {
+            quote!(Some(::std::time::Duration::from_millis(#warn_over_ms)))
+        }
+        None => quote!(None),
+    };
+
+    // The guard is dropped when the function returns, panics or its future completes
+    let guard = quote! {
+        let __log_duration = ::my_logger::macros_support::measure_fn(
+            #process,
+            #fn_name,
+            #warn_over,
+            module_path!(),
+        );
+    };
+
+    item_fn.block.stmts.insert(0, syn::parse2(guard)?);
+
+    Ok(quote!(#item_fn))
+}
@@ -0,0 +1,134 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{
+    parse::Parser,
+    visit_mut::{self, VisitMut},
+    Expr, Ident, ItemFn, LitStr, ReturnType, Type,
+};
+
+const LABEL: &str = "'__log_errors";
+
+pub fn generate(attr: TokenStream, item: TokenStream) -> syn::Result<TokenStream> {
+    let mut process: Option<LitStr> = None;
+    let mut args: Vec<Ident> = Vec::new();
+
+    let parser = syn::meta::parser(|meta| {
+        if meta.path.is_ident("process") {
+            process = Some(meta.value()?.parse()?);
+            Ok(())
+        } else if meta.path.is_ident("args") {
+            meta.parse_nested_meta(|arg| {
+                args.push(arg.path.require_ident()?.clone());
+                Ok(())
+            })
+        } else {
+            Err(meta.error("supported properties are process and args"))
+        }
+    });
+    parser.parse2(attr)?;
+
+    let mut item_fn: ItemFn = syn::parse2(item)?;
+
+    crate::attributes::check_args_exist(&args, &item_fn)?;
+
+    let return_type = match &item_fn.sig.output {
+        ReturnType::Type(_, return_type) => return_type.clone(),
+        ReturnType::Default => {
+            return Err(syn::Error::new_spanned(
+                &item_fn.sig,
+                "log_errors requires a function returning Result",
+            ))
+        }
+    };
+
+    let process = crate::attributes::get_process(process, &item_fn);
+    let arg_names = args.iter().map(|arg| arg.to_string());
+
+    let mut body = item_fn.block.as_ref().clone();
+    RedirectReturns.visit_block_mut(&mut body);
+
+    // `return` and `?` of the body break out of the labeled block, so the signature is kept as is
+    let label = get_label();
+    let result_type = match return_type.as_ref() {
+        Type::ImplTrait(_) => quote!(),
+        return_type => quote!(: #return_type),
+    };
+
+    let new_body = quote! {
+        {
+            let __log_ctx = ::my_logger::LogEventCtx::new()
+                #(.add(#arg_names, format!("{:?}", &#args)))*;
+
+            #[allow(unused_labels)]
+            let __result #result_type = #label: #body;
+
+            if let Err(err) = &__result {
+                ::my_logger::LOGGER.write_log_from_module(
+                    ::my_logger::LogLevel::Error,
+                    #process.to_string(),
+                    err.to_string(),
+                    __log_ctx.add("Error", format!("{:?}", err)).get_result(),
+                    module_path!(),
+                );
+            }
+
+            __result
+        }
+    };
+
+    item_fn.block = syn::parse2(new_body)?;
+
+    Ok(quote!(#item_fn))
+}
+
+/// Rewrites `return` and `?` of the function body into `break` out of the labeled block.
+/// Closures, async blocks and nested items keep their own `return` and `?`.
+/// `return` produced by macro invocations in the body is not rewritten.
+struct RedirectReturns;
+
+impl VisitMut for RedirectReturns {
+    fn visit_expr_mut(&mut self, expr: &mut Expr) {
+        match expr {
+            Expr::Closure(_) | Expr::Async(_) => {}
+            Expr::Return(expr_return) => {
+                if let Some(value) = expr_return.expr.as_mut() {
+                    self.visit_expr_mut(value);
+                }
+
+                *expr = break_with(expr_return.expr.take());
+            }
+            Expr::Try(expr_try) => {
+                self.visit_expr_mut(&mut expr_try.expr);
+
+                let value = &expr_try.expr;
+                let break_with_err = break_with(Some(syn::parse_quote!(Err(
+                    ::core::convert::From::from(err)
+                ))));
+
+                *expr = syn::parse_quote! {
+                    match #value {
+                        Ok(value) => value,
+                        Err(err) => #break_with_err,
+                    }
+                };
+            }
+            _ => visit_mut::visit_expr_mut(self, expr),
+        }
+    }
+
+    fn visit_item_mut(&mut self, _: &mut syn::Item) {}
+}
+
+/// Built directly: syn parses `break 'label ::path` as a labeled loop.
+fn break_with(value: Option<Box<Expr>>) -> Expr {
+    Expr::Break(syn::ExprBreak {
+        attrs: Vec::new(),
+        break_token: Default::default(),
+        label: Some(get_label()),
+        expr: value,
+    })
+}
+
+fn get_label() -> syn::Lifetime {
+    syn::Lifetime::new(LABEL, proc_macro2::Span::call_site())
+}
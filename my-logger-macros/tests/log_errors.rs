@@ -0,0 +1,9 @@
+// Expansions of `#[log_errors]` which must keep compiling, and the errors it reports
+#[test]
+fn test_log_errors_expansion() {
+    let t = trybuild::TestCases::new();
+    t.pass("tests/ui/log_errors_borrowed_return.rs");
+    t.pass("tests/ui/log_errors_return_and_try.rs");
+    t.compile_fail("tests/ui/log_errors_unknown_arg.rs");
+    t.compile_fail("tests/ui/log_errors_without_result.rs");
+}
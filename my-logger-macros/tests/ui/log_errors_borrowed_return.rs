@@ -0,0 +1,42 @@
+#[derive(Debug)]
+struct NotFound;
+
+impl std::fmt::Display for NotFound {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Not found")
+    }
+}
+
+struct Orders {
+    names: Vec<String>,
+}
+
+impl Orders {
+    #[my_logger::log_errors(process = "Orders", args(index))]
+    fn get(&self, index: usize) -> Result<&str, NotFound> {
+        match self.names.get(index) {
+            Some(name) => Ok(name.as_str()),
+            None => Err(NotFound),
+        }
+    }
+
+    #[my_logger::log_errors(process = "Orders")]
+    fn first_mut(&mut self) -> Result<&mut String, NotFound> {
+        self.names.first_mut().ok_or(NotFound)
+    }
+}
+
+fn main() {
+    let logs = my_logger::LogCapture::start();
+
+    let mut orders = Orders {
+        names: vec!["First".to_string()],
+    };
+
+    assert_eq!("First", orders.get(0).unwrap());
+    orders.first_mut().unwrap().push_str(" order");
+    assert!(logs.is_empty());
+
+    assert!(orders.get(1).is_err());
+    my_logger::assert_logged!(Error, process = "Orders", message = "Not found", context "index" = "1");
+}
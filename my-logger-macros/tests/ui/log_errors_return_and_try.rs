@@ -0,0 +1,64 @@
+#[derive(Debug)]
+struct ParseError(String);
+
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Can not parse {}", self.0)
+    }
+}
+
+impl From<std::num::ParseIntError> for ParseError {
+    fn from(err: std::num::ParseIntError) -> Self {
+        ParseError(err.to_string())
+    }
+}
+
+#[my_logger::log_errors(process = "Parser", args(src))]
+fn parse(src: &str) -> Result<u64, ParseError> {
+    if src.is_empty() {
+        return Err(ParseError("empty string".to_string()));
+    }
+
+    // `?` and `return` of closures and nested functions are not redirected
+    let first_digit = || -> Option<char> {
+        let first = src.chars().next()?;
+        first.is_ascii_digit().then_some(first)
+    };
+    fn double(value: u64) -> u64 {
+        return value * 2;
+    }
+
+    if first_digit().is_none() {
+        return Err(ParseError(src.to_string()));
+    }
+
+    let value: u64 = src.parse()?;
+    Ok(double(value))
+}
+
+#[my_logger::log_errors(process = "Parser")]
+fn parse_all(items: &[&str]) -> Result<Vec<u64>, ParseError> {
+    let mut result = Vec::new();
+    for item in items {
+        result.push(parse(item)?);
+    }
+
+    Ok(result)
+}
+
+fn main() {
+    let logs = my_logger::LogCapture::start();
+
+    assert_eq!(4, parse("2").unwrap());
+    assert!(logs.is_empty());
+
+    assert!(parse("").is_err());
+    my_logger::assert_logged!(Error, process = "Parser", message = "Can not parse empty string", context "src" = "\"\"");
+
+    assert!(parse("1x").is_err());
+    my_logger::assert_logged!(Error, process = "Parser", context "src" = "\"1x\"");
+
+    assert!(parse_all(&["1", "a"]).is_err());
+    my_logger::assert_logged!(Error, process = "Parser", message = "Can not parse a");
+    assert_eq!(4, logs.len());
+}
@@ -0,0 +1,8 @@
+#[my_logger::log_errors(args(order_id))]
+fn load_order(id: u64) -> Result<u64, String> {
+    Ok(id)
+}
+
+fn main() {
+    let _ = load_order(1);
+}
@@ -0,0 +1,8 @@
+#[my_logger::log_errors]
+fn load_order(id: u64) {
+    println!("{}", id);
+}
+
+fn main() {
+    load_order(1);
+}
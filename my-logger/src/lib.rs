@@ -1,115 +1,488 @@
 extern crate my_logger_core;
+// Lets code generated by my-logger-macros refer to `::my_logger` inside this crate
+extern crate self as my_logger;
 
 pub use my_logger_core::*;
+
+#[cfg(feature = "macros")]
+pub use my_logger_macros::{log_duration, log_errors};
+#[cfg(feature = "macros")]
+#[doc(hidden)]
+pub mod macros_support;
+
+mod log_result_ext;
+pub use log_result_ext::*;
+mod static_max_level;
+pub use static_max_level::*;
+
+#[cfg(feature = "test-utils")]
+mod log_capture;
+#[cfg(feature = "test-utils")]
+pub use log_capture::*;
+#[cfg(feature = "test-utils")]
+mod log_matcher;
+#[cfg(feature = "test-utils")]
+pub use log_matcher::*;
+
+/// Builds the context of `write_log_*` macros: `key = value` and `"Key" = value` are Debug formatted,
+/// `key = %value` is Display formatted, `ctx = existing_ctx` merges an existing `LogEventCtx`.
+/// `@expr (key) = value` is the old form, where the key is an expression.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __log_ctx {
+    ($ctx:ident;) => {};
+    ($ctx:ident; @expr ($key:expr) = $value:expr $(, $($rest:tt)*)?) => {
+        $ctx = $ctx.add($key, format!("{:?}", $value));
+        $crate::__log_ctx!($ctx; $($($rest)*)?);
+    };
+    ($ctx:ident; ctx = $value:expr $(, $($rest:tt)*)?) => {
+        $ctx = $ctx.merge($value);
+        $crate::__log_ctx!($ctx; $($($rest)*)?);
+    };
+    ($ctx:ident; $key:ident = %$value:expr $(, $($rest:tt)*)?) => {
+        $ctx = $ctx.add(stringify!($key), format!("{}", $value));
+        $crate::__log_ctx!($ctx; $($($rest)*)?);
+    };
+    ($ctx:ident; $key:ident = ?$value:expr $(, $($rest:tt)*)?) => {
+        $ctx = $ctx.add(stringify!($key), format!("{:?}", $value));
+        $crate::__log_ctx!($ctx; $($($rest)*)?);
+    };
+    ($ctx:ident; $key:ident = $value:expr $(, $($rest:tt)*)?) => {
+        $ctx = $ctx.add(stringify!($key), format!("{:?}", $value));
+        $crate::__log_ctx!($ctx; $($($rest)*)?);
+    };
+    ($ctx:ident; $key:literal = %$value:expr $(, $($rest:tt)*)?) => {
+        $ctx = $ctx.add($key, format!("{}", $value));
+        $crate::__log_ctx!($ctx; $($($rest)*)?);
+    };
+    ($ctx:ident; $key:literal = ?$value:expr $(, $($rest:tt)*)?) => {
+        $ctx = $ctx.add($key, format!("{:?}", $value));
+        $crate::__log_ctx!($ctx; $($($rest)*)?);
+    };
+    ($ctx:ident; $key:literal = $value:expr $(, $($rest:tt)*)?) => {
+        $ctx = $ctx.add($key, format!("{:?}", $value));
+        $crate::__log_ctx!($ctx; $($($rest)*)?);
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __log_message {
+    // Only messages with arguments are format strings, `{` and `}` in other messages are written as is.
+    // So inline captures like `"User {name}"` are not formatted without arguments; pass `format!(...)` instead
+    ($fmt:literal, $($args:tt)+) => {
+        format!($fmt, $($args)+)
+    };
+    ($message:expr $(,)?) => {
+        $message.to_string()
+    };
+}
+
+/// Message and context are formatted only if the level is compiled in and enabled on `LOGGER`
+/// and the process passes its process filters.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __write_log {
+    // Old form without `;`: `write_log_info!(process, message, KEY = value, "Key" = value)`.
+    // Keys are expressions, so a `const` key is written by its value
+    ($level:ident; $process:expr, $message:expr $(, $key:tt = $value:expr)* $(,)?) => {
+        $crate::__write_log!(@write $level, $process, [$message]; $(@expr ($key) = $value),*)
+    };
+    ($level:ident; $process:expr, $($rest:tt)+) => {
+        $crate::__write_log!(@split $level, $process, []; $($rest)+)
+    };
+    (@split $level:ident, $process:expr, [$($message:tt)*]; ; $($ctx:tt)*) => {
+        $crate::__write_log!(@write $level, $process, [$($message)*]; $($ctx)*)
+    };
+    (@split $level:ident, $process:expr, [$($message:tt)*];) => {
+        $crate::__write_log!(@write $level, $process, [$($message)*];)
+    };
+    (@split $level:ident, $process:expr, [$($message:tt)*]; $next:tt $($rest:tt)*) => {
+        $crate::__write_log!(@split $level, $process, [$($message)* $next]; $($rest)*)
+    };
+    (@write $level:ident, $process:expr, [$($message:tt)*]; $($ctx:tt)*) => {{
+        if $crate::is_level_compiled($crate::LogLevel::$level)
+            && $crate::LOGGER.is_level_enabled($crate::LogLevel::$level)
+        {
+            let process = $process.to_string();
+
+            if $crate::LOGGER.is_enabled($crate::LogLevel::$level, process.as_str()) {
+                #[allow(unused_mut)]
+                let mut logger_context = $crate::LogEventCtx::new();
+                $crate::__log_ctx!(logger_context; $($ctx)*);
+
+                $crate::LOGGER.write_log_from_module(
+                    $crate::LogLevel::$level,
+                    process,
+                    $crate::__log_message!($($message)*),
+                    logger_context.get_result(),
+                    module_path!(),
+                );
+            }
+        }
+    }};
+}
+
+/// `write_log_debug!("Process", "User {} logged in", id; user = %name, req = ?req, ctx = existing_ctx)`
 #[macro_export]
 macro_rules! write_log_debug {
-    (
-        $process:expr,
-        $message:expr,
-        $( $key:tt = $value:expr ),*
-    ) => {
-        let mut logger_context = my_logger::LogEventCtx::new();
-        $(
-            let value = format!("{:?}", $value);
-            logger_context = logger_context.add($key, value);
-        )*
-
-        my_logger::LOGGER.write_log(
-            my_logger::LogLevel::Debug,
-            $process.to_string(),
-            $message.to_string(),
-            logger_context.get_result()
-        );
+    ($($args:tt)+) => {
+        $crate::__write_log!(Debug; $($args)+)
     };
 }
 
+/// `write_log_err!("Process", "User {} logged in", id; user = %name, req = ?req, ctx = existing_ctx)`
 #[macro_export]
 macro_rules! write_log_err {
-    (
-        $process:expr,
-        $message:expr,
-        $( $key:tt = $value:expr ),*
-    ) => {
-        let mut logger_context = my_logger::LogEventCtx::new();
-        $(
-            let value = format!("{:?}", $value);
-            logger_context = logger_context.add($key, value);
-        )*
-
-        my_logger::LOGGER.write_log(
-            my_logger::LogLevel::Error,
-            $process.to_string(),
-            $message.to_string(),
-            logger_context.get_result()
-        );
+    ($($args:tt)+) => {
+        $crate::__write_log!(Error; $($args)+)
     };
 }
 
+/// `write_log_warning!("Process", "User {} logged in", id; user = %name, req = ?req, ctx = existing_ctx)`
 #[macro_export]
 macro_rules! write_log_warning {
-    (
-        $process:expr,
-        $message:expr,
-        $( $key:tt = $value:expr ),*
-    ) => {
-        let mut logger_context = my_logger::LogEventCtx::new();
-        $(
-            let value = format!("{:?}", $value);
-            logger_context = logger_context.add($key, value);
-        )*
-
-        my_logger::LOGGER.write_log(
-            my_logger::LogLevel::Warning,
-            $process.to_string(),
-            $message.to_string(),
-            logger_context.get_result()
-        );
+    ($($args:tt)+) => {
+        $crate::__write_log!(Warning; $($args)+)
     };
 }
 
+/// `write_log_fatal!("Process", "User {} logged in", id; user = %name, req = ?req, ctx = existing_ctx)`
 #[macro_export]
 macro_rules! write_log_fatal {
-    (
-        $process:expr,
-        $message:expr,
-        $( $key:tt = $value:expr ),*
-    ) => {
-        let mut logger_context = my_logger::LogEventCtx::new();
-        $(
-            let value = format!("{:?}", $value);
-            logger_context = logger_context.add($key, value);
-        )*
-
-        my_logger::LOGGER.write_log(
-            my_logger::LogLevel::FatalError,
-            $process.to_string(),
-            $message.to_string(),
-            logger_context.get_result()
-        );
+    ($($args:tt)+) => {
+        $crate::__write_log!(FatalError; $($args)+)
+    };
+}
+
+/// `write_log_trace!("Process", "User {} logged in", id; user = %name, req = ?req, ctx = existing_ctx)`
+#[macro_export]
+macro_rules! write_log_trace {
+    ($($args:tt)+) => {
+        $crate::__write_log!(Trace; $($args)+)
     };
 }
 
+/// `write_log_info!("Process", "User {} logged in", id; user = %name, req = ?req, ctx = existing_ctx)`
 #[macro_export]
 macro_rules! write_log_info {
-    (
-        $process:expr,
-        $message:expr,
-        $( $key:tt = $value:expr ),*
-    ) => {
-        let mut logger_context = my_logger::LogEventCtx::new();
-        $(
-            let value = format!("{:?}", $value);
-            logger_context = logger_context.add($key, value);
-        )*
-
-        my_logger::LOGGER.write_log(
-            my_logger::LogLevel::Info,
-            $process.to_string(),
-            $message.to_string(),
-            logger_context.get_result()
-        );
+    ($($args:tt)+) => {
+        $crate::__write_log!(Info; $($args)+)
+    };
+}
+
+#[cfg(feature = "test-utils")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __log_matcher {
+    ($matcher:expr;) => {
+        $matcher
+    };
+    ($matcher:expr; process = $value:expr $(, $($rest:tt)*)?) => {
+        $crate::__log_matcher!($matcher.process($value); $($($rest)*)?)
+    };
+    ($matcher:expr; process contains $value:expr $(, $($rest:tt)*)?) => {
+        $crate::__log_matcher!($matcher.process_contains($value); $($($rest)*)?)
+    };
+    ($matcher:expr; message = $value:expr $(, $($rest:tt)*)?) => {
+        $crate::__log_matcher!($matcher.message($value); $($($rest)*)?)
+    };
+    ($matcher:expr; message contains $value:expr $(, $($rest:tt)*)?) => {
+        $crate::__log_matcher!($matcher.message_contains($value); $($($rest)*)?)
+    };
+    ($matcher:expr; context $key:literal = $value:expr $(, $($rest:tt)*)?) => {
+        $crate::__log_matcher!($matcher.context($key, $value); $($($rest)*)?)
+    };
+    ($matcher:expr; context $key:literal contains $value:expr $(, $($rest:tt)*)?) => {
+        $crate::__log_matcher!($matcher.context_contains($key, $value); $($($rest)*)?)
+    };
+}
+
+/// Asserts that the active `LogCapture` holds an event matching the pattern:
+/// `assert_logged!(Error, process = "Db", message contains "timeout", context "Host" = "db1")`
+#[cfg(feature = "test-utils")]
+#[macro_export]
+macro_rules! assert_logged {
+    ($level:ident $(, $($rest:tt)*)?) => {
+        $crate::LogCapture::current().assert_logged(&$crate::__log_matcher!(
+            $crate::LogMatcher::new().level($crate::LogLevel::$level); $($($rest)*)?
+        ))
+    };
+}
+
+#[cfg(feature = "test-utils")]
+#[macro_export]
+macro_rules! assert_not_logged {
+    ($level:ident $(, $($rest:tt)*)?) => {
+        $crate::LogCapture::current().assert_not_logged(&$crate::__log_matcher!(
+            $crate::LogMatcher::new().level($crate::LogLevel::$level); $($($rest)*)?
+        ))
     };
 }
 
 #[cfg(feature = "my-seq-logger")]
 pub extern crate my_seq_logger;
+
+#[cfg(feature = "my-file-logger")]
+pub extern crate my_file_logger;
+
+#[cfg(feature = "my-syslog-logger")]
+pub extern crate my_syslog_logger;
+
+#[cfg(feature = "my-journald-logger")]
+pub extern crate my_journald_logger;
+
+#[cfg(feature = "my-otlp-logger")]
+pub extern crate my_otlp_logger;
+
+#[cfg(feature = "my-elastic-logger")]
+pub extern crate my_elastic_logger;
+
+#[cfg(feature = "my-loki-logger")]
+pub extern crate my_loki_logger;
+
+#[cfg(feature = "my-gelf-logger")]
+pub extern crate my_gelf_logger;
+
+#[cfg(feature = "my-socket-logger")]
+pub extern crate my_socket_logger;
+
+#[cfg(feature = "my-fluentd-logger")]
+pub extern crate my_fluentd_logger;
+
+#[cfg(feature = "my-webhook-logger")]
+pub extern crate my_webhook_logger;
+
+#[cfg(all(test, feature = "test-utils"))]
+mod tests {
+    use crate::{LogCapture, LogEventCtx};
+
+    #[test]
+    fn test_write_log_macros() {
+        if !crate::is_level_compiled(crate::LogLevel::Warning) {
+            return;
+        }
+
+        let _logs = LogCapture::start();
+        let name = "admin";
+        let existing_ctx = LogEventCtx::new().add("Host", "db1");
+
+        crate::write_log_info!("Auth", "User {} logged in", 42; user = %name, id = ?42, ctx = existing_ctx);
+        crate::write_log_err!("Db", "Connection timeout", "Host" = "db2");
+        crate::write_log_warning!("Cache", format!("Miss: {}", name));
+
+        crate::assert_logged!(Info, process = "Auth", message = "User 42 logged in", context "user" = "admin", context "id" = "42", context "Host" = "db1");
+        crate::assert_logged!(Error, process = "Db", context "Host" = "\"db2\"");
+        crate::assert_logged!(Warning, message = "Miss: admin");
+    }
+
+    #[test]
+    fn test_write_log_macros_old_form() {
+        if !crate::is_level_compiled(crate::LogLevel::Info) {
+            return;
+        }
+
+        const HOST_KEY: &str = "Host";
+        let _logs = LogCapture::start();
+
+        crate::write_log_info!("Db", "Brackets {} stay", HOST_KEY = "db1", "Port" = 5432,);
+
+        crate::assert_logged!(Info, process = "Db", message = "Brackets {} stay", context "Host" = "\"db1\"", context "Port" = "5432");
+
+        let name = "admin";
+        crate::write_log_info!("Db", "User {name} stays");
+        crate::write_log_info!("Db", format!("User {name} is formatted"));
+
+        crate::assert_logged!(Info, process = "Db", message = "User {name} stays");
+        crate::assert_logged!(Info, process = "Db", message = "User admin is formatted");
+    }
+
+    #[test]
+    fn test_write_log_macros_skip_formatting_of_filtered_processes() {
+        if !crate::is_level_compiled(crate::LogLevel::Info) {
+            return;
+        }
+
+        struct PanicOnFormat;
+
+        impl std::fmt::Display for PanicOnFormat {
+            fn fmt(&self, _: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                panic!("Filtered event is formatted");
+            }
+        }
+
+        crate::LOGGER.add_process_filter(|_, process| process != "FilteredByProcess");
+        let _logs = LogCapture::start();
+
+        crate::write_log_info!("FilteredByProcess", "Value {}", PanicOnFormat; value = %PanicOnFormat);
+        crate::assert_not_logged!(Info, process = "FilteredByProcess");
+    }
+
+    #[test]
+    fn test_write_log_macros_capture_source_location() {
+        if !crate::is_level_compiled(crate::LogLevel::Info) {
+            return;
+        }
+
+        let _logs = LogCapture::start();
+
+        crate::write_log_info!("Auth", "Logged in");
+        let line = line!() - 1;
+
+        let event = crate::assert_logged!(Info, process = "Auth");
+        assert_eq!(Some(file!()), event.file);
+        assert_eq!(Some(line), event.line);
+        assert_eq!(Some(module_path!()), event.module_path);
+    }
+
+    #[test]
+    fn test_measure_guard() {
+        let _logs = LogCapture::start();
+
+        let guard = crate::LOGGER
+            .measure(
+                "Snapshot",
+                "Loading snapshot",
+                LogEventCtx::new().add("Id", "1"),
+            )
+            .warn_over(std::time::Duration::ZERO);
+        std::thread::sleep(std::time::Duration::from_millis(1));
+        guard.finish_with(LogEventCtx::new().add("Outcome", "Ok"));
+
+        let event = crate::assert_logged!(Warning, process = "Snapshot", message = "Loading snapshot", context "Id" = "1", context "Outcome" = "Ok");
+        let duration_ms: f64 = event.context.as_ref().unwrap()[crate::DURATION_MS_KEY]
+            .parse()
+            .unwrap();
+        assert!(duration_ms >= 1.0);
+
+        {
+            let _guard = crate::LOGGER.measure("Snapshot", "Saving snapshot", LogEventCtx::new());
+        }
+        crate::assert_logged!(Info, process = "Snapshot", message = "Saving snapshot");
+    }
+
+    #[tokio::test]
+    async fn test_events_carry_trace_context() {
+        let trace_context = crate::TraceContext::new_root();
+
+        let (_, logs) = LogCapture::scope(trace_context.clone().scope(async {
+            crate::LOGGER.write_info("Api", "Request", LogEventCtx::new());
+        }))
+        .await;
+
+        let event = logs.assert_logged(&crate::LogMatcher::new().process("Api"));
+        assert_eq!(Some(trace_context), event.trace_context);
+    }
+
+    #[test]
+    fn test_spans() {
+        let _logs = LogCapture::start();
+
+        let mut parent = crate::LOGGER
+            .start_span(
+                "Orders",
+                "LoadOrders",
+                LogEventCtx::new().add("ClientId", "1"),
+            )
+            .kind(crate::SpanKind::Server);
+
+        {
+            let _entered = parent.enter();
+            crate::LOGGER.write_info("Orders", "Loading", LogEventCtx::new());
+
+            let mut child = crate::LOGGER.start_span("Db", "Query", LogEventCtx::new());
+            child.set_error("Timeout");
+        }
+
+        parent.set_status(crate::SpanStatus::Ok);
+        let parent_trace_context = parent.get_trace_context().clone();
+        drop(parent);
+
+        let child = crate::assert_logged!(Error, process = "Db", message = "Query", context "Error" = "Timeout", context "SpanStatus" = "Error");
+        let child_trace_context = child.trace_context.clone().unwrap();
+        assert_eq!(parent_trace_context.trace_id, child_trace_context.trace_id);
+        assert_eq!(
+            Some(parent_trace_context.span_id.clone()),
+            child_trace_context.parent_span_id
+        );
+
+        let parent = crate::assert_logged!(Info, process = "Orders", message = "LoadOrders", context "ClientId" = "1", context "SpanStatus" = "Ok");
+        let span = parent.span.as_ref().unwrap();
+        assert_eq!(crate::SpanKind::Server, span.kind);
+        assert!(span.started.unix_microseconds <= parent.dt.unix_microseconds);
+
+        let event = crate::assert_logged!(Info, message = "Loading");
+        assert_eq!(Some(parent_trace_context), event.trace_context);
+        assert!(event.span.is_none());
+    }
+}
+
+#[cfg(all(test, feature = "macros", feature = "test-utils"))]
+mod macros_tests {
+    use crate::LogCapture;
+
+    #[derive(Debug)]
+    struct TestError;
+
+    impl std::fmt::Display for TestError {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.write_str("Not found")
+        }
+    }
+
+    #[crate::log_errors(process = "Orders", args(order_id))]
+    fn load_order(order_id: u64, fail: bool) -> Result<u64, TestError> {
+        if fail {
+            return Err(TestError);
+        }
+
+        Ok(order_id)
+    }
+
+    #[crate::log_errors(args(order_id))]
+    async fn load_order_async(order_id: u64) -> Result<u64, TestError> {
+        let order_id = load_order(order_id, true)?;
+        Ok(order_id)
+    }
+
+    #[crate::log_duration(warn_over_ms = 0)]
+    fn slow_fn() -> u64 {
+        std::thread::sleep(std::time::Duration::from_millis(2));
+        5
+    }
+
+    #[test]
+    fn test_log_errors() {
+        let logs = LogCapture::start();
+
+        assert_eq!(1, load_order(1, false).unwrap());
+        assert!(logs.is_empty());
+
+        assert!(load_order(2, true).is_err());
+        crate::assert_logged!(Error, process = "Orders", message = "Not found", context "order_id" = "2");
+    }
+
+    #[tokio::test]
+    async fn test_log_errors_async_uses_fn_path_as_process() {
+        let (result, logs) = LogCapture::scope(load_order_async(3)).await;
+
+        assert!(result.is_err());
+        logs.assert_logged(
+            &crate::LogMatcher::new()
+                .process(concat!(module_path!(), "::load_order_async"))
+                .context("order_id", "3"),
+        );
+    }
+
+    #[test]
+    fn test_log_duration_escalates_to_warning() {
+        let _logs = LogCapture::start();
+
+        assert_eq!(5, slow_fn());
+        crate::assert_logged!(
+            Warning,
+            process = concat!(module_path!(), "::slow_fn"),
+            message = "slow_fn finished"
+        );
+    }
+}
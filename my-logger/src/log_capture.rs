@@ -0,0 +1,199 @@
+use std::{
+    cell::RefCell,
+    future::Future,
+    sync::{Arc, LazyLock},
+};
+
+use my_logger_core::{MyLogEvent, MyLoggerReader, LOGGER};
+use parking_lot::Mutex;
+
+use crate::LogMatcher;
+
+thread_local! {
+    static THREAD_CAPTURES: RefCell<Vec<LogCapture>> = const { RefCell::new(Vec::new()) };
+}
+
+tokio::task_local! {
+    static TASK_CAPTURE: LogCapture;
+}
+
+static CAPTURE_READER: LazyLock<Arc<CaptureReader>> = LazyLock::new(|| {
+    let reader = Arc::new(CaptureReader);
+    LOGGER.plug_reader(reader.clone());
+    reader
+});
+
+struct CaptureReader;
+
+impl MyLoggerReader for CaptureReader {
+    fn write_log(&self, log_event: Arc<MyLogEvent>) {
+        if let Some(capture) = LogCapture::try_current() {
+            capture.events.lock().push(log_event);
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct LogCapture {
+    events: Arc<Mutex<Vec<Arc<MyLogEvent>>>>,
+}
+
+impl LogCapture {
+    fn new() -> Self {
+        LazyLock::force(&CAPTURE_READER);
+        Self {
+            events: Arc::new(Mutex::new(Vec::new())),
+        }
+    }
+
+    /// Captures events written through `LOGGER` on the current thread until the guard is dropped.
+    pub fn start() -> LogCaptureGuard {
+        let capture = Self::new();
+        THREAD_CAPTURES.with(|captures| captures.borrow_mut().push(capture.clone()));
+        LogCaptureGuard { capture }
+    }
+
+    /// Captures events written through `LOGGER` while `future` runs.
+    ///
+    /// Tasks spawned from inside the future are not part of the scope.
+    pub async fn scope<TFuture: Future>(future: TFuture) -> (TFuture::Output, LogCapture) {
+        let capture = Self::new();
+        let result = TASK_CAPTURE.scope(capture.clone(), future).await;
+        (result, capture)
+    }
+
+    pub fn try_current() -> Option<LogCapture> {
+        if let Ok(capture) = TASK_CAPTURE.try_with(|capture| capture.clone()) {
+            return Some(capture);
+        }
+
+        THREAD_CAPTURES.with(|captures| captures.borrow().last().cloned())
+    }
+
+    pub fn current() -> LogCapture {
+        match Self::try_current() {
+            Some(capture) => capture,
+            None => panic!(
+                "No active log capture. Use LogCapture::start() or LogCapture::scope() first"
+            ),
+        }
+    }
+
+    pub fn events(&self) -> Vec<Arc<MyLogEvent>> {
+        self.events.lock().clone()
+    }
+
+    pub fn len(&self) -> usize {
+        self.events.lock().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.events.lock().is_empty()
+    }
+
+    pub fn clear(&self) {
+        self.events.lock().clear();
+    }
+
+    pub fn find(&self, matcher: &LogMatcher) -> Option<Arc<MyLogEvent>> {
+        self.events
+            .lock()
+            .iter()
+            .find(|itm| matcher.matches(itm))
+            .cloned()
+    }
+
+    pub fn assert_logged(&self, matcher: &LogMatcher) -> Arc<MyLogEvent> {
+        let events = self.events();
+        if let Some(event) = events.iter().find(|itm| matcher.matches(itm)) {
+            return event.clone();
+        }
+
+        panic!(
+            "assert_logged! failed: no captured event matches\n{}",
+            matcher.describe_mismatches(&events)
+        );
+    }
+
+    pub fn assert_not_logged(&self, matcher: &LogMatcher) {
+        let events = self.events();
+        let matched: Vec<_> = events
+            .iter()
+            .filter(|itm| matcher.matches(itm))
+            .cloned()
+            .collect();
+
+        if !matched.is_empty() {
+            panic!(
+                "assert_not_logged! failed: {} captured event(s) match\n{}",
+                matched.len(),
+                matcher.describe_mismatches(&matched)
+            );
+        }
+    }
+}
+
+pub struct LogCaptureGuard {
+    capture: LogCapture,
+}
+
+impl std::ops::Deref for LogCaptureGuard {
+    type Target = LogCapture;
+
+    fn deref(&self) -> &Self::Target {
+        &self.capture
+    }
+}
+
+impl Drop for LogCaptureGuard {
+    fn drop(&mut self) {
+        THREAD_CAPTURES.with(|captures| {
+            let mut captures = captures.borrow_mut();
+            if let Some(index) = captures
+                .iter()
+                .rposition(|itm| Arc::ptr_eq(&itm.events, &self.capture.events))
+            {
+                captures.remove(index);
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use my_logger_core::{LogEventCtx, LOGGER};
+
+    use super::LogCapture;
+
+    #[test]
+    fn test_capture_is_scoped_to_guard() {
+        let logs = LogCapture::start();
+        LOGGER.write_error("Db", "Connection timeout", LogEventCtx::new().add("Host", "db1"));
+
+        crate::assert_logged!(Error, process = "Db", message contains "timeout", context "Host" = "db1");
+        crate::assert_not_logged!(Info);
+        assert_eq!(1, logs.len());
+
+        drop(logs);
+        assert!(LogCapture::try_current().is_none());
+    }
+
+    #[test]
+    #[should_panic(expected = "no captured event matches")]
+    fn test_assert_logged_fails_on_mismatch() {
+        let _logs = LogCapture::start();
+        LOGGER.write_info("Db", "Connected", LogEventCtx::new());
+
+        crate::assert_logged!(Error, process = "Db");
+    }
+
+    #[tokio::test]
+    async fn test_capture_scope() {
+        let (_, logs) = LogCapture::scope(async {
+            LOGGER.write_warning("Cache", "Miss", LogEventCtx::new());
+        })
+        .await;
+
+        logs.assert_logged(&crate::LogMatcher::new().process("Cache"));
+    }
+}
@@ -0,0 +1,193 @@
+use std::{fmt::Write, sync::Arc};
+
+use my_logger_core::{LogLevel, MyLogEvent};
+
+#[derive(Debug, Clone)]
+pub enum TextMatch {
+    Equals(String),
+    Contains(String),
+}
+
+impl TextMatch {
+    pub fn matches(&self, value: &str) -> bool {
+        match self {
+            TextMatch::Equals(expected) => value == expected,
+            TextMatch::Contains(expected) => value.contains(expected.as_str()),
+        }
+    }
+
+    fn describe(&self) -> String {
+        match self {
+            TextMatch::Equals(expected) => format!("= {:?}", expected),
+            TextMatch::Contains(expected) => format!("contains {:?}", expected),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct LogMatcher {
+    level: Option<LogLevel>,
+    process: Option<TextMatch>,
+    message: Option<TextMatch>,
+    context: Vec<(String, TextMatch)>,
+}
+
+impl LogMatcher {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn level(mut self, level: LogLevel) -> Self {
+        self.level = Some(level);
+        self
+    }
+
+    pub fn process(mut self, value: impl Into<String>) -> Self {
+        self.process = Some(TextMatch::Equals(value.into()));
+        self
+    }
+
+    pub fn process_contains(mut self, value: impl Into<String>) -> Self {
+        self.process = Some(TextMatch::Contains(value.into()));
+        self
+    }
+
+    pub fn message(mut self, value: impl Into<String>) -> Self {
+        self.message = Some(TextMatch::Equals(value.into()));
+        self
+    }
+
+    pub fn message_contains(mut self, value: impl Into<String>) -> Self {
+        self.message = Some(TextMatch::Contains(value.into()));
+        self
+    }
+
+    pub fn context(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.context
+            .push((key.into(), TextMatch::Equals(value.into())));
+        self
+    }
+
+    pub fn context_contains(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.context
+            .push((key.into(), TextMatch::Contains(value.into())));
+        self
+    }
+
+    pub fn matches(&self, log_event: &MyLogEvent) -> bool {
+        self.get_mismatches(log_event).is_empty()
+    }
+
+    fn get_mismatches(&self, log_event: &MyLogEvent) -> Vec<String> {
+        let mut result = Vec::new();
+
+        if let Some(level) = &self.level {
+            if *level != log_event.level {
+                result.push(format!(
+                    "level: expected {}, got {}",
+                    level.as_str(),
+                    log_event.level.as_str()
+                ));
+            }
+        }
+
+        if let Some(process) = &self.process {
+            if !process.matches(&log_event.process) {
+                result.push(format!(
+                    "process: expected {}, got {:?}",
+                    process.describe(),
+                    log_event.process
+                ));
+            }
+        }
+
+        if let Some(message) = &self.message {
+            if !message.matches(&log_event.message) {
+                result.push(format!(
+                    "message: expected {}, got {:?}",
+                    message.describe(),
+                    log_event.message
+                ));
+            }
+        }
+
+        for (key, expected) in &self.context {
+            let value = log_event
+                .context
+                .as_ref()
+                .and_then(|ctx| ctx.get(key.as_str()));
+
+            match value {
+                Some(value) => {
+                    if !expected.matches(value) {
+                        result.push(format!(
+                            "context[{:?}]: expected {}, got {:?}",
+                            key,
+                            expected.describe(),
+                            value
+                        ));
+                    }
+                }
+                None => {
+                    result.push(format!(
+                        "context[{:?}]: expected {}, key is missing",
+                        key,
+                        expected.describe()
+                    ));
+                }
+            }
+        }
+
+        result
+    }
+
+    pub fn describe(&self) -> String {
+        let mut parts = Vec::new();
+
+        if let Some(level) = &self.level {
+            parts.push(format!("level = {}", level.as_str()));
+        }
+
+        if let Some(process) = &self.process {
+            parts.push(format!("process {}", process.describe()));
+        }
+
+        if let Some(message) = &self.message {
+            parts.push(format!("message {}", message.describe()));
+        }
+
+        for (key, expected) in &self.context {
+            parts.push(format!("context[{:?}] {}", key, expected.describe()));
+        }
+
+        if parts.is_empty() {
+            return "any event".to_string();
+        }
+
+        parts.join(", ")
+    }
+
+    /// Renders the matcher and, for every event, the fields that differ from it.
+    pub fn describe_mismatches(&self, events: &[Arc<MyLogEvent>]) -> String {
+        let mut result = String::new();
+        let _ = writeln!(&mut result, "  expected: {}", self.describe());
+        let _ = writeln!(&mut result, "  captured {} event(s):", events.len());
+
+        for (index, log_event) in events.iter().enumerate() {
+            let _ = writeln!(
+                &mut result,
+                "    [{}] {} process: {:?} message: {:?}",
+                index,
+                log_event.level.as_str(),
+                log_event.process,
+                log_event.message
+            );
+
+            for mismatch in self.get_mismatches(log_event) {
+                let _ = writeln!(&mut result, "        - {}", mismatch);
+            }
+        }
+
+        result
+    }
+}
@@ -0,0 +1,154 @@
+use rust_extensions::StrOrString;
+
+use crate::{LogEventCtx, LogLevel, LOGGER};
+
+const CAUSED_BY_KEY: &str = "CausedBy";
+
+/// Writes the error of a `Result` through `LOGGER` and returns the `Result` unchanged:
+/// `let rows = read_rows().await.log_err("ReadRows", LogEventCtx::new())?;`
+///
+/// The message is the error itself, the chain of its sources goes to the `CausedBy` context field.
+pub trait LogResultExt {
+    fn log_err(self, process: impl Into<StrOrString<'static>>, ctx: LogEventCtx) -> Self;
+    fn log_warn_if_err(self, process: impl Into<StrOrString<'static>>, ctx: LogEventCtx) -> Self;
+}
+
+impl<T, E: std::error::Error> LogResultExt for Result<T, E> {
+    #[track_caller]
+    fn log_err(self, process: impl Into<StrOrString<'static>>, ctx: LogEventCtx) -> Self {
+        if let Err(err) = &self {
+            write_error(LogLevel::Error, process, err, ctx);
+        }
+
+        self
+    }
+
+    #[track_caller]
+    fn log_warn_if_err(self, process: impl Into<StrOrString<'static>>, ctx: LogEventCtx) -> Self {
+        if let Err(err) = &self {
+            write_error(LogLevel::Warning, process, err, ctx);
+        }
+
+        self
+    }
+}
+
+/// Writes a warning through `LOGGER` if the `Option` is `None` and returns it unchanged.
+pub trait LogOptionExt {
+    fn log_if_none<'s>(
+        self,
+        process: impl Into<StrOrString<'static>>,
+        message: impl Into<StrOrString<'s>>,
+        ctx: LogEventCtx,
+    ) -> Self;
+}
+
+impl<T> LogOptionExt for Option<T> {
+    #[track_caller]
+    fn log_if_none<'s>(
+        self,
+        process: impl Into<StrOrString<'static>>,
+        message: impl Into<StrOrString<'s>>,
+        ctx: LogEventCtx,
+    ) -> Self {
+        if self.is_none() {
+            LOGGER.write_log(
+                LogLevel::Warning,
+                process.into().to_string(),
+                message.into().to_string(),
+                ctx.get_result(),
+            );
+        }
+
+        self
+    }
+}
+
+#[track_caller]
+fn write_error(
+    level: LogLevel,
+    process: impl Into<StrOrString<'static>>,
+    err: &dyn std::error::Error,
+    mut ctx: LogEventCtx,
+) {
+    if let Some(caused_by) = get_caused_by(err) {
+        ctx = ctx.add(CAUSED_BY_KEY, caused_by);
+    }
+
+    LOGGER.write_log(
+        level,
+        process.into().to_string(),
+        err.to_string(),
+        ctx.get_result(),
+    );
+}
+
+fn get_caused_by(err: &dyn std::error::Error) -> Option<String> {
+    let mut source = err.source()?;
+    let mut result = source.to_string();
+
+    while let Some(next) = source.source() {
+        result.push_str(" -> ");
+        result.push_str(next.to_string().as_str());
+        source = next;
+    }
+
+    Some(result)
+}
+
+#[cfg(all(test, feature = "test-utils"))]
+mod tests {
+    use crate::{LogCapture, LogEventCtx};
+
+    use super::{LogOptionExt, LogResultExt};
+
+    #[derive(Debug)]
+    struct TestError(&'static str, Option<Box<TestError>>);
+
+    impl std::fmt::Display for TestError {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.write_str(self.0)
+        }
+    }
+
+    impl std::error::Error for TestError {
+        fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+            self.1.as_deref().map(|itm| itm as _)
+        }
+    }
+
+    #[test]
+    fn test_log_err_writes_source_chain() {
+        let _logs = LogCapture::start();
+
+        let err = TestError(
+            "Query failed",
+            Some(Box::new(TestError(
+                "Connection reset",
+                Some(Box::new(TestError("Timeout", None))),
+            ))),
+        );
+
+        let result: Result<(), TestError> = Err(err);
+        let result = result.log_err("Db", LogEventCtx::new().add("Table", "users"));
+
+        assert!(result.is_err());
+        crate::assert_logged!(Error, process = "Db", message = "Query failed", context "CausedBy" = "Connection reset -> Timeout", context "Table" = "users");
+    }
+
+    #[test]
+    fn test_ok_and_some_are_not_logged() {
+        let logs = LogCapture::start();
+
+        let result: Result<u32, TestError> = Ok(5);
+        assert_eq!(5, result.log_warn_if_err("Db", LogEventCtx::new()).unwrap());
+        assert_eq!(
+            Some(1),
+            Some(1).log_if_none("Cache", "Miss", LogEventCtx::new())
+        );
+        assert!(logs.is_empty());
+
+        None::<u32>.log_if_none("Cache", "Miss", LogEventCtx::new());
+        crate::assert_logged!(Warning, process = "Cache", message = "Miss");
+    }
+}
@@ -0,0 +1,23 @@
+//! Used by code generated by `my-logger-macros`. Not a public API.
+
+use std::time::Duration;
+
+use crate::{LogEventCtx, LogLevel, MeasureGuard, LOGGER};
+
+#[track_caller]
+pub fn measure_fn(
+    process: &'static str,
+    fn_name: &'static str,
+    warn_over: Option<Duration>,
+    module_path: &'static str,
+) -> MeasureGuard {
+    let guard = LOGGER
+        .measure(process, format!("{} finished", fn_name), LogEventCtx::new())
+        .level(LogLevel::Debug)
+        .module_path(module_path);
+
+    match warn_over {
+        Some(warn_over) => guard.warn_over(warn_over),
+        None => guard,
+    }
+}
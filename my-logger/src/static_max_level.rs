@@ -0,0 +1,56 @@
+use my_logger_core::LogLevel;
+
+const MAX_LEVEL: Option<LogLevel> = if cfg!(feature = "max_level_off") {
+    None
+} else if cfg!(feature = "max_level_fatal") {
+    Some(LogLevel::FatalError)
+} else if cfg!(feature = "max_level_error") {
+    Some(LogLevel::Error)
+} else if cfg!(feature = "max_level_warning") {
+    Some(LogLevel::Warning)
+} else if cfg!(feature = "max_level_info") {
+    Some(LogLevel::Info)
+} else if cfg!(feature = "max_level_debug") {
+    Some(LogLevel::Debug)
+} else {
+    Some(LogLevel::Trace)
+};
+
+const RELEASE_MAX_LEVEL: Option<LogLevel> = if cfg!(feature = "release_max_level_off") {
+    None
+} else if cfg!(feature = "release_max_level_fatal") {
+    Some(LogLevel::FatalError)
+} else if cfg!(feature = "release_max_level_error") {
+    Some(LogLevel::Error)
+} else if cfg!(feature = "release_max_level_warning") {
+    Some(LogLevel::Warning)
+} else if cfg!(feature = "release_max_level_info") {
+    Some(LogLevel::Info)
+} else if cfg!(feature = "release_max_level_debug") {
+    Some(LogLevel::Debug)
+} else if cfg!(feature = "release_max_level_trace") {
+    Some(LogLevel::Trace)
+} else {
+    MAX_LEVEL
+};
+
+/// Least severe level compiled into `write_log_*` macros, `None` compiles out everything.
+/// Selected by `max_level_*` features, or by `release_max_level_*` features when `debug_assertions` are off.
+/// Among features of the same kind the most restrictive one wins. Without `debug_assertions` a
+/// `release_max_level_*` feature replaces `max_level_*` even if it is less restrictive; without any
+/// `release_max_level_*` feature release builds use `max_level_*`.
+const STATIC_MAX_LEVEL: Option<LogLevel> = if cfg!(debug_assertions) {
+    MAX_LEVEL
+} else {
+    RELEASE_MAX_LEVEL
+};
+
+/// `false` if the level is compiled out by `max_level_*` features.
+/// The `write_log_*` macros check it first, so their arguments are not evaluated and the call is optimized away.
+pub const fn is_level_compiled(level: LogLevel) -> bool {
+    match STATIC_MAX_LEVEL {
+        // Variants are declared in severity order; `Ord` is not usable in const fn
+        Some(max_level) => level as u8 >= max_level as u8,
+        None => false,
+    }
+}
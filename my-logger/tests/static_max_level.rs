@@ -0,0 +1,49 @@
+// Features are chosen per build, so this only runs with
+// `cargo test -p my-logger --features test-utils,max_level_warning --test static_max_level`
+#![cfg(all(
+    feature = "test-utils",
+    feature = "max_level_warning",
+    not(any(
+        feature = "max_level_off",
+        feature = "max_level_fatal",
+        feature = "max_level_error"
+    ))
+))]
+
+use my_logger::{LogCapture, LogLevel};
+
+struct NotFormatted;
+
+impl std::fmt::Display for NotFormatted {
+    fn fmt(&self, _: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        panic!("Arguments of a compiled out level must not be formatted")
+    }
+}
+
+fn not_evaluated() -> &'static str {
+    panic!("Arguments of a compiled out level must not be evaluated")
+}
+
+#[test]
+fn test_levels_below_max_level_are_compiled_out() {
+    assert!(!my_logger::is_level_compiled(LogLevel::Trace));
+    assert!(!my_logger::is_level_compiled(LogLevel::Debug));
+    assert!(!my_logger::is_level_compiled(LogLevel::Info));
+    assert!(my_logger::is_level_compiled(LogLevel::Warning));
+    assert!(my_logger::is_level_compiled(LogLevel::Error));
+    assert!(my_logger::is_level_compiled(LogLevel::FatalError));
+
+    let logs = LogCapture::start();
+
+    my_logger::write_log_trace!("Test", "Trace {}", NotFormatted);
+    my_logger::write_log_debug!("Test", "Debug {}", NotFormatted);
+    my_logger::write_log_info!(not_evaluated(), "Info {}", NotFormatted);
+    assert!(logs.is_empty());
+
+    my_logger::write_log_warning!("Test", "Warning {}", 1);
+    my_logger::write_log_err!("Test", "Error {}", 2);
+
+    assert_eq!(2, logs.len());
+    my_logger::assert_logged!(Warning, process = "Test", message = "Warning 1");
+    my_logger::assert_logged!(Error, process = "Test", message = "Error 2");
+}
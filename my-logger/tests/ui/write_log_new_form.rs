@@ -0,0 +1,10 @@
+fn main() {
+    let id = 5;
+    let name = "admin";
+    let ctx = my_logger::LogEventCtx::new().add("Host", "db1");
+
+    my_logger::write_log_info!("Auth", "User {} logged in", id);
+    my_logger::write_log_info!("Auth", "User {} logged in", id; user = %name, id = ?id, ctx = ctx);
+    my_logger::write_log_trace!("Auth", "Logged in"; "User" = name);
+    my_logger::write_log_err!("Auth", "User {id} failed", id = id; user = %name);
+}
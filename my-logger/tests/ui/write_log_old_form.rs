@@ -0,0 +1,13 @@
+const CLIENT_ID_KEY: &str = "ClientId";
+
+fn main() {
+    let client_id = 5;
+    let err = "Timeout";
+
+    my_logger::write_log_info!("Orders", "Started",);
+    my_logger::write_log_info!("Orders", "Started");
+    my_logger::write_log_err!("Orders", "Invalid json {\"id\": 1}", "Err" = err);
+    my_logger::write_log_warning!("Orders", format!("Order {}", 1), CLIENT_ID_KEY = client_id);
+    my_logger::write_log_debug!("Orders", "Placeholder {} is kept", CLIENT_ID_KEY = client_id, "Err" = err,);
+    my_logger::write_log_fatal!("Orders".to_string(), String::from("Crashed"), (CLIENT_ID_KEY) = client_id);
+}
@@ -0,0 +1,7 @@
+// Call forms of `write_log_*!` which must keep compiling
+#[test]
+fn test_write_log_macros_compile() {
+    let t = trybuild::TestCases::new();
+    t.pass("tests/ui/write_log_old_form.rs");
+    t.pass("tests/ui/write_log_new_form.rs");
+}
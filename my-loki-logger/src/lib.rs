@@ -0,0 +1,12 @@
+mod loki_logger;
+pub use loki_logger::LokiLogger;
+mod loki_logger_inner;
+pub use loki_logger_inner::*;
+mod settings;
+pub use settings::*;
+mod loki_labels;
+pub use loki_labels::*;
+mod loki_streams;
+pub use loki_streams::*;
+mod uploader;
+pub use uploader::*;
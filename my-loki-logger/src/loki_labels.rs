@@ -0,0 +1,173 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use my_logger_core::{MyLogEvent, PopulatedParams};
+use parking_lot::Mutex;
+
+const OTHER_LABEL_VALUE: &str = "other";
+
+pub type LokiLabelSet = BTreeMap<String, String>;
+
+/// Builds stream labels: `app` (Application), `env` (EnvInfo), `level`, `process`
+/// and configured context keys.
+///
+/// `process` and context labels are cardinality-limited: once a label has seen `max_label_values`
+/// distinct values, new values are reported as `other` and stay visible in the log line only.
+pub struct LokiLabelsMapper {
+    context_labels: Vec<String>,
+    max_label_values: usize,
+    label_values: Mutex<HashMap<String, HashSet<String>>>,
+}
+
+impl LokiLabelsMapper {
+    pub fn new(context_labels: Vec<String>, max_label_values: usize) -> Self {
+        Self {
+            context_labels,
+            max_label_values,
+            label_values: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn get_labels(
+        &self,
+        log_event: &MyLogEvent,
+        populated_params: &PopulatedParams,
+    ) -> LokiLabelSet {
+        let mut result = BTreeMap::new();
+
+        if let Some(app) = populated_params.get("Application") {
+            result.insert("app".to_string(), app.to_string());
+        }
+
+        if let Some(env) = populated_params.get("EnvInfo") {
+            result.insert("env".to_string(), env.to_string());
+        }
+
+        result.insert("level".to_string(), log_event.level.as_str().to_lowercase());
+
+        let mut label_values = self.label_values.lock();
+
+        let process = self.limit_cardinality(&mut label_values, "process", &log_event.process);
+        result.insert("process".to_string(), process);
+
+        if let Some(ctx) = &log_event.context {
+            for key in &self.context_labels {
+                if let Some(value) = ctx.get(key) {
+                    let name = get_label_name(key);
+                    let value = self.limit_cardinality(&mut label_values, name.as_str(), value);
+                    result.insert(name, value);
+                }
+            }
+        }
+
+        result
+    }
+
+    fn limit_cardinality(
+        &self,
+        label_values: &mut HashMap<String, HashSet<String>>,
+        name: &str,
+        value: &str,
+    ) -> String {
+        let values = label_values.entry(name.to_string()).or_default();
+
+        if values.contains(value) {
+            return value.to_string();
+        }
+
+        if values.len() >= self.max_label_values {
+            return OTHER_LABEL_VALUE.to_string();
+        }
+
+        values.insert(value.to_string());
+        value.to_string()
+    }
+}
+
+/// Label names must match `[a-zA-Z_][a-zA-Z0-9_]*`.
+pub fn get_label_name(src: &str) -> String {
+    let mut result = String::with_capacity(src.len() + 1);
+
+    for c in src.chars() {
+        if c.is_ascii_alphanumeric() || c == '_' {
+            result.push(c);
+        } else {
+            result.push('_');
+        }
+    }
+
+    if result.is_empty() || result.as_bytes()[0].is_ascii_digit() {
+        result.insert(0, '_');
+    }
+
+    result
+}
+
+/// Prometheus-style selector used by the protobuf encoding: `{app="pricing", level="info"}`.
+pub fn format_label_set(labels: &LokiLabelSet) -> String {
+    let mut result = String::from("{");
+
+    for (index, (name, value)) in labels.iter().enumerate() {
+        if index > 0 {
+            result.push_str(", ");
+        }
+
+        result.push_str(name);
+        result.push_str("=\"");
+        for c in value.chars() {
+            match c {
+                '"' => result.push_str("\\\""),
+                '\\' => result.push_str("\\\\"),
+                '\n' => result.push_str("\\n"),
+                _ => result.push(c),
+            }
+        }
+        result.push('"');
+    }
+
+    result.push('}');
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use my_logger_core::{test_utils::create_test_event, LogLevel, MyLogEvent, PopulatedParams};
+
+    use super::LokiLabelsMapper;
+
+    fn create_event(client_id: &str) -> MyLogEvent {
+        let mut log_event = create_test_event(LogLevel::Warning, "Orders", "Test");
+        log_event.context = Some([("Client-Id".to_string(), client_id.to_string())].into());
+        log_event
+    }
+
+    #[test]
+    fn test_context_label_cardinality_is_limited() {
+        let mapper = LokiLabelsMapper::new(vec!["Client-Id".to_string()], 2);
+        let populated_params = PopulatedParams::new(vec![("Application", "oms".into())]);
+
+        let labels = mapper.get_labels(&create_event("1"), &populated_params);
+        assert_eq!("oms", labels["app"]);
+        assert_eq!("warning", labels["level"]);
+        assert_eq!("Orders", labels["process"]);
+        assert_eq!("1", labels["Client_Id"]);
+
+        mapper.get_labels(&create_event("2"), &populated_params);
+        let labels = mapper.get_labels(&create_event("3"), &populated_params);
+        assert_eq!("other", labels["Client_Id"]);
+
+        let labels = mapper.get_labels(&create_event("1"), &populated_params);
+        assert_eq!("1", labels["Client_Id"]);
+    }
+
+    #[test]
+    fn test_format_label_set() {
+        let mut labels = super::LokiLabelSet::new();
+        labels.insert("app".to_string(), "a\"b".to_string());
+        labels.insert("level".to_string(), "info".to_string());
+
+        assert_eq!(
+            "{app=\"a\\\"b\", level=\"info\"}",
+            super::format_label_set(&labels)
+        );
+    }
+}
@@ -0,0 +1,122 @@
+use std::sync::Arc;
+
+use my_seq_logger::{LogEventsQueue, LogsChunkUploader};
+use parking_lot::Mutex;
+use rust_extensions::events_loop::EventsLoopTick;
+
+use crate::{
+    LokiEncoding, LokiLabelsMapper, LokiLoggerSettings, LokiSettings, LokiStreams, LokiUploader,
+};
+
+pub struct LokiLoggerInner {
+    pub(crate) log_events: LogEventsQueue,
+    settings: Arc<dyn LokiSettings + Send + Sync + 'static>,
+    /// Replaces the HTTP uploader built from settings.
+    pub(crate) custom_uploader: Option<Arc<dyn LogsChunkUploader + Send + Sync + 'static>>,
+    cached_uploader: Mutex<Option<Arc<LokiUploader>>>,
+    labels_mapper: Mutex<Option<Arc<LokiLabelsMapper>>>,
+    /// Held while a batch is uploaded, so a flush waits for the batch which is already in flight.
+    upload_lock: tokio::sync::Mutex<()>,
+}
+
+impl LokiLoggerInner {
+    pub fn new(settings: Arc<dyn LokiSettings + Send + Sync + 'static>) -> Self {
+        Self {
+            log_events: LogEventsQueue::new(),
+            settings,
+            custom_uploader: None,
+            cached_uploader: Mutex::new(None),
+            labels_mapper: Mutex::new(None),
+            upload_lock: tokio::sync::Mutex::new(()),
+        }
+    }
+
+    pub fn configure(&mut self, queue_size: usize) {
+        self.log_events.configure_size(queue_size);
+    }
+
+    async fn get_uploader(
+        &self,
+    ) -> (
+        Arc<dyn LogsChunkUploader + Send + Sync + 'static>,
+        LokiEncoding,
+        Arc<LokiLabelsMapper>,
+        usize,
+    ) {
+        let settings = LokiLoggerSettings::read(&self.settings).await;
+
+        // the mapper keeps the label values seen so far, so it lives as long as the logger
+        let labels_mapper = self
+            .labels_mapper
+            .lock()
+            .get_or_insert_with(|| {
+                Arc::new(LokiLabelsMapper::new(
+                    settings.context_labels.clone(),
+                    settings.max_label_values,
+                ))
+            })
+            .clone();
+
+        if let Some(custom_uploader) = self.custom_uploader.as_ref() {
+            return (
+                custom_uploader.clone(),
+                settings.encoding,
+                labels_mapper,
+                settings.max_batch_size,
+            );
+        }
+
+        let encoding = settings.encoding;
+        let uploader = LokiUploader::new(
+            settings.url,
+            settings.encoding,
+            settings.tenant_id,
+            settings.user,
+            settings.timeout,
+        );
+
+        let mut cached = self.cached_uploader.lock();
+        if let Some(existing) = cached.as_ref() {
+            if existing.matches(&uploader) {
+                return (
+                    existing.clone(),
+                    encoding,
+                    labels_mapper,
+                    settings.max_batch_size,
+                );
+            }
+        }
+
+        let uploader = Arc::new(uploader);
+        *cached = Some(uploader.clone());
+        (uploader, encoding, labels_mapper, settings.max_batch_size)
+    }
+
+    pub async fn upload_queued_events(&self) {
+        let _upload_lock = self.upload_lock.lock().await;
+
+        let events = match self.log_events.dequeue() {
+            Some(events) => events,
+            None => return,
+        };
+
+        let (uploader, encoding, labels_mapper, max_batch_size) = self.get_uploader().await;
+        let populated_params = my_logger_core::LOGGER.get_populated_params();
+
+        for batch in events.chunks(max_batch_size.max(1)) {
+            let streams = LokiStreams::new(batch, &populated_params, labels_mapper.as_ref());
+            uploader.upload_chunk(streams.serialize(encoding)).await;
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl EventsLoopTick<()> for LokiLoggerInner {
+    async fn started(&self) {
+        println!("Loki Logs writer is started");
+    }
+    async fn tick(&self, _: ()) {
+        self.upload_queued_events().await;
+    }
+    async fn finished(&self) {}
+}
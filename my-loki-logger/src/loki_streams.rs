@@ -0,0 +1,192 @@
+use std::{collections::BTreeMap, fmt::Write, sync::Arc};
+
+use my_json::json_writer::{JsonObjectWriter, RawJsonObject};
+use my_logger_core::{MyLogEvent, PopulatedParams};
+use my_seq_logger::{format_seq_string, format_value, ProtoWriter};
+
+use crate::{LokiEncoding, LokiLabelSet, LokiLabelsMapper};
+
+const LABEL_PARAMS: [&str; 2] = ["Application", "EnvInfo"];
+
+/// Events grouped into streams by their label set, in the order they were written.
+pub struct LokiStreams {
+    streams: BTreeMap<LokiLabelSet, Vec<(i64, String)>>,
+}
+
+impl LokiStreams {
+    pub fn new(
+        log_events: &[Arc<MyLogEvent>],
+        populated_params: &PopulatedParams,
+        labels_mapper: &LokiLabelsMapper,
+    ) -> Self {
+        let mut streams: BTreeMap<LokiLabelSet, Vec<(i64, String)>> = BTreeMap::new();
+
+        for log_event in log_events {
+            let labels = labels_mapper.get_labels(log_event, populated_params);
+            let line = serialize_line(log_event, populated_params);
+            let nanos = log_event.dt.unix_microseconds * 1000;
+
+            streams.entry(labels).or_default().push((nanos, line));
+        }
+
+        Self { streams }
+    }
+
+    pub fn len(&self) -> usize {
+        self.streams.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.streams.is_empty()
+    }
+
+    pub fn serialize(&self, encoding: LokiEncoding) -> Vec<u8> {
+        match encoding {
+            LokiEncoding::Json => self.serialize_json(),
+            LokiEncoding::Protobuf => self.serialize_protobuf(),
+        }
+    }
+
+    /// `{"streams":[{"stream":{labels},"values":[["<unix nanos>","<line>"]]}]}`
+    fn serialize_json(&self) -> Vec<u8> {
+        let mut streams = Vec::with_capacity(self.streams.len());
+
+        for (labels, entries) in &self.streams {
+            let mut stream = JsonObjectWriter::from_string(String::new());
+            for (name, value) in labels {
+                stream = stream.write(name, format_seq_string(value.as_str()).as_str());
+            }
+            let stream = stream.build();
+
+            let mut values = String::from("[");
+            for (index, (nanos, line)) in entries.iter().enumerate() {
+                if index > 0 {
+                    values.push(',');
+                }
+                values.push_str("[\"");
+                values.push_str(nanos.to_string().as_str());
+                values.push_str("\",");
+                write_json_string(&mut values, line.as_str());
+                values.push(']');
+            }
+            values.push(']');
+
+            streams.push(
+                JsonObjectWriter::from_string(String::new())
+                    .write("stream", RawJsonObject::AsStr(stream.as_str()))
+                    .write("values", RawJsonObject::AsStr(values.as_str()))
+                    .build(),
+            );
+        }
+
+        let streams = format!("[{}]", streams.join(","));
+        JsonObjectWriter::from_string(String::new())
+            .write("streams", RawJsonObject::AsStr(streams.as_str()))
+            .build()
+            .into_bytes()
+    }
+
+    /// Snappy-compressed `logproto.PushRequest`.
+    fn serialize_protobuf(&self) -> Vec<u8> {
+        let mut writer = ProtoWriter::new();
+
+        for (labels, entries) in &self.streams {
+            writer.write_message_field(1, |stream| {
+                stream.write_string_field(1, crate::format_label_set(labels).as_str());
+
+                for (nanos, line) in entries {
+                    stream.write_message_field(2, |entry| {
+                        entry.write_message_field(1, |timestamp| {
+                            timestamp.write_varint_field(1, nanos.div_euclid(1_000_000_000) as u64);
+                            timestamp.write_varint_field(2, nanos.rem_euclid(1_000_000_000) as u64);
+                        });
+                        entry.write_string_field(2, line.as_str());
+                    });
+                }
+            });
+        }
+
+        let payload = writer.build();
+        match snap::raw::Encoder::new().compress_vec(payload.as_slice()) {
+            Ok(result) => result,
+            Err(err) => {
+                eprintln!("Can not compress Loki payload. Err: {:?}", err);
+                Vec::new()
+            }
+        }
+    }
+}
+
+fn write_json_string(out: &mut String, value: &str) {
+    out.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 32 => {
+                let _ = write!(out, "\\u{:04x}", c as u32);
+            }
+            _ => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+/// The log line is a JSON object with the message, populated params which are not labels and the whole context,
+/// so it can be parsed with the `| json` LogQL stage.
+fn serialize_line(log_event: &MyLogEvent, populated_params: &PopulatedParams) -> String {
+    let mut json_writer = JsonObjectWriter::from_string(String::new()).write(
+        "message",
+        format_seq_string(log_event.message.as_str()).as_str(),
+    );
+
+    for (key, value) in populated_params.iter() {
+        if LABEL_PARAMS.contains(&key) {
+            continue;
+        }
+        json_writer = json_writer.write(key, format_seq_string(value).as_str());
+    }
+
+    if let Some(ctx) = &log_event.context {
+        for (key, value) in ctx {
+            json_writer = json_writer.write(key, format_value(value).as_str());
+        }
+    }
+
+    json_writer.build()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use my_logger_core::{test_utils::create_test_event, LogLevel, PopulatedParams};
+
+    use crate::LokiLabelsMapper;
+
+    use super::LokiStreams;
+
+    #[test]
+    fn test_events_are_grouped_by_labels() {
+        let create_event =
+            |level: LogLevel, process: &str| Arc::new(create_test_event(level, process, "Test"));
+
+        let events = vec![
+            create_event(LogLevel::Info, "A"),
+            create_event(LogLevel::Info, "B"),
+            create_event(LogLevel::Info, "A"),
+            create_event(LogLevel::Error, "A"),
+        ];
+
+        let streams = LokiStreams::new(
+            &events,
+            &PopulatedParams::new_empty(),
+            &LokiLabelsMapper::new(vec![], 10),
+        );
+
+        assert_eq!(3, streams.len());
+    }
+}
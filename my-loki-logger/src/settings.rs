@@ -0,0 +1,141 @@
+use std::{sync::Arc, time::Duration};
+
+use my_seq_logger::{parse_number, split_key_value};
+
+#[async_trait::async_trait]
+pub trait LokiSettings {
+    async fn get_conn_string(&self) -> String;
+}
+
+const DEFAULT_FLUSH_SLEEP: u64 = 1;
+const DEFAULT_TIMEOUT: u64 = 10;
+const DEFAULT_MAX_LABEL_VALUES: usize = 50;
+const DEFAULT_BATCH_SIZE: usize = 1000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LokiEncoding {
+    Json,
+    Protobuf,
+}
+
+pub struct LokiLoggerSettings {
+    pub url: String,
+    pub encoding: LokiEncoding,
+    /// Context keys which are promoted to labels.
+    pub context_labels: Vec<String>,
+    /// Distinct values a label may take before its values are collapsed into `other`.
+    pub max_label_values: usize,
+    pub tenant_id: Option<String>,
+    pub user: Option<(String, String)>,
+    pub max_batch_size: usize,
+    /// Interval at which queued events are uploaded.
+    pub flush_delay: Duration,
+    pub queue_size: Option<usize>,
+    pub timeout: Duration,
+}
+
+impl LokiLoggerSettings {
+    pub async fn read(settings: &Arc<dyn LokiSettings + Send + Sync + 'static>) -> Self {
+        loop {
+            let conn_string = settings.get_conn_string().await;
+            let settings = LokiLoggerSettings::try_parse(conn_string.as_str());
+
+            match settings {
+                Ok(result) => return result,
+                Err(err) => {
+                    eprintln!("Can not parse Loki Logs settings. Err: {:?}", err);
+                    tokio::time::sleep(Duration::from_secs(5)).await;
+                }
+            };
+        }
+    }
+
+    /// `url=http://loki:3100;encoding=protobuf;labels=ClientId,Instrument;maxlabelvalues=50;tenant=team-a`
+    pub fn try_parse(conn_string: &str) -> Result<Self, String> {
+        let mut url = None;
+        let mut encoding = LokiEncoding::Json;
+        let mut context_labels = Vec::new();
+        let mut max_label_values = DEFAULT_MAX_LABEL_VALUES;
+        let mut tenant_id = None;
+        let mut user = None;
+        let mut password = None;
+        let mut max_batch_size = DEFAULT_BATCH_SIZE;
+        let mut flush_delay = DEFAULT_FLUSH_SLEEP;
+        let mut queue_size = None;
+        let mut timeout = DEFAULT_TIMEOUT;
+
+        for item in conn_string.split(';') {
+            if item.is_empty() {
+                continue;
+            }
+
+            let (key, value) = split_key_value(item, "Loki connection string")?;
+
+            match key {
+                "url" => {
+                    url = Some(value);
+                }
+                "encoding" => {
+                    encoding = match value {
+                        "json" => LokiEncoding::Json,
+                        "protobuf" => LokiEncoding::Protobuf,
+                        _ => return Err(format!("Invalid Loki encoding {}", value)),
+                    };
+                }
+                "labels" => {
+                    context_labels = value
+                        .split(',')
+                        .map(|itm| itm.trim())
+                        .filter(|itm| !itm.is_empty())
+                        .map(|itm| itm.to_string())
+                        .collect();
+                }
+                "maxlabelvalues" => {
+                    max_label_values = parse_number(key, value)?;
+                }
+                "tenant" => {
+                    tenant_id = Some(value.to_string());
+                }
+                "user" => {
+                    user = Some(value.to_string());
+                }
+                "password" => {
+                    password = Some(value.to_string());
+                }
+                "batchsize" => {
+                    max_batch_size = parse_number(key, value)?;
+                }
+                "flushdelay" => {
+                    flush_delay = parse_number(key, value)?;
+                }
+                "queuesize" => {
+                    queue_size = Some(parse_number(key, value)?);
+                }
+                "timeout" => {
+                    timeout = parse_number(key, value)?;
+                }
+                _ => {
+                    return Err(format!("Invalid key {} of Loki connection string", key));
+                }
+            }
+        }
+
+        let url = match url {
+            Some(url) => url,
+            None => return Err("There is no URL parameter in Loki connection string".to_string()),
+        };
+
+        Ok(Self {
+            url: url.to_string(),
+            encoding,
+            context_labels,
+            max_label_values,
+            tenant_id,
+            user: user.map(|user| (user, password.unwrap_or_default())),
+            max_batch_size,
+            flush_delay: Duration::from_secs(flush_delay),
+            queue_size,
+            timeout: Duration::from_secs(timeout),
+        })
+    }
+}
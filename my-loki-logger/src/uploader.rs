@@ -0,0 +1,142 @@
+use std::time::Duration;
+
+use base64::Engine;
+use flurl::{body::FlUrlBody, *};
+use my_seq_logger::LogsChunkUploader;
+
+use crate::LokiEncoding;
+
+pub struct LokiUploader {
+    pub url: String,
+    pub encoding: LokiEncoding,
+    pub tenant_id: Option<String>,
+    pub authorization: Option<String>,
+    pub loki_debug: bool,
+    pub timeout: Duration,
+}
+
+impl LokiUploader {
+    pub fn new(
+        url: String,
+        encoding: LokiEncoding,
+        tenant_id: Option<String>,
+        user: Option<(String, String)>,
+        timeout: Duration,
+    ) -> Self {
+        Self {
+            url,
+            encoding,
+            tenant_id,
+            authorization: user.map(|(user, password)| {
+                format!(
+                    "Basic {}",
+                    base64::engine::general_purpose::STANDARD
+                        .encode(format!("{}:{}", user, password))
+                )
+            }),
+            loki_debug: std::env::var("LOKI_DEBUG").is_ok(),
+            timeout,
+        }
+    }
+
+    pub fn matches(&self, other: &LokiUploader) -> bool {
+        self.url == other.url
+            && self.encoding == other.encoding
+            && self.tenant_id == other.tenant_id
+            && self.authorization == other.authorization
+            && self.timeout == other.timeout
+    }
+}
+
+#[async_trait::async_trait]
+impl LogsChunkUploader for LokiUploader {
+    async fn upload_chunk(&self, chunk_to_upload: Vec<u8>) {
+        const MAX_ATTEMPTS: u32 = 4;
+
+        if self.loki_debug {
+            println!("Sending Loki push len={}", chunk_to_upload.len());
+        }
+
+        let mut attempt_no: u32 = 0;
+        let mut chunk = Some(chunk_to_upload);
+        loop {
+            attempt_no += 1;
+
+            let data = if attempt_no == MAX_ATTEMPTS {
+                chunk.take().unwrap()
+            } else {
+                chunk.as_ref().unwrap().clone()
+            };
+
+            let mut fl_url = FlUrl::new(self.url.as_str())
+                .set_timeout(self.timeout)
+                .append_path_segment("loki")
+                .append_path_segment("api")
+                .append_path_segment("v1")
+                .append_path_segment("push")
+                .with_retries(3);
+
+            fl_url = match self.encoding {
+                LokiEncoding::Json => fl_url.with_header("Content-Type", "application/json"),
+                LokiEncoding::Protobuf => fl_url
+                    .with_header("Content-Type", "application/x-protobuf")
+                    .with_header("Content-Encoding", "snappy"),
+            };
+
+            if let Some(tenant_id) = self.tenant_id.as_ref() {
+                fl_url = fl_url.with_header("X-Scope-OrgID", tenant_id.as_str());
+            }
+
+            if let Some(authorization) = self.authorization.as_ref() {
+                fl_url = fl_url.with_header("Authorization", authorization.as_str());
+            }
+
+            let response = fl_url
+                .post(FlUrlBody::Raw {
+                    data,
+                    content_type: None,
+                })
+                .await;
+
+            match response {
+                Ok(mut response) => {
+                    let status_code = response.get_status_code();
+                    if self.loki_debug {
+                        println!("Loki result: {}", status_code);
+                    }
+
+                    if (200..300).contains(&status_code) {
+                        return;
+                    }
+
+                    // 4xx other than throttling means the push is rejected and a retry won't help
+                    if status_code != 429 && (400..500).contains(&status_code) {
+                        let body = response.get_body_as_slice().await;
+                        eprintln!(
+                            "Loki rejected logs. Status code: {}. Body: {:?}",
+                            status_code,
+                            body.map(|itm| String::from_utf8_lossy(itm).to_string())
+                        );
+                        return;
+                    }
+
+                    if attempt_no >= MAX_ATTEMPTS {
+                        return;
+                    }
+                }
+                Err(err) => {
+                    eprintln!(
+                        "Attempt: {} Error while uploading logs to Loki. Err: {:?}",
+                        attempt_no, err
+                    );
+
+                    if attempt_no >= MAX_ATTEMPTS {
+                        return;
+                    }
+                }
+            }
+
+            tokio::time::sleep(Duration::from_secs(1)).await;
+        }
+    }
+}
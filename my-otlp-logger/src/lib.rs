@@ -0,0 +1,11 @@
+mod otlp_logger;
+pub use otlp_logger::OtlpLogger;
+mod otlp_logger_inner;
+pub use otlp_logger_inner::*;
+mod settings;
+pub use settings::*;
+mod serializer;
+pub use serializer::*;
+mod uploader;
+pub use uploader::*;
+mod upload_logs_chunk;
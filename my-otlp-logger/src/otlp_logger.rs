@@ -0,0 +1,117 @@
+use std::{sync::Arc, time::Duration};
+
+use crate::{OtlpLoggerInner, OtlpLoggerSettings, OtlpSettings};
+use my_logger_core::{MyLogEvent, MyLoggerReader};
+use rust_extensions::{events_loop::EventsLoop, AppStates};
+
+pub struct OtlpLogger {
+    inner: Arc<OtlpLoggerInner>,
+    events_loop: EventsLoop<()>,
+    app_states: Arc<AppStates>,
+}
+
+impl OtlpLogger {
+    pub async fn enable_from_connection_string(
+        settings: Arc<dyn OtlpSettings + Send + Sync + 'static>,
+    ) {
+        let mut inner = OtlpLoggerInner::new(settings.clone());
+        let settings = OtlpLoggerSettings::read(&settings).await;
+
+        if let Some(queue_size) = settings.queue_size {
+            inner.configure(queue_size)
+        }
+
+        let result = Self {
+            app_states: AppStates::create_initialized().into(),
+            inner: Arc::new(inner),
+            events_loop: EventsLoop::new("OtlpLogger".to_string())
+                .set_iteration_timeout(settings.timeout),
+        };
+
+        result.events_loop.register_event_loop(result.inner.clone());
+
+        result
+            .events_loop
+            .start(result.app_states.clone(), my_logger_core::LOGGER.clone());
+
+        let otlp_logger = Arc::new(result);
+        my_seq_logger::start_flush_timer(
+            Arc::downgrade(&otlp_logger),
+            settings.flush_delay,
+            |logger| logger.events_loop.send(()),
+        );
+        my_logger_core::LOGGER.plug_reader(otlp_logger.clone());
+    }
+}
+
+impl MyLoggerReader for OtlpLogger {
+    fn write_log(&self, log_event: Arc<MyLogEvent>) {
+        self.inner.log_events.enqueue(log_event);
+    }
+
+    fn flush(&self, timeout: Duration) {
+        let inner = self.inner.clone();
+        my_seq_logger::flush_on_private_runtime("OTLP", timeout, async move {
+            inner.upload_queued_events().await
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{sync::Arc, time::Duration};
+
+    use my_logger_core::{test_utils::create_test_event, LogLevel, MyLoggerReader};
+    use my_seq_logger::LogsChunkUploader;
+    use parking_lot::Mutex;
+    use rust_extensions::{events_loop::EventsLoop, AppStates};
+
+    use crate::{OtlpLoggerInner, OtlpSettings};
+
+    use super::OtlpLogger;
+
+    struct TestSettings;
+
+    #[async_trait::async_trait]
+    impl OtlpSettings for TestSettings {
+        async fn get_conn_string(&self) -> String {
+            "url=http://localhost:4318".to_string()
+        }
+    }
+
+    #[derive(Default)]
+    struct MockUploader {
+        chunks: Mutex<Vec<Vec<u8>>>,
+    }
+
+    #[async_trait::async_trait]
+    impl LogsChunkUploader for MockUploader {
+        async fn upload_chunk(&self, chunk: Vec<u8>) {
+            self.chunks.lock().push(chunk);
+        }
+    }
+
+    #[test]
+    fn test_flush_uploads_queued_events() {
+        let uploader = Arc::new(MockUploader::default());
+        let mut inner = OtlpLoggerInner::new(Arc::new(TestSettings));
+        inner.custom_uploader = Some(uploader.clone());
+
+        let otlp_logger = OtlpLogger {
+            inner: Arc::new(inner),
+            events_loop: EventsLoop::new("OtlpLogger".to_string()),
+            app_states: AppStates::create_initialized().into(),
+        };
+
+        otlp_logger.write_log(Arc::new(create_test_event(
+            LogLevel::Info,
+            "Orders",
+            "Created",
+        )));
+        otlp_logger.flush(Duration::from_secs(5));
+
+        let chunks = uploader.chunks.lock();
+        assert_eq!(1, chunks.len());
+        assert!(chunks[0].windows(7).any(|itm| itm == b"Created"));
+    }
+}
@@ -0,0 +1,104 @@
+use std::sync::Arc;
+
+use my_seq_logger::{LogEventsQueue, LogsChunkUploader};
+use parking_lot::Mutex;
+use rust_extensions::events_loop::EventsLoopTick;
+
+use crate::{OtlpEncoding, OtlpLoggerSettings, OtlpSettings, OtlpUploader};
+
+pub struct OtlpLoggerInner {
+    pub(crate) log_events: LogEventsQueue,
+    settings: Arc<dyn OtlpSettings + Send + Sync + 'static>,
+    /// Replaces the HTTP uploader built from settings.
+    pub(crate) custom_uploader: Option<Arc<dyn LogsChunkUploader + Send + Sync + 'static>>,
+    cached_uploader: Mutex<Option<Arc<OtlpUploader>>>,
+    /// Held while a batch is uploaded, so a flush waits for the batch which is already in flight.
+    upload_lock: tokio::sync::Mutex<()>,
+}
+
+impl OtlpLoggerInner {
+    pub fn new(settings: Arc<dyn OtlpSettings + Send + Sync + 'static>) -> Self {
+        Self {
+            log_events: LogEventsQueue::new(),
+            settings,
+            custom_uploader: None,
+            cached_uploader: Mutex::new(None),
+            upload_lock: tokio::sync::Mutex::new(()),
+        }
+    }
+
+    pub fn configure(&mut self, queue_size: usize) {
+        self.log_events.configure_size(queue_size);
+    }
+
+    async fn get_uploader(
+        &self,
+    ) -> (
+        Arc<dyn LogsChunkUploader + Send + Sync + 'static>,
+        OtlpEncoding,
+        usize,
+    ) {
+        let settings = OtlpLoggerSettings::read(&self.settings).await;
+
+        if let Some(custom_uploader) = self.custom_uploader.as_ref() {
+            return (
+                custom_uploader.clone(),
+                settings.encoding,
+                settings.max_batch_size,
+            );
+        }
+
+        let mut cached = self.cached_uploader.lock();
+        if let Some(existing) = cached.as_ref() {
+            if existing.matches(
+                &settings.url,
+                settings.encoding,
+                &settings.headers,
+                settings.timeout,
+            ) {
+                return (existing.clone(), settings.encoding, settings.max_batch_size);
+            }
+        }
+
+        let uploader = Arc::new(OtlpUploader::new(
+            settings.url,
+            settings.encoding,
+            settings.headers,
+            settings.timeout,
+        ));
+        *cached = Some(uploader.clone());
+        (uploader, settings.encoding, settings.max_batch_size)
+    }
+
+    pub async fn upload_queued_events(&self) {
+        let _upload_lock = self.upload_lock.lock().await;
+
+        let events = match self.log_events.dequeue() {
+            Some(events) => events,
+            None => return,
+        };
+
+        let (uploader, encoding, max_batch_size) = self.get_uploader().await;
+        let populated_params = my_logger_core::LOGGER.get_populated_params();
+
+        crate::upload_logs_chunk::upload_log_events_chunk(
+            uploader.as_ref(),
+            encoding,
+            max_batch_size,
+            populated_params,
+            events,
+        )
+        .await;
+    }
+}
+
+#[async_trait::async_trait]
+impl EventsLoopTick<()> for OtlpLoggerInner {
+    async fn started(&self) {
+        println!("OTLP Logs writer is started");
+    }
+    async fn tick(&self, _: ()) {
+        self.upload_queued_events().await;
+    }
+    async fn finished(&self) {}
+}
@@ -0,0 +1,635 @@
+use std::sync::Arc;
+
+use my_json::json_writer::{JsonObjectWriter, RawJsonObject};
+use my_logger_core::{LogLevel, MyLogEvent, PopulatedParams};
+use my_seq_logger::ProtoWriter;
+
+use crate::OtlpEncoding;
+
+const SCOPE_NAME: &str = "my-logger";
+const PROCESS_ATTRIBUTE: &str = "process";
+
+pub fn get_severity_number(level: LogLevel) -> u8 {
+    match level {
+        LogLevel::Trace => 1,
+        LogLevel::Debug => 5,
+        LogLevel::Info => 9,
+        LogLevel::Warning => 13,
+        LogLevel::Error => 17,
+        LogLevel::FatalError => 21,
+    }
+}
+
+/// Maps well known populated params to OpenTelemetry semantic convention names.
+pub fn get_resource_attribute_name(key: &str) -> &str {
+    match key {
+        "Application" => "service.name",
+        "Version" => "service.version",
+        "EnvInfo" => "deployment.environment",
+        _ => key,
+    }
+}
+
+pub enum AttributeValue<'s> {
+    String(&'s str),
+    Bool(bool),
+    Int(i64),
+    Double(f64),
+}
+
+impl<'s> AttributeValue<'s> {
+    /// Numbers are coerced only when written canonically, so values like `007` or
+    /// ids longer than `i64` stay strings instead of losing digits.
+    pub fn parse(src: &'s str) -> Self {
+        if let Ok(value) = src.parse::<i64>() {
+            if value.to_string() == src {
+                return Self::Int(value);
+            }
+        }
+
+        if let Ok(value) = src.parse::<f64>() {
+            if value.is_finite() && value.to_string() == src {
+                return Self::Double(value);
+            }
+        }
+
+        match src {
+            "true" => Self::Bool(true),
+            "false" => Self::Bool(false),
+            _ => Self::String(src),
+        }
+    }
+
+    fn write_proto(&self, writer: &mut ProtoWriter) {
+        match self {
+            AttributeValue::String(value) => writer.write_string_field(1, value),
+            AttributeValue::Bool(value) => writer.write_varint_field(2, *value as u64),
+            AttributeValue::Int(value) => writer.write_varint_field(3, *value as u64),
+            AttributeValue::Double(value) => writer.write_fixed64_field(4, value.to_bits()),
+        }
+    }
+
+    fn to_json(&self) -> String {
+        let json_writer = JsonObjectWriter::from_string(String::new());
+        let json_writer = match self {
+            AttributeValue::String(value) => json_writer.write(
+                "stringValue",
+                my_seq_logger::format_seq_string(*value).as_str(),
+            ),
+            AttributeValue::Bool(value) => {
+                json_writer.write("boolValue", RawJsonObject::AsStr(bool_as_str(*value)))
+            }
+            AttributeValue::Int(value) => {
+                json_writer.write("intValue", value.to_string().as_str())
+            }
+            AttributeValue::Double(value) => {
+                let value = value.to_string();
+                json_writer.write("doubleValue", RawJsonObject::AsStr(value.as_str()))
+            }
+        };
+
+        json_writer.build()
+    }
+}
+
+fn bool_as_str(value: bool) -> &'static str {
+    if value {
+        "true"
+    } else {
+        "false"
+    }
+}
+
+fn get_unix_nanos(log_event: &MyLogEvent) -> u64 {
+    log_event.dt.unix_microseconds as u64 * 1000
+}
+
+/// Protobuf carries trace and span ids as raw bytes, JSON as the same hex strings.
+fn decode_hex_id(src: &str) -> Option<Vec<u8>> {
+    if !src.len().is_multiple_of(2) {
+        return None;
+    }
+
+    (0..src.len())
+        .step_by(2)
+        .map(|index| u8::from_str_radix(src.get(index..index + 2)?, 16).ok())
+        .collect()
+}
+
+pub fn serialize_logs_request(
+    encoding: OtlpEncoding,
+    compile_buffer: Vec<u8>,
+    log_events: &[Arc<MyLogEvent>],
+    populated_params: &PopulatedParams,
+) -> Vec<u8> {
+    match encoding {
+        OtlpEncoding::Protobuf => serialize_protobuf(compile_buffer, log_events, populated_params),
+        OtlpEncoding::Json => serialize_json(compile_buffer, log_events, populated_params),
+    }
+}
+
+fn write_proto_attribute(writer: &mut ProtoWriter, field_no: u32, key: &str, value: &str) {
+    writer.write_message_field(field_no, |key_value| {
+        key_value.write_string_field(1, key);
+        key_value.write_message_field(2, |any_value| {
+            AttributeValue::parse(value).write_proto(any_value)
+        });
+    });
+}
+
+/// ExportLogsServiceRequest { ResourceLogs { Resource, ScopeLogs { InstrumentationScope, LogRecord* } } }
+fn serialize_protobuf(
+    compile_buffer: Vec<u8>,
+    log_events: &[Arc<MyLogEvent>],
+    populated_params: &PopulatedParams,
+) -> Vec<u8> {
+    let mut writer = ProtoWriter::from_vec(compile_buffer);
+
+    writer.write_message_field(1, |resource_logs| {
+        resource_logs.write_message_field(1, |resource| {
+            for (key, value) in populated_params.iter() {
+                resource.write_message_field(1, |key_value| {
+                    key_value.write_string_field(1, get_resource_attribute_name(key));
+                    key_value.write_message_field(2, |any_value| {
+                        any_value.write_string_field(1, value)
+                    });
+                });
+            }
+        });
+
+        resource_logs.write_message_field(2, |scope_logs| {
+            scope_logs.write_message_field(1, |scope| {
+                scope.write_string_field(1, SCOPE_NAME);
+            });
+
+            for log_event in log_events {
+                scope_logs.write_message_field(2, |log_record| {
+                    let nanos = get_unix_nanos(log_event);
+                    log_record.write_fixed64_field(1, nanos);
+                    log_record.write_varint_field(2, get_severity_number(log_event.level) as u64);
+                    log_record.write_string_field(3, log_event.level.as_str());
+                    log_record.write_message_field(5, |body| {
+                        body.write_string_field(1, log_event.message.as_str());
+                    });
+
+                    write_proto_attribute(
+                        log_record,
+                        6,
+                        PROCESS_ATTRIBUTE,
+                        log_event.process.as_str(),
+                    );
+
+                    if let Some(ctx) = &log_event.context {
+                        for (key, value) in ctx {
+                            write_proto_attribute(log_record, 6, key, value);
+                        }
+                    }
+
+                    if let Some(trace_context) = &log_event.trace_context {
+                        if let Some(trace_id) = decode_hex_id(&trace_context.trace_id) {
+                            log_record.write_bytes_field(9, &trace_id);
+                        }
+
+                        if let Some(span_id) = decode_hex_id(&trace_context.span_id) {
+                            log_record.write_bytes_field(10, &span_id);
+                        }
+                    }
+
+                    log_record.write_fixed64_field(11, nanos);
+                });
+            }
+        });
+    });
+
+    writer.build()
+}
+
+fn build_json_array(items: Vec<String>) -> String {
+    let mut result = String::from("[");
+    for (index, item) in items.iter().enumerate() {
+        if index > 0 {
+            result.push(',');
+        }
+        result.push_str(item);
+    }
+    result.push(']');
+    result
+}
+
+fn build_json_attribute(key: &str, value: AttributeValue) -> String {
+    let value = value.to_json();
+    JsonObjectWriter::from_string(String::new())
+        .write("key", key)
+        .write("value", RawJsonObject::AsStr(value.as_str()))
+        .build()
+}
+
+fn serialize_json(
+    compile_buffer: Vec<u8>,
+    log_events: &[Arc<MyLogEvent>],
+    populated_params: &PopulatedParams,
+) -> Vec<u8> {
+    let resource_attributes = build_json_array(
+        populated_params
+            .iter()
+            .map(|(key, value)| {
+                build_json_attribute(get_resource_attribute_name(key), AttributeValue::String(value))
+            })
+            .collect(),
+    );
+
+    let resource = JsonObjectWriter::from_string(String::new())
+        .write("attributes", RawJsonObject::AsStr(resource_attributes.as_str()))
+        .build();
+
+    let log_records = build_json_array(
+        log_events
+            .iter()
+            .map(|log_event| {
+                let mut attributes = vec![build_json_attribute(
+                    PROCESS_ATTRIBUTE,
+                    AttributeValue::String(log_event.process.as_str()),
+                )];
+
+                if let Some(ctx) = &log_event.context {
+                    for (key, value) in ctx {
+                        attributes.push(build_json_attribute(key, AttributeValue::parse(value)));
+                    }
+                }
+
+                let attributes = build_json_array(attributes);
+                let body = AttributeValue::String(log_event.message.as_str()).to_json();
+                let nanos = get_unix_nanos(log_event).to_string();
+                let severity_number = get_severity_number(log_event.level).to_string();
+
+                let mut log_record = JsonObjectWriter::from_string(String::new())
+                    .write("timeUnixNano", nanos.as_str())
+                    .write("observedTimeUnixNano", nanos.as_str())
+                    .write(
+                        "severityNumber",
+                        RawJsonObject::AsStr(severity_number.as_str()),
+                    )
+                    .write("severityText", log_event.level.as_str())
+                    .write("body", RawJsonObject::AsStr(body.as_str()))
+                    .write("attributes", RawJsonObject::AsStr(attributes.as_str()));
+
+                if let Some(trace_context) = &log_event.trace_context {
+                    if decode_hex_id(&trace_context.trace_id).is_some() {
+                        log_record = log_record.write("traceId", trace_context.trace_id.as_str());
+                    }
+
+                    if decode_hex_id(&trace_context.span_id).is_some() {
+                        log_record = log_record.write("spanId", trace_context.span_id.as_str());
+                    }
+                }
+
+                log_record.build()
+            })
+            .collect(),
+    );
+
+    let scope = JsonObjectWriter::from_string(String::new())
+        .write("name", SCOPE_NAME)
+        .build();
+
+    let scope_logs = JsonObjectWriter::from_string(String::new())
+        .write("scope", RawJsonObject::AsStr(scope.as_str()))
+        .write("logRecords", RawJsonObject::AsStr(log_records.as_str()))
+        .build();
+    let scope_logs = build_json_array(vec![scope_logs]);
+
+    let resource_logs = JsonObjectWriter::from_string(String::new())
+        .write("resource", RawJsonObject::AsStr(resource.as_str()))
+        .write("scopeLogs", RawJsonObject::AsStr(scope_logs.as_str()))
+        .build();
+    let resource_logs = build_json_array(vec![resource_logs]);
+
+    let mut result = compile_buffer;
+    result.clear();
+    let request = JsonObjectWriter::from_string(String::new())
+        .write("resourceLogs", RawJsonObject::AsStr(resource_logs.as_str()))
+        .build();
+    result.extend_from_slice(request.as_bytes());
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashMap, sync::Arc};
+
+    use my_logger_core::{
+        test_utils::create_test_event, LogLevel, MyLogEvent, PopulatedParams, TraceContext,
+    };
+
+    use crate::OtlpEncoding;
+
+    use super::{serialize_logs_request, AttributeValue};
+
+    const TRACEPARENT: &str = "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01";
+
+    const LEVELS: [(LogLevel, u64); 6] = [
+        (LogLevel::Trace, 1),
+        (LogLevel::Debug, 5),
+        (LogLevel::Info, 9),
+        (LogLevel::Warning, 13),
+        (LogLevel::Error, 17),
+        (LogLevel::FatalError, 21),
+    ];
+
+    enum ProtoValue<'s> {
+        Varint(u64),
+        Fixed64(u64),
+        Bytes(&'s [u8]),
+    }
+
+    fn read_varint(src: &[u8], pos: &mut usize) -> u64 {
+        let mut result = 0;
+        let mut shift = 0;
+        loop {
+            let b = src[*pos];
+            *pos += 1;
+            result |= ((b & 0x7F) as u64) << shift;
+            if b < 0x80 {
+                return result;
+            }
+            shift += 7;
+        }
+    }
+
+    fn decode_proto(src: &[u8]) -> Vec<(u64, ProtoValue<'_>)> {
+        let mut result = Vec::new();
+        let mut pos = 0;
+
+        while pos < src.len() {
+            let tag = read_varint(src, &mut pos);
+            let value = match tag & 7 {
+                0 => ProtoValue::Varint(read_varint(src, &mut pos)),
+                1 => {
+                    let bytes: [u8; 8] = src[pos..pos + 8].try_into().unwrap();
+                    pos += 8;
+                    ProtoValue::Fixed64(u64::from_le_bytes(bytes))
+                }
+                2 => {
+                    let len = read_varint(src, &mut pos) as usize;
+                    pos += len;
+                    ProtoValue::Bytes(&src[pos - len..pos])
+                }
+                wire_type => panic!("Unexpected wire type {}", wire_type),
+            };
+            result.push((tag >> 3, value));
+        }
+
+        result
+    }
+
+    fn get_bytes<'s>(fields: &[(u64, ProtoValue<'s>)], field_no: u64) -> Vec<&'s [u8]> {
+        fields
+            .iter()
+            .filter_map(|(no, value)| match value {
+                ProtoValue::Bytes(bytes) if *no == field_no => Some(*bytes),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn get_string(fields: &[(u64, ProtoValue)], field_no: u64) -> String {
+        String::from_utf8(get_bytes(fields, field_no)[0].to_vec()).unwrap()
+    }
+
+    fn get_varint(fields: &[(u64, ProtoValue)], field_no: u64) -> u64 {
+        fields
+            .iter()
+            .find_map(|(no, value)| match value {
+                ProtoValue::Varint(value) if *no == field_no => Some(*value),
+                _ => None,
+            })
+            .unwrap()
+    }
+
+    fn to_hex(bytes: &[u8]) -> String {
+        bytes.iter().map(|b| format!("{:02x}", b)).collect()
+    }
+
+    /// KeyValue messages as `key -> type:value`.
+    fn decode_proto_attributes(fields: &[(u64, ProtoValue)]) -> HashMap<String, String> {
+        get_bytes(fields, 1)
+            .into_iter()
+            .chain(get_bytes(fields, 6))
+            .map(|key_value| {
+                let key_value = decode_proto(key_value);
+                let any_value = decode_proto(get_bytes(&key_value, 2)[0]);
+                let value = match &any_value[0] {
+                    (1, ProtoValue::Bytes(value)) => {
+                        format!("string:{}", std::str::from_utf8(value).unwrap())
+                    }
+                    (2, ProtoValue::Varint(value)) => format!("bool:{}", *value == 1),
+                    (3, ProtoValue::Varint(value)) => format!("int:{}", *value as i64),
+                    (4, ProtoValue::Fixed64(value)) => format!("double:{}", f64::from_bits(*value)),
+                    _ => panic!("Unexpected AnyValue"),
+                };
+                (get_string(&key_value, 1), value)
+            })
+            .collect()
+    }
+
+    fn decode_json_attributes(attributes: &serde_json::Value) -> HashMap<String, String> {
+        attributes
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|key_value| {
+                let (value_type, value) = key_value["value"]
+                    .as_object()
+                    .unwrap()
+                    .iter()
+                    .next()
+                    .unwrap();
+                let value = match value_type.as_str() {
+                    "stringValue" => format!("string:{}", value.as_str().unwrap()),
+                    "boolValue" => format!("bool:{}", value.as_bool().unwrap()),
+                    "intValue" => format!("int:{}", value.as_str().unwrap()),
+                    "doubleValue" => format!("double:{}", value.as_f64().unwrap()),
+                    _ => panic!("Unexpected AnyValue {}", value_type),
+                };
+                (key_value["key"].as_str().unwrap().to_string(), value)
+            })
+            .collect()
+    }
+
+    fn create_events() -> Vec<Arc<MyLogEvent>> {
+        LEVELS
+            .iter()
+            .enumerate()
+            .map(|(index, (level, _))| {
+                let mut log_event = create_test_event(*level, "Orders", "Created");
+                if index == 0 {
+                    log_event.trace_context = TraceContext::parse_traceparent(TRACEPARENT);
+                    for (key, value) in [
+                        ("OrderId", "007"),
+                        ("Amount", "15"),
+                        ("Ratio", "1.5"),
+                        ("Paid", "true"),
+                        ("ExternalId", "12345678901234567890"),
+                    ] {
+                        log_event.add_context_if_missing(key, || value.to_string());
+                    }
+                }
+                Arc::new(log_event)
+            })
+            .collect()
+    }
+
+    fn create_populated_params() -> PopulatedParams {
+        PopulatedParams::new(vec![
+            ("Application", "oms".into()),
+            ("Version", "1.0".into()),
+        ])
+    }
+
+    fn assert_attributes(attributes: &HashMap<String, String>) {
+        assert_eq!("string:Orders", attributes["process"]);
+        assert_eq!("string:007", attributes["OrderId"]);
+        assert_eq!("int:15", attributes["Amount"]);
+        assert_eq!("double:1.5", attributes["Ratio"]);
+        assert_eq!("bool:true", attributes["Paid"]);
+        assert_eq!("string:12345678901234567890", attributes["ExternalId"]);
+    }
+
+    fn assert_resource_attributes(attributes: &HashMap<String, String>) {
+        assert_eq!("string:oms", attributes["service.name"]);
+        assert_eq!("string:1.0", attributes["service.version"]);
+    }
+
+    #[test]
+    fn test_attribute_value_parse() {
+        assert!(matches!(
+            AttributeValue::parse("15"),
+            AttributeValue::Int(15)
+        ));
+        assert!(matches!(
+            AttributeValue::parse("-15"),
+            AttributeValue::Int(-15)
+        ));
+        assert!(matches!(
+            AttributeValue::parse("1.5"),
+            AttributeValue::Double(_)
+        ));
+        assert!(matches!(
+            AttributeValue::parse("true"),
+            AttributeValue::Bool(true)
+        ));
+        assert!(matches!(
+            AttributeValue::parse("abc"),
+            AttributeValue::String("abc")
+        ));
+        assert!(matches!(
+            AttributeValue::parse("NaN"),
+            AttributeValue::String("NaN")
+        ));
+        assert!(matches!(
+            AttributeValue::parse("007"),
+            AttributeValue::String("007")
+        ));
+        assert!(matches!(
+            AttributeValue::parse("+15"),
+            AttributeValue::String("+15")
+        ));
+        assert!(matches!(
+            AttributeValue::parse("1.50"),
+            AttributeValue::String("1.50")
+        ));
+        assert!(matches!(
+            AttributeValue::parse("1e3"),
+            AttributeValue::String("1e3")
+        ));
+        assert!(matches!(
+            AttributeValue::parse("12345678901234567890"),
+            AttributeValue::String("12345678901234567890")
+        ));
+    }
+
+    #[test]
+    fn test_protobuf_request() {
+        let payload = serialize_logs_request(
+            OtlpEncoding::Protobuf,
+            Vec::new(),
+            &create_events(),
+            &create_populated_params(),
+        );
+
+        let request = decode_proto(&payload);
+        let resource_logs = decode_proto(get_bytes(&request, 1)[0]);
+
+        let resource = decode_proto(get_bytes(&resource_logs, 1)[0]);
+        assert_resource_attributes(&decode_proto_attributes(&resource));
+
+        let scope_logs = decode_proto(get_bytes(&resource_logs, 2)[0]);
+        let log_records = get_bytes(&scope_logs, 2);
+        assert_eq!(LEVELS.len(), log_records.len());
+
+        for (log_record, (level, severity_number)) in log_records.iter().zip(LEVELS) {
+            let log_record = decode_proto(log_record);
+            assert_eq!(severity_number, get_varint(&log_record, 2));
+            assert_eq!(level.as_str(), get_string(&log_record, 3));
+            assert_eq!(
+                "Created",
+                get_string(&decode_proto(get_bytes(&log_record, 5)[0]), 1)
+            );
+        }
+
+        let first = decode_proto(log_records[0]);
+        assert_attributes(&decode_proto_attributes(&first));
+        assert_eq!(
+            "4bf92f3577b34da6a3ce929d0e0e4736",
+            to_hex(get_bytes(&first, 9)[0])
+        );
+        assert_eq!("00f067aa0ba902b7", to_hex(get_bytes(&first, 10)[0]));
+
+        let second = decode_proto(log_records[1]);
+        assert!(get_bytes(&second, 9).is_empty());
+        assert!(get_bytes(&second, 10).is_empty());
+    }
+
+    #[test]
+    fn test_json_request() {
+        let payload = serialize_logs_request(
+            OtlpEncoding::Json,
+            Vec::new(),
+            &create_events(),
+            &create_populated_params(),
+        );
+
+        let request: serde_json::Value = serde_json::from_slice(&payload).unwrap();
+        let resource_logs = &request["resourceLogs"][0];
+        assert_resource_attributes(&decode_json_attributes(
+            &resource_logs["resource"]["attributes"],
+        ));
+
+        let log_records = resource_logs["scopeLogs"][0]["logRecords"]
+            .as_array()
+            .unwrap();
+        assert_eq!(LEVELS.len(), log_records.len());
+
+        for (log_record, (level, severity_number)) in log_records.iter().zip(LEVELS) {
+            assert_eq!(
+                severity_number,
+                log_record["severityNumber"].as_u64().unwrap()
+            );
+            assert_eq!(level.as_str(), log_record["severityText"].as_str().unwrap());
+            assert_eq!(
+                "Created",
+                log_record["body"]["stringValue"].as_str().unwrap()
+            );
+        }
+
+        assert_attributes(&decode_json_attributes(&log_records[0]["attributes"]));
+        assert_eq!(
+            "4bf92f3577b34da6a3ce929d0e0e4736",
+            log_records[0]["traceId"]
+        );
+        assert_eq!("00f067aa0ba902b7", log_records[0]["spanId"]);
+
+        assert!(log_records[1].get("traceId").is_none());
+        assert!(log_records[1].get("spanId").is_none());
+    }
+}
@@ -0,0 +1,152 @@
+use std::{sync::Arc, time::Duration};
+
+use my_seq_logger::{parse_number, split_key_value};
+
+#[async_trait::async_trait]
+pub trait OtlpSettings {
+    async fn get_conn_string(&self) -> String;
+}
+
+const DEFAULT_FLUSH_SLEEP: u64 = 1;
+const DEFAULT_BATCH_SIZE: usize = 512;
+const DEFAULT_TIMEOUT: u64 = 10;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OtlpEncoding {
+    Protobuf,
+    Json,
+}
+
+impl OtlpEncoding {
+    pub fn get_content_type(&self) -> &'static str {
+        match self {
+            OtlpEncoding::Protobuf => "application/x-protobuf",
+            OtlpEncoding::Json => "application/json",
+        }
+    }
+}
+
+pub struct OtlpLoggerSettings {
+    pub url: String,
+    pub encoding: OtlpEncoding,
+    pub headers: Vec<(String, String)>,
+    pub max_batch_size: usize,
+    /// Interval at which queued events are uploaded.
+    pub flush_delay: Duration,
+    pub queue_size: Option<usize>,
+    pub timeout: Duration,
+}
+
+impl OtlpLoggerSettings {
+    pub async fn read(settings: &Arc<dyn OtlpSettings + Send + Sync + 'static>) -> Self {
+        loop {
+            let conn_string = settings.get_conn_string().await;
+            let settings = OtlpLoggerSettings::try_parse(conn_string.as_str());
+
+            match settings {
+                Ok(result) => return result,
+                Err(err) => {
+                    eprintln!("Can not parse OTLP Logs settings. Err: {:?}", err);
+                    tokio::time::sleep(Duration::from_secs(5)).await;
+                }
+            };
+        }
+    }
+
+    /// `url=http://otel-collector:4318;encoding=protobuf;headers=Authorization:Bearer xxx,X-Tenant:a;batchsize=512`
+    pub fn try_parse(conn_string: &str) -> Result<Self, String> {
+        let mut url = None;
+        let mut encoding = OtlpEncoding::Protobuf;
+        let mut headers = Vec::new();
+        let mut max_batch_size = DEFAULT_BATCH_SIZE;
+        let mut flush_delay = DEFAULT_FLUSH_SLEEP;
+        let mut queue_size = None;
+        let mut timeout = DEFAULT_TIMEOUT;
+
+        for item in conn_string.split(';') {
+            if item.is_empty() {
+                continue;
+            }
+
+            let (key, value) = split_key_value(item, "OTLP connection string")?;
+
+            match key {
+                "url" => {
+                    url = Some(value);
+                }
+                "encoding" => {
+                    encoding = match value {
+                        "protobuf" => OtlpEncoding::Protobuf,
+                        "json" => OtlpEncoding::Json,
+                        _ => return Err(format!("Invalid OTLP encoding {}", value)),
+                    };
+                }
+                "headers" => {
+                    for header in value.split(',') {
+                        match header.find(':') {
+                            Some(index) => headers.push((
+                                header[..index].trim().to_string(),
+                                header[index + 1..].trim().to_string(),
+                            )),
+                            None => return Err(format!("Invalid OTLP header {}", header)),
+                        }
+                    }
+                }
+                "batchsize" => {
+                    max_batch_size = parse_number(key, value)?;
+                }
+                "flushdelay" => {
+                    flush_delay = parse_number(key, value)?;
+                }
+                "queuesize" => {
+                    queue_size = Some(parse_number(key, value)?);
+                }
+                "timeout" => {
+                    timeout = parse_number(key, value)?;
+                }
+                _ => {
+                    return Err(format!("Invalid key {} of OTLP connection string", key));
+                }
+            }
+        }
+
+        let url = match url {
+            Some(url) => url,
+            None => return Err("There is no URL parameter in OTLP connection string".to_string()),
+        };
+
+        Ok(Self {
+            url: url.to_string(),
+            encoding,
+            headers,
+            max_batch_size,
+            flush_delay: Duration::from_secs(flush_delay),
+            queue_size,
+            timeout: Duration::from_secs(timeout),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{OtlpEncoding, OtlpLoggerSettings};
+
+    #[test]
+    fn test_try_parse() {
+        let result = OtlpLoggerSettings::try_parse(
+            "url=http://collector:4318;encoding=json;headers=Authorization:Bearer 1,X-Tenant:a;batchsize=100",
+        )
+        .unwrap();
+
+        assert_eq!("http://collector:4318", result.url);
+        assert_eq!(OtlpEncoding::Json, result.encoding);
+        assert_eq!(
+            vec![
+                ("Authorization".to_string(), "Bearer 1".to_string()),
+                ("X-Tenant".to_string(), "a".to_string())
+            ],
+            result.headers
+        );
+        assert_eq!(100, result.max_batch_size);
+    }
+}
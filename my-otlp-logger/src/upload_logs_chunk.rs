@@ -0,0 +1,77 @@
+use std::sync::Arc;
+
+use my_logger_core::{MyLogEvent, PopulatedParams};
+use my_seq_logger::LogsChunkUploader;
+
+use crate::OtlpEncoding;
+
+const INITIAL_CHUNK_CAPACITY: usize = 64 * 1024;
+
+pub async fn upload_log_events_chunk(
+    uploader: &(impl LogsChunkUploader + ?Sized),
+    encoding: OtlpEncoding,
+    max_batch_size: usize,
+    populated_params: PopulatedParams,
+    data: Vec<Arc<MyLogEvent>>,
+) {
+    for batch in data.chunks(max_batch_size.max(1)) {
+        let chunk_to_upload = crate::serialize_logs_request(
+            encoding,
+            Vec::with_capacity(INITIAL_CHUNK_CAPACITY),
+            batch,
+            &populated_params,
+        );
+
+        uploader.upload_chunk(chunk_to_upload).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use my_logger_core::{test_utils::create_test_event, LogLevel, PopulatedParams};
+    use my_seq_logger::LogsChunkUploader;
+    use parking_lot::Mutex;
+
+    use crate::OtlpEncoding;
+
+    pub struct MockUploader {
+        chunks: Mutex<Vec<Vec<u8>>>,
+    }
+
+    #[async_trait::async_trait]
+    impl LogsChunkUploader for MockUploader {
+        async fn upload_chunk(&self, chunk: Vec<u8>) {
+            self.chunks.lock().push(chunk);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_events_are_split_into_batches() {
+        let events: Vec<_> = (0..5)
+            .map(|no| {
+                Arc::new(create_test_event(
+                    LogLevel::Info,
+                    "Test",
+                    format!("Message{}", no).as_str(),
+                ))
+            })
+            .collect();
+
+        let mock_uploader = MockUploader {
+            chunks: Mutex::new(Vec::new()),
+        };
+
+        super::upload_log_events_chunk(
+            &mock_uploader,
+            OtlpEncoding::Protobuf,
+            2,
+            PopulatedParams::new_empty(),
+            events,
+        )
+        .await;
+
+        assert_eq!(3, mock_uploader.chunks.lock().len());
+    }
+}
@@ -0,0 +1,120 @@
+use std::time::Duration;
+
+use flurl::{body::FlUrlBody, *};
+use my_seq_logger::LogsChunkUploader;
+
+use crate::OtlpEncoding;
+
+pub struct OtlpUploader {
+    pub url: String,
+    pub encoding: OtlpEncoding,
+    pub headers: Vec<(String, String)>,
+    pub otlp_debug: bool,
+    pub timeout: Duration,
+}
+
+impl OtlpUploader {
+    pub fn new(
+        url: String,
+        encoding: OtlpEncoding,
+        headers: Vec<(String, String)>,
+        timeout: Duration,
+    ) -> Self {
+        Self {
+            url,
+            encoding,
+            headers,
+            otlp_debug: std::env::var("OTLP_DEBUG").is_ok(),
+            timeout,
+        }
+    }
+
+    pub fn matches(
+        &self,
+        url: &str,
+        encoding: OtlpEncoding,
+        headers: &[(String, String)],
+        timeout: Duration,
+    ) -> bool {
+        self.url == url
+            && self.encoding == encoding
+            && self.headers.as_slice() == headers
+            && self.timeout == timeout
+    }
+}
+
+#[async_trait::async_trait]
+impl LogsChunkUploader for OtlpUploader {
+    async fn upload_chunk(&self, chunk_to_upload: Vec<u8>) {
+        const MAX_ATTEMPTS: u32 = 4;
+
+        if self.otlp_debug {
+            println!("Sending OTLP logs len={}", chunk_to_upload.len());
+        }
+
+        let mut attempt_no: u32 = 0;
+        let mut chunk = Some(chunk_to_upload);
+        loop {
+            attempt_no += 1;
+
+            let data = if attempt_no == MAX_ATTEMPTS {
+                chunk.take().unwrap()
+            } else {
+                chunk.as_ref().unwrap().clone()
+            };
+
+            let mut fl_url = FlUrl::new(self.url.as_str())
+                .set_timeout(self.timeout)
+                .append_path_segment("v1")
+                .append_path_segment("logs")
+                .with_header("Content-Type", self.encoding.get_content_type())
+                .with_retries(3);
+
+            for (name, value) in self.headers.iter() {
+                fl_url = fl_url.with_header(name.as_str(), value.as_str());
+            }
+
+            let response = fl_url
+                .post(FlUrlBody::Raw {
+                    data,
+                    content_type: None,
+                })
+                .await;
+
+            match response {
+                Ok(response) => {
+                    let status_code = response.get_status_code();
+                    if self.otlp_debug {
+                        println!("OTLP result: {}", status_code);
+                    }
+
+                    if (200..300).contains(&status_code) {
+                        return;
+                    }
+
+                    // 4xx other than throttling means the payload is rejected and a retry won't help
+                    if status_code != 429 && (400..500).contains(&status_code) {
+                        eprintln!("OTLP collector rejected logs. Status code: {}", status_code);
+                        return;
+                    }
+
+                    if attempt_no >= MAX_ATTEMPTS {
+                        return;
+                    }
+                }
+                Err(err) => {
+                    eprintln!(
+                        "Attempt: {} Error while uploading logs to OTLP collector. Err: {:?}",
+                        attempt_no, err
+                    );
+
+                    if attempt_no >= MAX_ATTEMPTS {
+                        return;
+                    }
+                }
+            }
+
+            tokio::time::sleep(Duration::from_secs(1)).await;
+        }
+    }
+}
@@ -0,0 +1,34 @@
+use std::{future::Future, time::Duration};
+
+/// Runs `flush` on a separate thread with its own runtime, so it works both from a tokio worker
+/// and from a plain thread. Waits for it at most `timeout`.
+pub fn flush_on_private_runtime(
+    writer_name: &str,
+    timeout: Duration,
+    flush: impl Future<Output = ()> + Send + 'static,
+) {
+    let (sender, receiver) = std::sync::mpsc::channel();
+    let thread_writer_name = writer_name.to_string();
+
+    let spawn_result = std::thread::Builder::new()
+        .name("logger-flush".to_string())
+        .spawn(move || {
+            let runtime = tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build();
+
+            match runtime {
+                Ok(runtime) => runtime.block_on(flush),
+                Err(err) => eprintln!(
+                    "Can not create runtime to flush {} logs. Err: {:?}",
+                    thread_writer_name, err
+                ),
+            }
+
+            let _ = sender.send(());
+        });
+
+    if spawn_result.is_ok() && receiver.recv_timeout(timeout).is_err() {
+        eprintln!("{} logs were not flushed within {:?}", writer_name, timeout);
+    }
+}
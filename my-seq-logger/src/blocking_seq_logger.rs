@@ -0,0 +1,292 @@
+use std::{
+    sync::Arc,
+    thread::JoinHandle,
+    time::{Duration, Instant},
+};
+
+use my_logger_core::{MyLogEvent, MyLogger, MyLoggerReader};
+use parking_lot::{Condvar, Mutex};
+
+use crate::{FlUrlUploader, LogEventsQueue, LogsChunkUploader, SeqLoggerSettings};
+
+#[derive(Default)]
+struct WorkerState {
+    wake_up: bool,
+    shutdown: bool,
+    requested_flush: u64,
+    completed_flush: u64,
+}
+
+struct BlockingSeqLoggerInner {
+    log_events: LogEventsQueue,
+    flush_delay: Duration,
+    state: Mutex<WorkerState>,
+    state_changed: Condvar,
+}
+
+/// Seq writer for applications without a tokio runtime.
+///
+/// Events are uploaded once per `flush_delay` from a dedicated OS thread. The uploader is async,
+/// so that thread owns a private single-threaded tokio runtime and blocks on every upload;
+/// callers never need `#[tokio::main]` and never see that runtime.
+///
+/// [`BlockingSeqLogger::shutdown`] uploads the queued events and stops the thread.
+/// Dropping the last handle does the same, but the target logger keeps a handle until the writer is unplugged.
+pub struct BlockingSeqLogger {
+    inner: Arc<BlockingSeqLoggerInner>,
+    logger: Arc<MyLogger>,
+    worker: Mutex<Option<JoinHandle<()>>>,
+}
+
+impl BlockingSeqLogger {
+    /// Plugs the writer into `LOGGER` and installs the panic hook.
+    pub fn enable(settings: SeqLoggerSettings) -> Arc<Self> {
+        my_logger_core::install_panic_hook();
+        Self::start(settings, my_logger_core::LOGGER.clone(), None)
+    }
+
+    pub fn enable_from_connection_string(conn_string: &str) -> Arc<Self> {
+        match SeqLoggerSettings::try_parse(conn_string) {
+            Ok(settings) => Self::enable(settings),
+            Err(err) => panic!("Can not parse Seq settings. Err: {}", err),
+        }
+    }
+
+    /// Plugs the writer into `logger`. Without `uploader` events are posted to the url from settings.
+    pub fn start(
+        settings: SeqLoggerSettings,
+        logger: Arc<MyLogger>,
+        uploader: Option<Arc<dyn LogsChunkUploader + Send + Sync + 'static>>,
+    ) -> Arc<Self> {
+        let mut log_events = LogEventsQueue::new();
+        if let Some(queue_size) = settings.queue_size {
+            log_events.configure_size(queue_size);
+        }
+
+        let inner = Arc::new(BlockingSeqLoggerInner {
+            log_events,
+            flush_delay: settings.flush_delay,
+            state: Mutex::new(WorkerState::default()),
+            state_changed: Condvar::new(),
+        });
+
+        let uploader = uploader.unwrap_or_else(|| {
+            Arc::new(FlUrlUploader::new(
+                settings.url.clone(),
+                settings.api_key.clone(),
+                settings.timeout,
+            ))
+        });
+
+        let worker_inner = inner.clone();
+        let worker_logger = logger.clone();
+        let worker = std::thread::Builder::new()
+            .name("blocking-seq-logger".to_string())
+            .spawn(move || run_worker(worker_inner, worker_logger, uploader))
+            .expect("Can not start blocking Seq logger thread");
+
+        let seq_logger = Arc::new(Self {
+            inner,
+            logger: logger.clone(),
+            worker: Mutex::new(Some(worker)),
+        });
+
+        logger.plug_reader(seq_logger.clone());
+        seq_logger
+    }
+
+    /// Unplugs the writer, uploads the queued events and waits for the worker thread to exit.
+    pub fn shutdown(self: &Arc<Self>) {
+        let reader: Arc<dyn MyLoggerReader + Send + Sync + 'static> = self.clone();
+        self.logger.unplug_reader(&reader);
+        self.stop_worker();
+    }
+
+    fn stop_worker(&self) {
+        let worker = match self.worker.lock().take() {
+            Some(worker) => worker,
+            None => return,
+        };
+
+        {
+            let mut state = self.inner.state.lock();
+            state.shutdown = true;
+            self.inner.state_changed.notify_all();
+        }
+
+        if worker.join().is_err() {
+            eprintln!("Blocking Seq logger thread panicked");
+        }
+    }
+}
+
+impl Drop for BlockingSeqLogger {
+    fn drop(&mut self) {
+        self.stop_worker();
+    }
+}
+
+impl MyLoggerReader for BlockingSeqLogger {
+    fn write_log(&self, log_event: Arc<MyLogEvent>) {
+        self.inner.log_events.enqueue(log_event);
+    }
+
+    /// Wakes the worker and waits until every event queued so far is uploaded.
+    fn flush(&self, timeout: Duration) {
+        let deadline = Instant::now() + timeout;
+
+        let mut state = self.inner.state.lock();
+        if state.shutdown {
+            return;
+        }
+
+        state.requested_flush += 1;
+        state.wake_up = true;
+        let flush_no = state.requested_flush;
+        self.inner.state_changed.notify_all();
+
+        while state.completed_flush < flush_no {
+            if self
+                .inner
+                .state_changed
+                .wait_until(&mut state, deadline)
+                .timed_out()
+            {
+                eprintln!("Seq logs were not flushed within {:?}", timeout);
+                return;
+            }
+        }
+    }
+}
+
+fn run_worker(
+    inner: Arc<BlockingSeqLoggerInner>,
+    logger: Arc<MyLogger>,
+    uploader: Arc<dyn LogsChunkUploader + Send + Sync + 'static>,
+) {
+    let runtime = match tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+    {
+        Ok(runtime) => runtime,
+        Err(err) => {
+            eprintln!(
+                "Can not create runtime for blocking Seq logger. Err: {:?}",
+                err
+            );
+            return;
+        }
+    };
+
+    println!("Blocking Seq Logs writer is started");
+
+    loop {
+        let (flush_no, shutdown) = {
+            let mut state = inner.state.lock();
+            if !state.wake_up && !state.shutdown {
+                inner.state_changed.wait_for(&mut state, inner.flush_delay);
+            }
+            state.wake_up = false;
+            (state.requested_flush, state.shutdown)
+        };
+
+        while let Some(events) = inner.log_events.dequeue() {
+            runtime.block_on(crate::upload_logs_chunk::upload_log_events_chunk(
+                uploader.as_ref(),
+                logger.get_populated_params(),
+                events,
+            ));
+        }
+
+        let mut state = inner.state.lock();
+        if state.completed_flush < flush_no {
+            state.completed_flush = flush_no;
+            inner.state_changed.notify_all();
+        }
+
+        if shutdown {
+            return;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{sync::Arc, time::Duration};
+
+    use my_logger_core::{LogEventCtx, MyLogger, MyLoggerReader};
+    use parking_lot::Mutex;
+
+    use crate::{LogsChunkUploader, SeqLoggerSettings};
+
+    use super::BlockingSeqLogger;
+
+    #[derive(Default)]
+    struct MockUploader {
+        chunks: Mutex<Vec<String>>,
+    }
+
+    #[async_trait::async_trait]
+    impl LogsChunkUploader for MockUploader {
+        async fn upload_chunk(&self, chunk: Vec<u8>) {
+            self.chunks.lock().push(String::from_utf8(chunk).unwrap());
+        }
+    }
+
+    fn start(logger: &Arc<MyLogger>, uploader: &Arc<MockUploader>) -> Arc<BlockingSeqLogger> {
+        let settings =
+            SeqLoggerSettings::try_parse("url=http://localhost:5341;flushdelay=60").unwrap();
+        BlockingSeqLogger::start(settings, logger.clone(), Some(uploader.clone()))
+    }
+
+    #[test]
+    fn test_flush_uploads_without_tokio() {
+        let logger = Arc::new(MyLogger::new());
+        logger.get_console_filter().set_print_infos(false);
+        let uploader = Arc::new(MockUploader::default());
+        let seq_logger = start(&logger, &uploader);
+
+        logger.write_info("Orders", "Created", LogEventCtx::new());
+        seq_logger.flush(Duration::from_secs(5));
+
+        let chunks = uploader.chunks.lock().clone();
+        assert_eq!(1, chunks.len());
+        assert!(chunks[0].contains("Created"));
+
+        seq_logger.shutdown();
+    }
+
+    #[test]
+    fn test_shutdown_uploads_queued_events() {
+        let logger = Arc::new(MyLogger::new());
+        logger.get_console_filter().set_print_infos(false);
+        let uploader = Arc::new(MockUploader::default());
+        let seq_logger = start(&logger, &uploader);
+
+        logger.write_info("Orders", "Before shutdown", LogEventCtx::new());
+        seq_logger.shutdown();
+        logger.write_info("Orders", "After shutdown", LogEventCtx::new());
+
+        let chunks = uploader.chunks.lock().clone();
+        assert_eq!(1, chunks.len());
+        assert!(chunks[0].contains("Before shutdown"));
+    }
+
+    #[test]
+    fn test_drop_uploads_queued_events() {
+        let logger = Arc::new(MyLogger::new());
+        logger.get_console_filter().set_print_infos(false);
+        let uploader = Arc::new(MockUploader::default());
+        let seq_logger = start(&logger, &uploader);
+
+        logger.write_info("Orders", "Before drop", LogEventCtx::new());
+
+        let reader: Arc<dyn MyLoggerReader + Send + Sync + 'static> = seq_logger;
+        logger.unplug_reader(&reader);
+        drop(reader);
+
+        let chunks = uploader.chunks.lock().clone();
+        assert_eq!(1, chunks.len());
+        assert!(chunks[0].contains("Before drop"));
+    }
+}
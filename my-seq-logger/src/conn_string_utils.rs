@@ -0,0 +1,46 @@
+use std::str::FromStr;
+
+/// Splits `key=value` item of a `;` separated connection string.
+/// `conn_string_name` names the connection string in the error message.
+pub fn split_key_value<'s>(
+    src: &'s str,
+    conn_string_name: &str,
+) -> Result<(&'s str, &'s str), String> {
+    match src.find('=') {
+        Some(index) => Ok((&src[..index], &src[index + 1..])),
+        None => Err(format!("Invalid {} key value of {}", src, conn_string_name)),
+    }
+}
+
+pub fn parse_number<T: FromStr>(key: &str, value: &str) -> Result<T, String> {
+    value
+        .parse::<T>()
+        .map_err(|_| format!("{} must be a number", key))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_number, split_key_value};
+
+    #[test]
+    fn test_split_key_value() {
+        assert_eq!(
+            ("url", "http://localhost:3100/a=b"),
+            split_key_value("url=http://localhost:3100/a=b", "Loki connection string").unwrap()
+        );
+
+        assert_eq!(
+            "Invalid url key value of Loki connection string",
+            split_key_value("url", "Loki connection string").unwrap_err()
+        );
+    }
+
+    #[test]
+    fn test_parse_number() {
+        assert_eq!(10, parse_number::<usize>("queuesize", "10").unwrap());
+        assert_eq!(
+            "queuesize must be a number",
+            parse_number::<u64>("queuesize", "ten").unwrap_err()
+        );
+    }
+}
@@ -0,0 +1,20 @@
+use std::{sync::Weak, time::Duration};
+
+/// Queued events are written once per `flush_delay`, not on every event.
+/// Calls `tick` until the writer is dropped. Must be called inside a tokio runtime.
+pub fn start_flush_timer<TWriter: Send + Sync + 'static>(
+    writer: Weak<TWriter>,
+    flush_delay: Duration,
+    tick: fn(&TWriter),
+) {
+    tokio::spawn(async move {
+        loop {
+            tokio::time::sleep(flush_delay).await;
+
+            match writer.upgrade() {
+                Some(writer) => tick(&writer),
+                None => return,
+            }
+        }
+    });
+}
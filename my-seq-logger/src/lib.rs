@@ -1,7 +1,14 @@
 mod seq_logger;
 mod settings;
 mod upload_logs_chunk;
-pub use seq_logger::SeqLogger;
+pub use seq_logger::{SeqLogger, SeqLoggerMetrics};
+pub use upload_logs_chunk::upload_serialized_log_events;
+mod proto_writer;
+pub use proto_writer::*;
+mod seq_logger_builder;
+pub use seq_logger_builder::*;
+mod blocking_seq_logger;
+pub use blocking_seq_logger::BlockingSeqLogger;
 pub use settings::*;
 mod log_events_queue;
 pub use log_events_queue::*;
@@ -13,3 +20,11 @@ mod seq_utils;
 pub use seq_utils::*;
 mod uploader;
 pub use uploader::*;
+mod conn_string_utils;
+pub use conn_string_utils::*;
+mod pending_events;
+pub use pending_events::*;
+mod flush_timer;
+pub use flush_timer::*;
+mod blocking_flush;
+pub use blocking_flush::*;
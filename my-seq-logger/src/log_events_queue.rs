@@ -1,35 +1,72 @@
-use std::sync::Arc;
+use std::{
+    collections::VecDeque,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+};
 
 use my_logger_core::MyLogEvent;
 use parking_lot::Mutex;
 
 const INITIAL_QUEUE_CAPACITY: usize = 64;
 
+/// What happens to events written while the queue is full.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QueuePolicy {
+    Unbounded,
+    /// New events are dropped until the queue is uploaded.
+    DropNewest(usize),
+    /// The oldest queued events are dropped to make room for new ones.
+    DropOldest(usize),
+}
+
 pub struct LogEventsQueue {
-    queue: Mutex<Vec<Arc<MyLogEvent>>>,
-    queue_size: Option<usize>,
+    queue: Mutex<VecDeque<Arc<MyLogEvent>>>,
+    policy: QueuePolicy,
+    dropped: AtomicU64,
 }
 
 impl LogEventsQueue {
     pub fn new() -> Self {
         Self {
-            queue: Mutex::new(Vec::with_capacity(INITIAL_QUEUE_CAPACITY)),
-            queue_size: None,
+            queue: Mutex::new(VecDeque::with_capacity(INITIAL_QUEUE_CAPACITY)),
+            policy: QueuePolicy::Unbounded,
+            dropped: AtomicU64::new(0),
         }
     }
 
     pub fn configure_size(&mut self, queue_size: usize) {
-        self.queue_size = Some(queue_size);
+        self.policy = QueuePolicy::DropNewest(queue_size);
+    }
+
+    pub fn configure_policy(&mut self, policy: QueuePolicy) {
+        self.policy = policy;
     }
 
     pub fn enqueue(&self, log_event: Arc<MyLogEvent>) {
         let mut queue = self.queue.lock();
-        if let Some(limit) = self.queue_size {
-            if queue.len() >= limit {
-                return;
+        match self.policy {
+            QueuePolicy::Unbounded => {}
+            QueuePolicy::DropNewest(limit) => {
+                if queue.len() >= limit {
+                    self.dropped.fetch_add(1, Ordering::Relaxed);
+                    return;
+                }
+            }
+            QueuePolicy::DropOldest(limit) => {
+                if limit == 0 {
+                    self.dropped.fetch_add(1, Ordering::Relaxed);
+                    return;
+                }
+
+                while queue.len() >= limit {
+                    queue.pop_front();
+                    self.dropped.fetch_add(1, Ordering::Relaxed);
+                }
             }
         }
-        queue.push(log_event);
+        queue.push_back(log_event);
     }
 
     pub fn dequeue(&self) -> Option<Vec<Arc<MyLogEvent>>> {
@@ -38,6 +75,60 @@ impl LogEventsQueue {
             return None;
         }
         let cap = queue.capacity();
-        Some(std::mem::replace(&mut *queue, Vec::with_capacity(cap)))
+        Some(std::mem::replace(&mut *queue, VecDeque::with_capacity(cap)).into())
+    }
+
+    pub fn len(&self) -> usize {
+        self.queue.lock().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.queue.lock().is_empty()
+    }
+
+    /// Events rejected by the queue policy since the queue was created.
+    pub fn get_dropped_amount(&self) -> u64 {
+        self.dropped.load(Ordering::Relaxed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use my_logger_core::{test_utils::create_test_event, LogLevel, MyLogEvent};
+
+    use super::{LogEventsQueue, QueuePolicy};
+
+    fn create_event(message: &str) -> Arc<MyLogEvent> {
+        Arc::new(create_test_event(LogLevel::Info, "Test", message))
+    }
+
+    #[test]
+    fn test_queue_policies() {
+        let mut queue = LogEventsQueue::new();
+        queue.configure_policy(QueuePolicy::DropOldest(2));
+        for message in ["1", "2", "3"] {
+            queue.enqueue(create_event(message));
+        }
+
+        let messages: Vec<String> = queue
+            .dequeue()
+            .unwrap()
+            .iter()
+            .map(|itm| itm.message.clone())
+            .collect();
+        assert_eq!(vec!["2", "3"], messages);
+        assert_eq!(1, queue.get_dropped_amount());
+
+        let mut queue = LogEventsQueue::new();
+        queue.configure_policy(QueuePolicy::DropNewest(2));
+        for message in ["1", "2", "3"] {
+            queue.enqueue(create_event(message));
+        }
+
+        assert_eq!(2, queue.len());
+        assert_eq!("1", queue.dequeue().unwrap()[0].message);
+        assert_eq!(1, queue.get_dropped_amount());
     }
 }
@@ -0,0 +1,108 @@
+use std::{collections::VecDeque, fmt::Arguments, sync::Arc};
+
+use my_logger_core::MyLogEvent;
+
+pub const DEFAULT_PENDING_SIZE: usize = 10_000;
+
+/// Events taken from [`crate::LogEventsQueue`] but not delivered yet.
+/// Kept while the destination is unreachable; the oldest ones are dropped above `max_size`.
+pub struct PendingEvents {
+    events: VecDeque<Arc<MyLogEvent>>,
+    max_size: usize,
+    dropped: usize,
+}
+
+impl PendingEvents {
+    pub fn new(max_size: usize) -> Self {
+        Self {
+            events: VecDeque::new(),
+            max_size,
+            dropped: 0,
+        }
+    }
+
+    pub fn add(&mut self, events: Vec<Arc<MyLogEvent>>) {
+        self.events.extend(events);
+
+        while self.events.len() > self.max_size {
+            self.events.pop_front();
+            self.dropped += 1;
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.events.len()
+    }
+
+    pub fn front(&self) -> Option<&Arc<MyLogEvent>> {
+        self.events.front()
+    }
+
+    pub fn pop_front(&mut self) {
+        self.events.pop_front();
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Arc<MyLogEvent>> {
+        self.events.iter()
+    }
+
+    pub fn remove_front(&mut self, amount: usize) {
+        self.events.drain(..amount.min(self.events.len()));
+    }
+
+    /// Returns events taken with [`PendingEvents::remove_front`] back to the queue keeping their order.
+    pub fn push_front(&mut self, events: Vec<Arc<MyLogEvent>>) {
+        for log_event in events.into_iter().rev() {
+            self.events.push_front(log_event);
+        }
+    }
+
+    pub fn get_dropped_amount(&self) -> usize {
+        self.dropped
+    }
+
+    /// Prints how many events were dropped since the previous report.
+    pub fn report_dropped(&mut self, destination: Arguments) {
+        if self.dropped == 0 {
+            return;
+        }
+
+        eprintln!(
+            "{} log events were dropped while {} was unreachable",
+            self.dropped, destination
+        );
+        self.dropped = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use my_logger_core::{test_utils::create_test_event, LogLevel};
+
+    use super::PendingEvents;
+
+    #[test]
+    fn test_oldest_events_are_dropped() {
+        let mut pending = PendingEvents::new(2);
+
+        pending.add(
+            ["1", "2", "3"]
+                .into_iter()
+                .map(|message| Arc::new(create_test_event(LogLevel::Info, "Test", message)))
+                .collect(),
+        );
+
+        assert_eq!(2, pending.len());
+        assert_eq!(1, pending.get_dropped_amount());
+        assert_eq!("2", pending.front().unwrap().message);
+
+        pending.report_dropped(format_args!("test"));
+        assert_eq!(0, pending.get_dropped_amount());
+    }
+}
@@ -0,0 +1,84 @@
+const WIRE_TYPE_VARINT: u8 = 0;
+const WIRE_TYPE_FIXED64: u8 = 1;
+const WIRE_TYPE_LEN: u8 = 2;
+
+/// Minimal protobuf encoder covering the field types used by the OTLP logs and Loki push messages.
+pub struct ProtoWriter {
+    buffer: Vec<u8>,
+}
+
+impl ProtoWriter {
+    pub fn new() -> Self {
+        Self { buffer: Vec::new() }
+    }
+
+    pub fn from_vec(mut buffer: Vec<u8>) -> Self {
+        buffer.clear();
+        Self { buffer }
+    }
+
+    pub fn write_varint_field(&mut self, field_no: u32, value: u64) {
+        self.write_tag(field_no, WIRE_TYPE_VARINT);
+        self.write_varint(value);
+    }
+
+    pub fn write_fixed64_field(&mut self, field_no: u32, value: u64) {
+        self.write_tag(field_no, WIRE_TYPE_FIXED64);
+        self.buffer.extend_from_slice(&value.to_le_bytes());
+    }
+
+    pub fn write_bytes_field(&mut self, field_no: u32, value: &[u8]) {
+        self.write_tag(field_no, WIRE_TYPE_LEN);
+        self.write_varint(value.len() as u64);
+        self.buffer.extend_from_slice(value);
+    }
+
+    pub fn write_string_field(&mut self, field_no: u32, value: &str) {
+        self.write_bytes_field(field_no, value.as_bytes());
+    }
+
+    pub fn write_message_field(&mut self, field_no: u32, write: impl FnOnce(&mut ProtoWriter)) {
+        let mut nested = ProtoWriter::new();
+        write(&mut nested);
+        self.write_bytes_field(field_no, nested.buffer.as_slice());
+    }
+
+    pub fn build(self) -> Vec<u8> {
+        self.buffer
+    }
+
+    fn write_tag(&mut self, field_no: u32, wire_type: u8) {
+        self.write_varint(((field_no as u64) << 3) | wire_type as u64);
+    }
+
+    fn write_varint(&mut self, mut value: u64) {
+        while value >= 0x80 {
+            self.buffer.push((value as u8) | 0x80);
+            value >>= 7;
+        }
+        self.buffer.push(value as u8);
+    }
+}
+
+impl Default for ProtoWriter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ProtoWriter;
+
+    #[test]
+    fn test_varint_and_nested_message() {
+        let mut writer = ProtoWriter::new();
+        writer.write_varint_field(2, 300);
+        writer.write_message_field(5, |nested| nested.write_string_field(1, "hi"));
+
+        assert_eq!(
+            vec![0x10, 0xAC, 0x02, 0x2A, 0x04, 0x0A, 0x02, b'h', b'i'],
+            writer.build()
+        );
+    }
+}
@@ -1,69 +1,82 @@
-use std::sync::Arc;
+use std::{
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
 
-use crate::{SeqLoggerInner, SeqLoggerSettings, SeqSettings};
-use my_logger_core::{LogEventCtx, MyLogEvent, MyLoggerReader};
+use crate::{SeqLogFilter, SeqLoggerBuilder, SeqLoggerInner, SeqSettings};
+use my_logger_core::{MyLogEvent, MyLogger, MyLoggerReader};
 use rust_extensions::{events_loop::EventsLoop, AppStates};
 
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SeqLoggerMetrics {
+    /// Events waiting for the next upload.
+    pub queued_events: usize,
+    /// Events rejected by the queue policy.
+    pub dropped_events: u64,
+    /// Events rejected by filters.
+    pub filtered_events: u64,
+    /// Events passed to the uploader.
+    pub uploaded_events: u64,
+}
+
 pub struct SeqLogger {
-    inner: Arc<SeqLoggerInner>,
-    events_loop: EventsLoop<()>,
-    app_states: Arc<AppStates>,
+    pub(crate) inner: Arc<SeqLoggerInner>,
+    pub(crate) events_loop: EventsLoop<()>,
+    pub(crate) app_states: Arc<AppStates>,
+    pub(crate) logger: Arc<MyLogger>,
+    pub(crate) filters: Vec<SeqLogFilter>,
+    pub(crate) filtered_events: AtomicU64,
 }
 
 impl SeqLogger {
     pub async fn enable_from_connection_string(
         settings: Arc<dyn SeqSettings + Send + Sync + 'static>,
     ) {
-        std::panic::set_hook(Box::new(|itm| {
-            let mut ctx = if let Some(location) = itm.location() {
-                LogEventCtx::new().add("Location", format!("{}", location))
-            } else {
-                LogEventCtx::new()
-            };
-
-            ctx = ctx.add("PanicInfo", format!("{}", itm));
-
-            let payload = itm.payload();
-
-            let panic_message = if let Some(s) = payload.downcast_ref::<&str>() {
-                format!("{s:?}")
-            } else if let Some(s) = payload.downcast_ref::<String>() {
-                format!("{s:?}")
-            } else {
-                format!("{:?}", payload)
-            };
-
-            my_logger_core::LOGGER.write_fatal_error("Panic Handler", panic_message, ctx);
-        }));
-
-        let mut inner = SeqLoggerInner::new(settings.clone());
-        let settings = SeqLoggerSettings::read(&settings).await;
+        SeqLoggerBuilder::new(settings).build().await;
+    }
 
-        if let Some(queue_size) = settings.queue_size {
-            inner.configure(queue_size)
+    pub fn get_metrics(&self) -> SeqLoggerMetrics {
+        SeqLoggerMetrics {
+            queued_events: self.inner.log_events.len(),
+            dropped_events: self.inner.log_events.get_dropped_amount(),
+            filtered_events: self.filtered_events.load(Ordering::Relaxed),
+            uploaded_events: self.inner.uploaded_events.load(Ordering::Relaxed),
         }
+    }
 
-        let result = Self {
-            app_states: AppStates::create_initialized().into(),
-            inner: Arc::new(inner),
-            events_loop: EventsLoop::new("SeqLogger".to_string())
-                .set_iteration_timeout(settings.timeout),
-        };
-
-        result.events_loop.register_event_loop(result.inner.clone());
-
-        result
-            .events_loop
-            .start(result.app_states.clone(), my_logger_core::LOGGER.clone());
+    /// Uploads queued events on a separate thread with its own runtime,
+    /// so it works both from a tokio worker and from a plain thread.
+    pub fn flush(&self, timeout: Duration) {
+        let inner = self.inner.clone();
+        crate::flush_on_private_runtime("Seq", timeout, async move {
+            inner.upload_queued_events().await
+        });
+    }
 
-        let seq_logger = Arc::new(result);
-        my_logger_core::LOGGER.plug_reader(seq_logger.clone());
+    /// Stops receiving events from the target logger. Events queued so far are not uploaded
+    /// unless [`SeqLogger::flush`] is called.
+    pub fn unplug(self: &Arc<Self>) {
+        let reader: Arc<dyn MyLoggerReader + Send + Sync + 'static> = self.clone();
+        self.logger.unplug_reader(&reader);
+        self.events_loop.stop();
     }
 }
 
 impl MyLoggerReader for SeqLogger {
     fn write_log(&self, log_event: Arc<MyLogEvent>) {
+        if !self.filters.iter().all(|filter| filter(&log_event)) {
+            self.filtered_events.fetch_add(1, Ordering::Relaxed);
+            return;
+        }
+
         self.inner.log_events.enqueue(log_event);
         self.events_loop.send(());
     }
+
+    fn flush(&self, timeout: Duration) {
+        SeqLogger::flush(self, timeout);
+    }
 }
@@ -0,0 +1,171 @@
+use std::sync::Arc;
+
+use my_logger_core::{MyLogEvent, MyLogger};
+use rust_extensions::{events_loop::EventsLoop, AppStates};
+
+use crate::{
+    LogsChunkUploader, QueuePolicy, SeqLogger, SeqLoggerInner, SeqLoggerSettings, SeqSettings,
+};
+
+pub type SeqLogFilter = Box<dyn Fn(&MyLogEvent) -> bool + Send + Sync + 'static>;
+
+/// Configures a [`SeqLogger`]. By default it is plugged into the global `LOGGER`,
+/// installs the panic hook and uploads to the url from settings.
+pub struct SeqLoggerBuilder {
+    settings: Arc<dyn SeqSettings + Send + Sync + 'static>,
+    logger: Arc<MyLogger>,
+    panic_hook: bool,
+    queue_policy: Option<QueuePolicy>,
+    uploader: Option<Arc<dyn LogsChunkUploader + Send + Sync + 'static>>,
+    filters: Vec<SeqLogFilter>,
+}
+
+impl SeqLoggerBuilder {
+    pub fn new(settings: Arc<dyn SeqSettings + Send + Sync + 'static>) -> Self {
+        Self {
+            settings,
+            logger: my_logger_core::LOGGER.clone(),
+            panic_hook: true,
+            queue_policy: None,
+            uploader: None,
+            filters: Vec::new(),
+        }
+    }
+
+    pub fn target(mut self, logger: Arc<MyLogger>) -> Self {
+        self.logger = logger;
+        self
+    }
+
+    /// The hook writes panics to the global `LOGGER` and flushes its readers only,
+    /// so it does not cover a writer plugged into another logger with [`SeqLoggerBuilder::target`].
+    pub fn panic_hook(mut self, install: bool) -> Self {
+        self.panic_hook = install;
+        self
+    }
+
+    /// Overrides `queuesize` of the connection string.
+    pub fn queue_policy(mut self, queue_policy: QueuePolicy) -> Self {
+        self.queue_policy = Some(queue_policy);
+        self
+    }
+
+    pub fn uploader(
+        mut self,
+        uploader: Arc<dyn LogsChunkUploader + Send + Sync + 'static>,
+    ) -> Self {
+        self.uploader = Some(uploader);
+        self
+    }
+
+    /// Events are sent to Seq only if every filter returns true.
+    pub fn filter(mut self, filter: impl Fn(&MyLogEvent) -> bool + Send + Sync + 'static) -> Self {
+        self.filters.push(Box::new(filter));
+        self
+    }
+
+    pub async fn build(self) -> Arc<SeqLogger> {
+        if self.panic_hook {
+            my_logger_core::install_panic_hook();
+        }
+
+        let mut inner = SeqLoggerInner::new(self.settings.clone());
+        let settings = SeqLoggerSettings::read(&self.settings).await;
+
+        match self.queue_policy {
+            Some(queue_policy) => inner.log_events.configure_policy(queue_policy),
+            None => {
+                if let Some(queue_size) = settings.queue_size {
+                    inner.configure(queue_size)
+                }
+            }
+        }
+
+        inner.logger = self.logger.clone();
+        inner.custom_uploader = self.uploader;
+
+        let result = SeqLogger {
+            app_states: AppStates::create_initialized().into(),
+            inner: Arc::new(inner),
+            events_loop: EventsLoop::new("SeqLogger".to_string())
+                .set_iteration_timeout(settings.timeout),
+            logger: self.logger.clone(),
+            filters: self.filters,
+            filtered_events: Default::default(),
+        };
+
+        result.events_loop.register_event_loop(result.inner.clone());
+
+        result
+            .events_loop
+            .start(result.app_states.clone(), self.logger.clone());
+
+        let seq_logger = Arc::new(result);
+        self.logger.plug_reader(seq_logger.clone());
+        seq_logger
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{sync::Arc, time::Duration};
+
+    use my_logger_core::{LogEventCtx, MyLogger};
+    use parking_lot::Mutex;
+
+    use crate::{LogsChunkUploader, SeqSettings};
+
+    use super::SeqLoggerBuilder;
+
+    struct TestSettings;
+
+    #[async_trait::async_trait]
+    impl SeqSettings for TestSettings {
+        async fn get_conn_string(&self) -> String {
+            "url=http://localhost:5341".to_string()
+        }
+    }
+
+    #[derive(Default)]
+    struct MockUploader {
+        chunks: Mutex<Vec<String>>,
+    }
+
+    #[async_trait::async_trait]
+    impl LogsChunkUploader for MockUploader {
+        async fn upload_chunk(&self, chunk: Vec<u8>) {
+            self.chunks.lock().push(String::from_utf8(chunk).unwrap());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_pipeline_with_mock_uploader() {
+        let logger = Arc::new(MyLogger::new());
+        let uploader = Arc::new(MockUploader::default());
+
+        let seq_logger = SeqLoggerBuilder::new(Arc::new(TestSettings))
+            .target(logger.clone())
+            .panic_hook(false)
+            .uploader(uploader.clone())
+            .filter(|log_event| log_event.process != "Ignored")
+            .build()
+            .await;
+
+        logger.write_info("Orders", "Created", LogEventCtx::new());
+        logger.write_info("Ignored", "Skipped", LogEventCtx::new());
+        seq_logger.flush(Duration::from_secs(5));
+
+        let metrics = seq_logger.get_metrics();
+        assert_eq!(1, metrics.uploaded_events);
+        assert_eq!(1, metrics.filtered_events);
+        assert_eq!(0, metrics.queued_events);
+
+        let chunks = uploader.chunks.lock().clone();
+        assert_eq!(1, chunks.len());
+        assert!(chunks[0].contains("Created"));
+
+        seq_logger.unplug();
+        logger.write_info("Orders", "After unplug", LogEventCtx::new());
+        assert_eq!(0, seq_logger.get_metrics().queued_events);
+    }
+}
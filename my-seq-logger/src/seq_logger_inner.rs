@@ -1,14 +1,25 @@
-use std::sync::Arc;
+use std::sync::{
+    atomic::{AtomicU64, Ordering},
+    Arc,
+};
 
+use my_logger_core::MyLogger;
 use parking_lot::Mutex;
 use rust_extensions::events_loop::EventsLoopTick;
 
-use crate::{FlUrlUploader, LogEventsQueue, SeqLoggerSettings, SeqSettings};
+use crate::{FlUrlUploader, LogEventsQueue, LogsChunkUploader, SeqLoggerSettings, SeqSettings};
 
 pub struct SeqLoggerInner {
     pub(crate) log_events: LogEventsQueue,
     settings: Arc<dyn SeqSettings + Send + Sync + 'static>,
+    /// Logger whose populated params are sent with events.
+    pub(crate) logger: Arc<MyLogger>,
+    /// Replaces the HTTP uploader built from settings.
+    pub(crate) custom_uploader: Option<Arc<dyn LogsChunkUploader + Send + Sync + 'static>>,
     cached_uploader: Mutex<Option<Arc<FlUrlUploader>>>,
+    /// Held while a batch is uploaded, so a flush waits for the batch which is already in flight.
+    upload_lock: tokio::sync::Mutex<()>,
+    pub(crate) uploaded_events: AtomicU64,
 }
 
 impl SeqLoggerInner {
@@ -16,7 +27,11 @@ impl SeqLoggerInner {
         Self {
             log_events: LogEventsQueue::new(),
             settings,
+            logger: my_logger_core::LOGGER.clone(),
+            custom_uploader: None,
             cached_uploader: Mutex::new(None),
+            upload_lock: tokio::sync::Mutex::new(()),
+            uploaded_events: AtomicU64::new(0),
         }
     }
 
@@ -24,7 +39,11 @@ impl SeqLoggerInner {
         self.log_events.configure_size(queue_size);
     }
 
-    async fn get_uploader(&self) -> Arc<FlUrlUploader> {
+    async fn get_uploader(&self) -> Arc<dyn LogsChunkUploader + Send + Sync + 'static> {
+        if let Some(custom_uploader) = self.custom_uploader.as_ref() {
+            return custom_uploader.clone();
+        }
+
         let settings = SeqLoggerSettings::read(&self.settings).await;
 
         let mut cached = self.cached_uploader.lock();
@@ -42,21 +61,18 @@ impl SeqLoggerInner {
         *cached = Some(uploader.clone());
         uploader
     }
-}
 
-#[async_trait::async_trait]
-impl EventsLoopTick<()> for SeqLoggerInner {
-    async fn started(&self) {
-        println!("Seq Logs writer is started");
-    }
-    async fn tick(&self, _: ()) {
+    pub async fn upload_queued_events(&self) {
+        let _upload_lock = self.upload_lock.lock().await;
+
         let events = match self.log_events.dequeue() {
             Some(events) => events,
             None => return,
         };
 
         let uploader = self.get_uploader().await;
-        let populated_params = my_logger_core::LOGGER.get_populated_params();
+        let populated_params = self.logger.get_populated_params();
+        let events_amount = events.len() as u64;
 
         crate::upload_logs_chunk::upload_log_events_chunk(
             uploader.as_ref(),
@@ -64,6 +80,19 @@ impl EventsLoopTick<()> for SeqLoggerInner {
             events,
         )
         .await;
+
+        self.uploaded_events
+            .fetch_add(events_amount, Ordering::Relaxed);
+    }
+}
+
+#[async_trait::async_trait]
+impl EventsLoopTick<()> for SeqLoggerInner {
+    async fn started(&self) {
+        println!("Seq Logs writer is started");
+    }
+    async fn tick(&self, _: ()) {
+        self.upload_queued_events().await;
     }
     async fn finished(&self) {}
 }
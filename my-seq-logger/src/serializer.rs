@@ -37,6 +37,7 @@ pub fn serialize(
     let mut json_writer = JsonObjectWriter::from_string(compile_buffer);
 
     let level_as_str = match &log_event.level {
+        my_logger_core::LogLevel::Trace => "Verbose",
         my_logger_core::LogLevel::Info => "Info",
         my_logger_core::LogLevel::Warning => "Warning",
         my_logger_core::LogLevel::Error => "Error",
@@ -59,6 +60,39 @@ pub fn serialize(
             crate::seq_utils::format_seq_string(log_event.message.as_str()).as_str(),
         );
 
+    if let Some(trace_context) = &log_event.trace_context {
+        json_writer = json_writer
+            .write("@tr", trace_context.trace_id.as_str())
+            .write("@sp", trace_context.span_id.as_str());
+
+        if let Some(parent_span_id) = &trace_context.parent_span_id {
+            json_writer = json_writer.write("@ps", parent_span_id.as_str());
+        }
+    }
+
+    if let Some(span) = &log_event.span {
+        let started = span.started.to_rfc3339();
+        let started = started.get(..26).unwrap_or(started.as_str());
+
+        json_writer = json_writer
+            .write("@st", started)
+            .write("@sk", span.kind.as_str());
+    }
+
+    if let Some(module_path) = log_event.module_path {
+        json_writer = json_writer.write(
+            "SourceContext",
+            crate::seq_utils::format_seq_string(module_path).as_str(),
+        );
+    }
+
+    if let Some(source_location) = log_event.get_source_location() {
+        json_writer = json_writer.write(
+            "SourceLocation",
+            crate::seq_utils::format_seq_string(source_location.as_str()).as_str(),
+        );
+    }
+
     for (key, value) in populated_params.iter() {
         if key == LOCATION_KEY {
             json_writer =
@@ -86,3 +120,100 @@ pub fn serialize(
 
     json_writer.build()
 }
+
+#[cfg(test)]
+mod tests {
+    use my_logger_core::{
+        test_utils::create_test_event, LogLevel, MyLogEvent, PopulatedParams, SpanInfo, SpanKind,
+        SpanStatus, TraceContext,
+    };
+    use rust_extensions::date_time::DateTimeAsMicroseconds;
+
+    use super::serialize;
+
+    const TRACEPARENT: &str = "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01";
+
+    fn serialize_to_json(log_event: &MyLogEvent) -> serde_json::Value {
+        let payload = serialize(String::new(), log_event, &PopulatedParams::new_empty());
+        serde_json::from_str(&payload).unwrap()
+    }
+
+    #[test]
+    fn test_trace_context_fields() {
+        let remote = TraceContext::parse_traceparent(TRACEPARENT).unwrap();
+        let local = remote.child();
+
+        let mut log_event = create_test_event(LogLevel::Info, "Orders", "Created");
+        log_event.trace_context = Some(local.clone());
+
+        let json = serialize_to_json(&log_event);
+        assert_eq!("4bf92f3577b34da6a3ce929d0e0e4736", json["@tr"]);
+        assert_eq!(local.span_id.as_str(), json["@sp"]);
+        assert_eq!("00f067aa0ba902b7", json["@ps"]);
+    }
+
+    #[test]
+    fn test_parent_span_is_absent_for_root_context() {
+        let mut log_event = create_test_event(LogLevel::Info, "Orders", "Created");
+        log_event.trace_context = TraceContext::parse_traceparent(TRACEPARENT);
+
+        let json = serialize_to_json(&log_event);
+        assert_eq!("00f067aa0ba902b7", json["@sp"]);
+        assert!(json.get("@ps").is_none());
+    }
+
+    #[test]
+    fn test_trace_fields_are_absent_without_trace_context() {
+        let log_event = create_test_event(LogLevel::Info, "Orders", "Created");
+
+        let json = serialize_to_json(&log_event);
+        assert_eq!("Created", json["@m"]);
+        assert!(json.get("@tr").is_none());
+        assert!(json.get("@sp").is_none());
+        assert!(json.get("@ps").is_none());
+    }
+
+    #[test]
+    fn test_span_fields() {
+        let mut log_event = create_test_event(LogLevel::Info, "Orders", "GET /orders");
+        log_event.span = Some(SpanInfo {
+            started: DateTimeAsMicroseconds::new(1_700_000_000_123_456),
+            kind: SpanKind::Server,
+            status: SpanStatus::Ok,
+        });
+
+        let json = serialize_to_json(&log_event);
+        assert_eq!("2023-11-14T22:13:20.123456", json["@st"]);
+        assert_eq!("Server", json["@sk"]);
+    }
+
+    #[test]
+    fn test_span_fields_are_absent_without_span() {
+        let log_event = create_test_event(LogLevel::Info, "Orders", "Created");
+
+        let json = serialize_to_json(&log_event);
+        assert!(json.get("@st").is_none());
+        assert!(json.get("@sk").is_none());
+    }
+
+    #[test]
+    fn test_source_fields() {
+        let mut log_event = create_test_event(LogLevel::Info, "Orders", "Created");
+        log_event.file = Some("src/orders/create.rs");
+        log_event.line = Some(42);
+        log_event.module_path = Some("oms::orders::create");
+
+        let json = serialize_to_json(&log_event);
+        assert_eq!("oms::orders::create", json["SourceContext"]);
+        assert_eq!("src/orders/create.rs:42", json["SourceLocation"]);
+    }
+
+    #[test]
+    fn test_source_fields_are_absent_without_location() {
+        let log_event = create_test_event(LogLevel::Info, "Orders", "Created");
+
+        let json = serialize_to_json(&log_event);
+        assert!(json.get("SourceContext").is_none());
+        assert!(json.get("SourceLocation").is_none());
+    }
+}
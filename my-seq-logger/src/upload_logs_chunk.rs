@@ -9,17 +9,40 @@ const INITIAL_CHUNK_CAPACITY: usize = 64 * 1024;
 const INITIAL_PAYLOAD_CAPACITY: usize = 1024;
 
 pub async fn upload_log_events_chunk(
-    uploader: &impl LogsChunkUploader,
+    uploader: &(impl LogsChunkUploader + ?Sized),
     populated_params: PopulatedParams,
     data: Vec<Arc<MyLogEvent>>,
+) {
+    upload_serialized_log_events(
+        uploader,
+        data.as_slice(),
+        b"\r\n",
+        false,
+        |payload, log_event| super::serialize(payload, log_event, &populated_params),
+    )
+    .await;
+}
+
+/// Serializes events one by one and uploads them in chunks of up to 2Mb joined by `separator`.
+/// With `terminate_chunk` each chunk ends with the separator as well (NDJSON style).
+pub async fn upload_serialized_log_events(
+    uploader: &(impl LogsChunkUploader + ?Sized),
+    data: &[Arc<MyLogEvent>],
+    separator: &[u8],
+    terminate_chunk: bool,
+    mut serialize: impl FnMut(String, &MyLogEvent) -> String,
 ) {
     let mut chunk_to_upload: Vec<u8> = Vec::with_capacity(INITIAL_CHUNK_CAPACITY);
     let mut payload = String::with_capacity(INITIAL_PAYLOAD_CAPACITY);
 
     for log_event in data.iter() {
-        payload = super::serialize(payload, log_event, &populated_params);
+        payload = serialize(payload, log_event);
 
-        let separator_len = if chunk_to_upload.is_empty() { 0 } else { 2 };
+        let separator_len = if chunk_to_upload.is_empty() && !terminate_chunk {
+            0
+        } else {
+            separator.len()
+        };
         let projected = chunk_to_upload.len() + separator_len + payload.len();
 
         if projected > MAX_CHUNK_SIZE && !chunk_to_upload.is_empty() {
@@ -28,10 +51,14 @@ pub async fn upload_log_events_chunk(
             uploader.upload_chunk(to_send).await;
         }
 
-        if !chunk_to_upload.is_empty() {
-            chunk_to_upload.extend_from_slice(b"\r\n");
+        if !chunk_to_upload.is_empty() && !terminate_chunk {
+            chunk_to_upload.extend_from_slice(separator);
         }
         chunk_to_upload.extend_from_slice(payload.as_bytes());
+
+        if terminate_chunk {
+            chunk_to_upload.extend_from_slice(separator);
+        }
     }
 
     if !chunk_to_upload.is_empty() {
@@ -43,8 +70,7 @@ pub async fn upload_log_events_chunk(
 mod tests {
     use std::sync::Arc;
 
-    use my_logger_core::{MyLogEvent, PopulatedParams};
-    use rust_extensions::date_time::DateTimeAsMicroseconds;
+    use my_logger_core::{test_utils::create_test_event, LogLevel, PopulatedParams};
 
     use crate::LogsChunkUploader;
 
@@ -60,20 +86,8 @@ mod tests {
     #[tokio::test]
     async fn test_chunk_compiler() {
         let events = vec![
-            Arc::new(MyLogEvent {
-                dt: DateTimeAsMicroseconds::now(),
-                level: my_logger_core::LogLevel::Info,
-                process: "Test".to_string(),
-                message: "Message".to_string(),
-                context: None,
-            }),
-            Arc::new(MyLogEvent {
-                dt: DateTimeAsMicroseconds::now(),
-                level: my_logger_core::LogLevel::Info,
-                process: "Test".to_string(),
-                message: "Message2".to_string(),
-                context: None,
-            }),
+            Arc::new(create_test_event(LogLevel::Info, "Test", "Message")),
+            Arc::new(create_test_event(LogLevel::Info, "Test", "Message2")),
         ];
 
         let mock_uploader = MockUploader;
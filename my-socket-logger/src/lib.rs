@@ -0,0 +1,12 @@
+mod socket_logger;
+pub use socket_logger::SocketLogger;
+mod socket_logger_inner;
+pub use socket_logger_inner::*;
+mod settings;
+pub use settings::*;
+mod serializer;
+pub use serializer::*;
+mod reconnect_backoff;
+pub use reconnect_backoff::*;
+mod transport;
+pub use transport::*;
@@ -0,0 +1,65 @@
+use std::time::{Duration, Instant};
+
+/// Exponential reconnect delay: `min`, `2 * min`, `4 * min` ... up to `max`.
+pub struct ReconnectBackoff {
+    min_delay: Duration,
+    max_delay: Duration,
+    delay: Duration,
+    next_attempt: Option<Instant>,
+}
+
+impl ReconnectBackoff {
+    pub fn new(min_delay: Duration, max_delay: Duration) -> Self {
+        Self {
+            min_delay,
+            max_delay,
+            delay: min_delay,
+            next_attempt: None,
+        }
+    }
+
+    pub fn can_connect(&self, now: Instant) -> bool {
+        match self.next_attempt {
+            Some(next_attempt) => now >= next_attempt,
+            None => true,
+        }
+    }
+
+    pub fn connection_failed(&mut self, now: Instant) -> Duration {
+        let delay = self.delay;
+        self.next_attempt = Some(now + delay);
+        self.delay = (delay * 2).min(self.max_delay);
+        delay
+    }
+
+    pub fn reset(&mut self) {
+        self.delay = self.min_delay;
+        self.next_attempt = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use super::ReconnectBackoff;
+
+    #[test]
+    fn test_delay_doubles_up_to_max() {
+        let mut backoff = ReconnectBackoff::new(Duration::from_secs(1), Duration::from_secs(5));
+        let now = Instant::now();
+
+        assert!(backoff.can_connect(now));
+        assert_eq!(1, backoff.connection_failed(now).as_secs());
+        assert!(!backoff.can_connect(now));
+        assert!(backoff.can_connect(now + Duration::from_secs(1)));
+
+        assert_eq!(2, backoff.connection_failed(now).as_secs());
+        assert_eq!(4, backoff.connection_failed(now).as_secs());
+        assert_eq!(5, backoff.connection_failed(now).as_secs());
+
+        backoff.reset();
+        assert!(backoff.can_connect(now));
+        assert_eq!(1, backoff.connection_failed(now).as_secs());
+    }
+}
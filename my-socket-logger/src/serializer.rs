@@ -0,0 +1,41 @@
+use my_json::json_writer::{JsonObjectWriter, RawJsonObject};
+use my_logger_core::{MyLogEvent, PopulatedParams};
+use my_seq_logger::{format_seq_string, format_value};
+
+/// One event per line: `{"timestamp":..,"level":..,"process":..,"message":..,<populated params>,"context":{..}}`.
+/// Field names are lowercase so Vector, Fluent Bit and Logstash pick them up without remapping.
+pub fn serialize_json_line(
+    mut compile_buffer: String,
+    log_event: &MyLogEvent,
+    populated_params: &PopulatedParams,
+) -> String {
+    compile_buffer.clear();
+    let mut json_writer = JsonObjectWriter::from_string(compile_buffer)
+        .write("timestamp", log_event.dt.to_rfc3339())
+        .write("level", log_event.level.as_str())
+        .write(
+            "process",
+            format_seq_string(log_event.process.as_str()).as_str(),
+        )
+        .write(
+            "message",
+            format_seq_string(log_event.message.as_str()).as_str(),
+        );
+
+    for (key, value) in populated_params.iter() {
+        json_writer = json_writer.write(key, format_seq_string(value).as_str());
+    }
+
+    if let Some(ctx) = &log_event.context {
+        let mut ctx_writer = JsonObjectWriter::from_string(String::new());
+        for (key, value) in ctx {
+            ctx_writer = ctx_writer.write(key, format_value(value).as_str());
+        }
+        let ctx = ctx_writer.build();
+        json_writer = json_writer.write("context", RawJsonObject::AsStr(ctx.as_str()));
+    }
+
+    let mut result = json_writer.build();
+    result.push('\n');
+    result
+}
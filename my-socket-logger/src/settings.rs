@@ -0,0 +1,151 @@
+use std::time::Duration;
+
+use my_seq_logger::{parse_number, split_key_value};
+
+const DEFAULT_QUEUE_SIZE: usize = 10_000;
+const DEFAULT_FLUSH_DELAY: u64 = 1;
+const DEFAULT_TIMEOUT: u64 = 10;
+const DEFAULT_MIN_RECONNECT_DELAY: u64 = 1;
+const DEFAULT_MAX_RECONNECT_DELAY: u64 = 30;
+
+#[derive(Debug, Clone)]
+pub enum SocketTransport {
+    Tcp(String),
+    Unix(String),
+}
+
+pub struct SocketLoggerSettings {
+    pub transport: SocketTransport,
+    /// Applied to TCP only. Requires feature **tls**.
+    pub tls: bool,
+    /// Name the server certificate is validated against. Host of the url is used if not set.
+    pub tls_server_name: Option<String>,
+    /// Max events kept while the endpoint is unreachable. The oldest events are dropped first.
+    pub queue_size: usize,
+    /// Interval at which queued events are sent and a lost connection is retried.
+    pub flush_delay: Duration,
+    pub timeout: Duration,
+    pub min_reconnect_delay: Duration,
+    pub max_reconnect_delay: Duration,
+}
+
+impl SocketLoggerSettings {
+    pub fn new(transport: SocketTransport) -> Self {
+        Self {
+            transport,
+            tls: false,
+            tls_server_name: None,
+            queue_size: DEFAULT_QUEUE_SIZE,
+            flush_delay: Duration::from_secs(DEFAULT_FLUSH_DELAY),
+            timeout: Duration::from_secs(DEFAULT_TIMEOUT),
+            min_reconnect_delay: Duration::from_secs(DEFAULT_MIN_RECONNECT_DELAY),
+            max_reconnect_delay: Duration::from_secs(DEFAULT_MAX_RECONNECT_DELAY),
+        }
+    }
+
+    /// `url=tcp://vector:9000;tls=true;queuesize=10000;maxreconnectdelay=30` or `url=unix:///var/run/vector.sock`
+    pub fn try_parse(conn_string: &str) -> Result<Self, String> {
+        let mut transport = None;
+        let mut tls = false;
+        let mut tls_server_name = None;
+        let mut queue_size = DEFAULT_QUEUE_SIZE;
+        let mut flush_delay = DEFAULT_FLUSH_DELAY;
+        let mut timeout = DEFAULT_TIMEOUT;
+        let mut min_reconnect_delay = DEFAULT_MIN_RECONNECT_DELAY;
+        let mut max_reconnect_delay = DEFAULT_MAX_RECONNECT_DELAY;
+
+        for item in conn_string.split(';') {
+            if item.is_empty() {
+                continue;
+            }
+
+            let (key, value) = split_key_value(item, "socket connection string")?;
+
+            match key {
+                "url" => {
+                    transport = if let Some(addr) = value.strip_prefix("tcp://") {
+                        Some(SocketTransport::Tcp(addr.to_string()))
+                    } else if let Some(path) = value.strip_prefix("unix://") {
+                        Some(SocketTransport::Unix(path.to_string()))
+                    } else {
+                        return Err(format!("Invalid socket url {}", value));
+                    };
+                }
+                "tls" => {
+                    tls = match value {
+                        "true" => true,
+                        "false" => false,
+                        _ => return Err(format!("Invalid tls value {}", value)),
+                    };
+                }
+                "servername" => {
+                    tls_server_name = Some(value.to_string());
+                }
+                "queuesize" => {
+                    queue_size = parse_number(key, value)?;
+                }
+                "flushdelay" => {
+                    flush_delay = parse_number(key, value)?;
+                }
+                "timeout" => {
+                    timeout = parse_number(key, value)?;
+                }
+                "minreconnectdelay" => {
+                    min_reconnect_delay = parse_number(key, value)?;
+                }
+                "maxreconnectdelay" => {
+                    max_reconnect_delay = parse_number(key, value)?;
+                }
+                _ => {
+                    return Err(format!("Invalid key {} of socket connection string", key));
+                }
+            }
+        }
+
+        let transport = match transport {
+            Some(transport) => transport,
+            None => return Err("There is no URL parameter in socket connection string".to_string()),
+        };
+
+        if tls {
+            if let SocketTransport::Unix(_) = &transport {
+                return Err("TLS is not supported over unix socket".to_string());
+            }
+
+            if !cfg!(feature = "tls") {
+                return Err("TLS requires feature tls of my-socket-logger".to_string());
+            }
+        }
+
+        Ok(Self {
+            transport,
+            tls,
+            tls_server_name,
+            queue_size,
+            flush_delay: Duration::from_secs(flush_delay),
+            timeout: Duration::from_secs(timeout),
+            min_reconnect_delay: Duration::from_secs(min_reconnect_delay),
+            max_reconnect_delay: Duration::from_secs(max_reconnect_delay),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{SocketLoggerSettings, SocketTransport};
+
+    #[test]
+    fn test_parse_connection_string() {
+        let settings = SocketLoggerSettings::try_parse(
+            "url=tcp://vector:9000;queuesize=100;maxreconnectdelay=5",
+        )
+        .unwrap();
+
+        assert!(matches!(settings.transport, SocketTransport::Tcp(addr) if addr == "vector:9000"));
+        assert_eq!(100, settings.queue_size);
+        assert_eq!(5, settings.max_reconnect_delay.as_secs());
+        assert!(!settings.tls);
+
+        assert!(SocketLoggerSettings::try_parse("url=unix:///tmp/a.sock;tls=true").is_err());
+    }
+}
@@ -0,0 +1,105 @@
+use std::{sync::Arc, time::Duration};
+
+use crate::{SocketLoggerInner, SocketLoggerSettings};
+use my_logger_core::{MyLogEvent, MyLoggerReader};
+use rust_extensions::{events_loop::EventsLoop, AppStates};
+
+pub struct SocketLogger {
+    inner: Arc<SocketLoggerInner>,
+    events_loop: EventsLoop<()>,
+    app_states: Arc<AppStates>,
+}
+
+impl SocketLogger {
+    /// Plugs the socket writer into `LOGGER`. Must be called inside a tokio runtime.
+    pub fn enable(settings: SocketLoggerSettings) -> Arc<Self> {
+        let flush_delay = settings.flush_delay;
+        let timeout = settings.timeout;
+
+        let result = Self {
+            app_states: AppStates::create_initialized().into(),
+            inner: Arc::new(SocketLoggerInner::new(settings)),
+            events_loop: EventsLoop::new("SocketLogger".to_string()).set_iteration_timeout(timeout),
+        };
+
+        result.events_loop.register_event_loop(result.inner.clone());
+
+        result
+            .events_loop
+            .start(result.app_states.clone(), my_logger_core::LOGGER.clone());
+
+        let socket_logger = Arc::new(result);
+        my_seq_logger::start_flush_timer(Arc::downgrade(&socket_logger), flush_delay, |logger| {
+            logger.events_loop.send(())
+        });
+        my_logger_core::LOGGER.plug_reader(socket_logger.clone());
+        socket_logger
+    }
+
+    pub fn enable_from_connection_string(conn_string: &str) -> Arc<Self> {
+        match SocketLoggerSettings::try_parse(conn_string) {
+            Ok(settings) => Self::enable(settings),
+            Err(err) => panic!("Can not parse socket logger settings. Err: {}", err),
+        }
+    }
+}
+
+impl MyLoggerReader for SocketLogger {
+    fn write_log(&self, log_event: Arc<MyLogEvent>) {
+        self.inner.log_events.enqueue(log_event);
+    }
+
+    fn flush(&self, timeout: Duration) {
+        let inner = self.inner.clone();
+        my_seq_logger::flush_on_private_runtime("Socket", timeout, async move {
+            inner.send_queued_events().await
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        io::{BufRead, BufReader},
+        net::TcpListener,
+        sync::Arc,
+        time::Duration,
+    };
+
+    use my_logger_core::{test_utils::create_test_event, LogLevel, MyLoggerReader};
+    use rust_extensions::{events_loop::EventsLoop, AppStates};
+
+    use crate::{SocketLoggerInner, SocketLoggerSettings};
+
+    use super::SocketLogger;
+
+    #[test]
+    fn test_flush_sends_queued_events() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let conn_string = format!("url=tcp://{}", listener.local_addr().unwrap());
+
+        let server = std::thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut line = String::new();
+            BufReader::new(stream).read_line(&mut line).unwrap();
+            line
+        });
+
+        let socket_logger = SocketLogger {
+            inner: Arc::new(SocketLoggerInner::new(
+                SocketLoggerSettings::try_parse(conn_string.as_str()).unwrap(),
+            )),
+            events_loop: EventsLoop::new("SocketLogger".to_string()),
+            app_states: AppStates::create_initialized().into(),
+        };
+
+        socket_logger.write_log(Arc::new(create_test_event(
+            LogLevel::Info,
+            "Orders",
+            "Created",
+        )));
+        socket_logger.flush(Duration::from_secs(5));
+
+        assert!(server.join().unwrap().contains("Created"));
+    }
+}
@@ -0,0 +1,127 @@
+use std::time::Instant;
+
+use my_seq_logger::{LogEventsQueue, PendingEvents};
+use rust_extensions::events_loop::EventsLoopTick;
+use tokio::sync::Mutex;
+
+use crate::{ReconnectBackoff, SocketConnection, SocketLoggerSettings};
+
+const INITIAL_PAYLOAD_CAPACITY: usize = 1024;
+
+struct SocketWriterState {
+    connection: Option<SocketConnection>,
+    /// Events which are not delivered yet. Kept while the endpoint is unreachable.
+    pending: PendingEvents,
+    reconnect_backoff: ReconnectBackoff,
+}
+
+pub struct SocketLoggerInner {
+    pub(crate) log_events: LogEventsQueue,
+    settings: SocketLoggerSettings,
+    state: Mutex<SocketWriterState>,
+}
+
+impl SocketLoggerInner {
+    pub fn new(settings: SocketLoggerSettings) -> Self {
+        let mut log_events = LogEventsQueue::new();
+        log_events.configure_size(settings.queue_size);
+
+        let reconnect_backoff =
+            ReconnectBackoff::new(settings.min_reconnect_delay, settings.max_reconnect_delay);
+
+        let pending = PendingEvents::new(settings.queue_size);
+
+        Self {
+            log_events,
+            settings,
+            state: Mutex::new(SocketWriterState {
+                connection: None,
+                pending,
+                reconnect_backoff,
+            }),
+        }
+    }
+
+    /// Sends the queued events and the ones left from the previous attempts.
+    pub async fn send_queued_events(&self) {
+        let mut state = self.state.lock().await;
+
+        if let Some(events) = self.log_events.dequeue() {
+            state.pending.add(events);
+        }
+
+        if state.pending.is_empty() {
+            return;
+        }
+
+        if state.connection.is_none() {
+            let now = Instant::now();
+            if !state.reconnect_backoff.can_connect(now) {
+                return;
+            }
+
+            match SocketConnection::connect(&self.settings).await {
+                Ok(connection) => {
+                    state.connection = Some(connection);
+                    state.reconnect_backoff.reset();
+                }
+                Err(err) => {
+                    let delay = state.reconnect_backoff.connection_failed(now);
+                    eprintln!(
+                        "Can not connect to {:?}. Next attempt in {:?}. Err: {:?}",
+                        self.settings.transport, delay, err
+                    );
+                    return;
+                }
+            }
+        }
+
+        state
+            .pending
+            .report_dropped(format_args!("{:?}", self.settings.transport));
+
+        let populated_params = my_logger_core::LOGGER.get_populated_params();
+        let mut payload = String::with_capacity(INITIAL_PAYLOAD_CAPACITY);
+        let state = &mut *state;
+
+        while let Some(log_event) = state.pending.front() {
+            payload = crate::serialize_json_line(payload, log_event, &populated_params);
+
+            if let Err(err) = state
+                .connection
+                .as_mut()
+                .unwrap()
+                .send(payload.as_bytes())
+                .await
+            {
+                eprintln!(
+                    "Error while sending logs to {:?}. Err: {:?}",
+                    self.settings.transport, err
+                );
+                state.connection = None;
+                return;
+            }
+
+            state.pending.pop_front();
+        }
+
+        if let Err(err) = state.connection.as_mut().unwrap().flush().await {
+            eprintln!(
+                "Error while sending logs to {:?}. Err: {:?}",
+                self.settings.transport, err
+            );
+            state.connection = None;
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl EventsLoopTick<()> for SocketLoggerInner {
+    async fn started(&self) {
+        println!("Socket writer is started");
+    }
+    async fn tick(&self, _: ()) {
+        self.send_queued_events().await;
+    }
+    async fn finished(&self) {}
+}
@@ -0,0 +1,129 @@
+use tokio::{
+    io::AsyncWriteExt,
+    net::{TcpStream, UnixStream},
+};
+
+use crate::{SocketLoggerSettings, SocketTransport};
+
+pub enum SocketConnection {
+    Tcp(TcpStream),
+    Unix(UnixStream),
+    #[cfg(feature = "tls")]
+    Tls(Box<tokio_rustls::client::TlsStream<TcpStream>>),
+}
+
+impl SocketConnection {
+    pub async fn connect(settings: &SocketLoggerSettings) -> std::io::Result<Self> {
+        let future = async {
+            match &settings.transport {
+                SocketTransport::Tcp(addr) => {
+                    let stream = TcpStream::connect(addr.as_str()).await?;
+
+                    #[cfg(feature = "tls")]
+                    if settings.tls {
+                        let server_name = match &settings.tls_server_name {
+                            Some(server_name) => server_name.as_str(),
+                            None => get_host(addr),
+                        };
+                        return connect_tls(stream, server_name).await;
+                    }
+
+                    Ok(Self::Tcp(stream))
+                }
+                SocketTransport::Unix(path) => {
+                    let stream = UnixStream::connect(path.as_str()).await?;
+                    Ok(Self::Unix(stream))
+                }
+            }
+        };
+
+        match tokio::time::timeout(settings.timeout, future).await {
+            Ok(result) => result,
+            Err(_) => Err(std::io::ErrorKind::TimedOut.into()),
+        }
+    }
+
+    pub async fn send(&mut self, payload: &[u8]) -> std::io::Result<()> {
+        match self {
+            SocketConnection::Tcp(stream) => stream.write_all(payload).await,
+            SocketConnection::Unix(stream) => stream.write_all(payload).await,
+            #[cfg(feature = "tls")]
+            SocketConnection::Tls(stream) => stream.write_all(payload).await,
+        }
+    }
+
+    pub async fn flush(&mut self) -> std::io::Result<()> {
+        match self {
+            SocketConnection::Tcp(stream) => stream.flush().await,
+            SocketConnection::Unix(stream) => stream.flush().await,
+            #[cfg(feature = "tls")]
+            SocketConnection::Tls(stream) => stream.flush().await,
+        }
+    }
+}
+
+#[cfg(feature = "tls")]
+fn get_host(addr: &str) -> &str {
+    let host = match addr.rsplit_once(':') {
+        Some((host, _)) => host,
+        None => addr,
+    };
+
+    host.trim_start_matches('[').trim_end_matches(']')
+}
+
+#[cfg(feature = "tls")]
+async fn connect_tls(stream: TcpStream, server_name: &str) -> std::io::Result<SocketConnection> {
+    use std::sync::Arc;
+
+    use tokio_rustls::rustls;
+
+    let mut root_store = rustls::RootCertStore::empty();
+    root_store.extend(webpki_roots::TLS_SERVER_ROOTS.iter().cloned());
+
+    let config = rustls::ClientConfig::builder_with_provider(Arc::new(
+        rustls::crypto::ring::default_provider(),
+    ))
+    .with_safe_default_protocol_versions()
+    .map_err(std::io::Error::other)?
+    .with_root_certificates(root_store)
+    .with_no_client_auth();
+
+    let server_name = rustls::pki_types::ServerName::try_from(server_name.to_string())
+        .map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidInput, err))?;
+
+    let stream = tokio_rustls::TlsConnector::from(Arc::new(config))
+        .connect(server_name, stream)
+        .await?;
+
+    Ok(SocketConnection::Tls(Box::new(stream)))
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::{io::AsyncReadExt, net::TcpListener};
+
+    use crate::{SocketLoggerSettings, SocketTransport};
+
+    use super::SocketConnection;
+
+    #[tokio::test]
+    async fn test_tcp_lines_are_sent_as_is() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let settings = SocketLoggerSettings::new(SocketTransport::Tcp(
+            listener.local_addr().unwrap().to_string(),
+        ));
+
+        let mut connection = SocketConnection::connect(&settings).await.unwrap();
+        let (mut stream, _) = listener.accept().await.unwrap();
+
+        connection.send(b"{\"a\":1}\n").await.unwrap();
+        connection.send(b"{\"a\":2}\n").await.unwrap();
+        connection.flush().await.unwrap();
+        drop(connection);
+
+        let mut result = String::new();
+        stream.read_to_string(&mut result).await.unwrap();
+        assert_eq!("{\"a\":1}\n{\"a\":2}\n", result);
+    }
+}
@@ -0,0 +1,10 @@
+mod syslog_logger;
+pub use syslog_logger::SyslogLogger;
+mod syslog_logger_inner;
+pub use syslog_logger_inner::*;
+mod settings;
+pub use settings::*;
+mod syslog_format;
+pub use syslog_format::*;
+mod transport;
+pub use transport::*;
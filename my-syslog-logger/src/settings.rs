@@ -0,0 +1,192 @@
+use std::time::Duration;
+
+use my_seq_logger::split_key_value;
+
+const DEFAULT_FACILITY: u8 = 16;
+const DEFAULT_FLUSH_DELAY: u64 = 1;
+const DEFAULT_TIMEOUT: u64 = 10;
+
+#[derive(Debug, Clone)]
+pub enum SyslogTransport {
+    Udp(String),
+    Tcp(String),
+    Unix(String),
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum SyslogFormat {
+    Rfc5424,
+    Rfc3164,
+}
+
+pub struct SyslogSettings {
+    pub transport: SyslogTransport,
+    pub format: SyslogFormat,
+    pub facility: u8,
+    pub hostname: String,
+    pub queue_size: Option<usize>,
+    /// Interval at which queued events are sent.
+    pub flush_delay: Duration,
+    pub timeout: Duration,
+}
+
+impl SyslogSettings {
+    pub fn new(transport: SyslogTransport) -> Self {
+        Self {
+            transport,
+            format: SyslogFormat::Rfc5424,
+            facility: DEFAULT_FACILITY,
+            hostname: get_default_hostname(),
+            queue_size: None,
+            flush_delay: Duration::from_secs(DEFAULT_FLUSH_DELAY),
+            timeout: Duration::from_secs(DEFAULT_TIMEOUT),
+        }
+    }
+
+    /// `url=udp://127.0.0.1:514;format=rfc5424;facility=local0`.
+    /// Url schemes are udp://, tcp:// and unix:// (e.g. unix:///dev/log).
+    pub fn try_parse(conn_string: &str) -> Result<Self, String> {
+        let mut transport = None;
+        let mut format = SyslogFormat::Rfc5424;
+        let mut facility = DEFAULT_FACILITY;
+        let mut hostname = None;
+        let mut queue_size = None;
+        let mut flush_delay = DEFAULT_FLUSH_DELAY;
+        let mut timeout = DEFAULT_TIMEOUT;
+
+        for item in conn_string.split(';') {
+            if item.is_empty() {
+                continue;
+            }
+
+            let (key, value) = split_key_value(item, "syslog connection string")?;
+
+            match key {
+                "url" => {
+                    transport = Some(parse_url(value)?);
+                }
+                "format" => {
+                    format = match value {
+                        "rfc5424" => SyslogFormat::Rfc5424,
+                        "rfc3164" => SyslogFormat::Rfc3164,
+                        _ => return Err(format!("Invalid syslog format {}", value)),
+                    };
+                }
+                "facility" => {
+                    facility = parse_facility(value)?;
+                }
+                "hostname" => {
+                    hostname = Some(value.to_string());
+                }
+                "queuesize" => {
+                    queue_size = Some(
+                        value
+                            .parse::<usize>()
+                            .map_err(|_| "QueueSize must be a number".to_string())?,
+                    );
+                }
+                "flushdelay" => {
+                    flush_delay = value
+                        .parse::<u64>()
+                        .map_err(|_| "FlushDelay must be a number".to_string())?;
+                }
+                "timeout" => {
+                    timeout = value
+                        .parse::<u64>()
+                        .map_err(|_| "Timeout must be a number".to_string())?;
+                }
+                _ => {
+                    return Err(format!("Invalid key {} of syslog connection string", key));
+                }
+            }
+        }
+
+        let transport = match transport {
+            Some(transport) => transport,
+            None => return Err("There is no URL parameter in syslog connection string".to_string()),
+        };
+
+        Ok(Self {
+            transport,
+            format,
+            facility,
+            hostname: hostname.unwrap_or_else(get_default_hostname),
+            queue_size,
+            flush_delay: Duration::from_secs(flush_delay),
+            timeout: Duration::from_secs(timeout),
+        })
+    }
+}
+
+fn parse_url(src: &str) -> Result<SyslogTransport, String> {
+    if let Some(addr) = src.strip_prefix("udp://") {
+        return Ok(SyslogTransport::Udp(addr.to_string()));
+    }
+
+    if let Some(addr) = src.strip_prefix("tcp://") {
+        return Ok(SyslogTransport::Tcp(addr.to_string()));
+    }
+
+    if let Some(path) = src.strip_prefix("unix://") {
+        return Ok(SyslogTransport::Unix(path.to_string()));
+    }
+
+    Err(format!("Invalid syslog url {}", src))
+}
+
+fn parse_facility(src: &str) -> Result<u8, String> {
+    let result = match src {
+        "kern" => 0,
+        "user" => 1,
+        "daemon" => 3,
+        "auth" => 4,
+        "syslog" => 5,
+        "local0" => 16,
+        "local1" => 17,
+        "local2" => 18,
+        "local3" => 19,
+        "local4" => 20,
+        "local5" => 21,
+        "local6" => 22,
+        "local7" => 23,
+        _ => src
+            .parse::<u8>()
+            .map_err(|_| format!("Invalid syslog facility {}", src))?,
+    };
+
+    if result > 23 {
+        return Err(format!("Invalid syslog facility {}", src));
+    }
+
+    Ok(result)
+}
+
+fn get_default_hostname() -> String {
+    my_logger_core::get_hostname().unwrap_or_else(|| "-".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{SyslogFormat, SyslogSettings, SyslogTransport};
+
+    #[test]
+    fn test_try_parse() {
+        let result = SyslogSettings::try_parse(
+            "url=tcp://10.0.0.1:601;format=rfc3164;facility=local3;hostname=srv",
+        )
+        .unwrap();
+
+        assert!(
+            matches!(result.transport, SyslogTransport::Tcp(ref addr) if addr == "10.0.0.1:601")
+        );
+        assert!(matches!(result.format, SyslogFormat::Rfc3164));
+        assert_eq!(19, result.facility);
+        assert_eq!("srv", result.hostname);
+    }
+
+    #[test]
+    fn test_try_parse_unix_socket() {
+        let result = SyslogSettings::try_parse("url=unix:///dev/log").unwrap();
+        assert!(matches!(result.transport, SyslogTransport::Unix(ref path) if path == "/dev/log"));
+    }
+}
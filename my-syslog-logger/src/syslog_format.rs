@@ -0,0 +1,217 @@
+use std::fmt::Write;
+
+use my_logger_core::{LogLevel, MyLogEvent, PopulatedParams};
+
+use crate::SyslogFormat;
+
+const NIL_VALUE: &str = "-";
+const SD_ID: &str = "ctx@32473";
+const MAX_SD_NAME_LEN: usize = 32;
+const MAX_APP_NAME_LEN: usize = 48;
+const APPLICATION_KEY: &str = "Application";
+
+const MONTHS: [&str; 12] = [
+    "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+];
+
+pub fn get_syslog_severity(level: LogLevel) -> u8 {
+    match level {
+        LogLevel::FatalError => 2,
+        LogLevel::Error => 3,
+        LogLevel::Warning => 4,
+        LogLevel::Info => 6,
+        LogLevel::Debug => 7,
+        LogLevel::Trace => 7,
+    }
+}
+
+pub fn serialize_syslog_message(
+    format: SyslogFormat,
+    mut compile_buffer: String,
+    log_event: &MyLogEvent,
+    populated_params: &PopulatedParams,
+    facility: u8,
+    hostname: &str,
+) -> String {
+    compile_buffer.clear();
+
+    let priority = facility as u16 * 8 + get_syslog_severity(log_event.level) as u16;
+    let app_name = get_app_name(populated_params);
+
+    match format {
+        SyslogFormat::Rfc5424 => {
+            let timestamp = log_event.dt.to_rfc3339();
+            let timestamp = timestamp.get(..26).unwrap_or(timestamp.as_str());
+
+            let _ = write!(
+                &mut compile_buffer,
+                "<{}>1 {}Z {} {} {} {} ",
+                priority,
+                timestamp,
+                get_header_value(hostname, 255),
+                app_name,
+                std::process::id(),
+                NIL_VALUE
+            );
+
+            write_structured_data(&mut compile_buffer, log_event, populated_params);
+            compile_buffer.push(' ');
+            compile_buffer.push_str(log_event.message.as_str());
+        }
+        SyslogFormat::Rfc3164 => {
+            let _ = write!(
+                &mut compile_buffer,
+                "<{}>{} {} {}[{}]: [{}] {}",
+                priority,
+                get_rfc3164_timestamp(log_event),
+                get_header_value(hostname, 255),
+                app_name,
+                std::process::id(),
+                log_event.process,
+                log_event.message
+            );
+        }
+    }
+
+    compile_buffer
+}
+
+fn get_app_name(populated_params: &PopulatedParams) -> String {
+    match populated_params.get(APPLICATION_KEY) {
+        Some(app_name) => get_header_value(app_name, MAX_APP_NAME_LEN),
+        None => NIL_VALUE.to_string(),
+    }
+}
+
+/// Header fields are PRINTUSASCII without spaces.
+fn get_header_value(src: &str, max_len: usize) -> String {
+    let result: String = src
+        .chars()
+        .filter(|c| c.is_ascii_graphic())
+        .take(max_len)
+        .collect();
+
+    if result.is_empty() {
+        return NIL_VALUE.to_string();
+    }
+
+    result
+}
+
+/// SD-NAME is PRINTUSASCII except '=', SP, ']' and '"'.
+fn get_sd_name(src: &str) -> String {
+    src.chars()
+        .filter(|c| c.is_ascii_graphic() && !matches!(c, '=' | ']' | '"'))
+        .take(MAX_SD_NAME_LEN)
+        .collect()
+}
+
+fn write_sd_param(out: &mut String, key: &str, value: &str) {
+    let key = get_sd_name(key);
+    if key.is_empty() {
+        return;
+    }
+
+    out.push(' ');
+    out.push_str(key.as_str());
+    out.push_str("=\"");
+    for c in value.chars() {
+        if matches!(c, '"' | '\\' | ']') {
+            out.push('\\');
+        }
+        out.push(c);
+    }
+    out.push('"');
+}
+
+fn write_structured_data(
+    out: &mut String,
+    log_event: &MyLogEvent,
+    populated_params: &PopulatedParams,
+) {
+    out.push('[');
+    out.push_str(SD_ID);
+    write_sd_param(out, "Process", log_event.process.as_str());
+
+    for (key, value) in populated_params.iter() {
+        if key == APPLICATION_KEY {
+            continue;
+        }
+        write_sd_param(out, key, value);
+    }
+
+    if let Some(ctx) = &log_event.context {
+        for (key, value) in ctx {
+            write_sd_param(out, key, value);
+        }
+    }
+
+    out.push(']');
+}
+
+/// `Mmm dd hh:mm:ss` built from the rfc3339 representation of the event time.
+fn get_rfc3164_timestamp(log_event: &MyLogEvent) -> String {
+    let rfc3339 = log_event.dt.to_rfc3339();
+
+    let month = rfc3339
+        .get(5..7)
+        .and_then(|itm| itm.parse::<usize>().ok())
+        .unwrap_or(1);
+    let day = rfc3339
+        .get(8..10)
+        .and_then(|itm| itm.parse::<u8>().ok())
+        .unwrap_or(1);
+    let time = rfc3339.get(11..19).unwrap_or("00:00:00");
+
+    format!("{} {:>2} {}", MONTHS[(month + 11) % 12], day, time)
+}
+
+#[cfg(test)]
+mod tests {
+    use my_logger_core::{test_utils::create_test_event, LogLevel, MyLogEvent, PopulatedParams};
+    use rust_extensions::date_time::DateTimeAsMicroseconds;
+
+    use crate::SyslogFormat;
+
+    fn create_event() -> MyLogEvent {
+        let mut log_event = create_test_event(LogLevel::Error, "Db", "Timeout");
+        log_event.dt = DateTimeAsMicroseconds::new(1_700_000_000_000_000);
+        log_event.context = Some([("Host".to_string(), "db\"1]".to_string())].into());
+        log_event
+    }
+
+    #[test]
+    fn test_rfc5424() {
+        let populated_params = PopulatedParams::new(vec![("Application", "pricing".into())]);
+
+        let result = super::serialize_syslog_message(
+            SyslogFormat::Rfc5424,
+            String::new(),
+            &create_event(),
+            &populated_params,
+            16,
+            "srv",
+        );
+
+        let expected = format!(
+            "<131>1 2023-11-14T22:13:20.000000Z srv pricing {} - [ctx@32473 Process=\"Db\" Host=\"db\\\"1\\]\"] Timeout",
+            std::process::id()
+        );
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn test_rfc3164() {
+        let result = super::serialize_syslog_message(
+            SyslogFormat::Rfc3164,
+            String::new(),
+            &create_event(),
+            &PopulatedParams::new_empty(),
+            1,
+            "srv",
+        );
+
+        let expected = format!("<11>Nov 14 22:13:20 srv -[{}]: [Db] Timeout", std::process::id());
+        assert_eq!(expected, result);
+    }
+}
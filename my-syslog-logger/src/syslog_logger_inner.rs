@@ -0,0 +1,114 @@
+use my_seq_logger::{LogEventsQueue, PendingEvents, DEFAULT_PENDING_SIZE};
+use rust_extensions::events_loop::EventsLoopTick;
+use tokio::sync::Mutex;
+
+use crate::{SyslogConnection, SyslogSettings};
+
+const INITIAL_PAYLOAD_CAPACITY: usize = 1024;
+
+struct SyslogWriterState {
+    connection: Option<SyslogConnection>,
+    /// Events which are not delivered yet. Kept while the server is unreachable.
+    pending: PendingEvents,
+}
+
+pub struct SyslogLoggerInner {
+    pub(crate) log_events: LogEventsQueue,
+    settings: SyslogSettings,
+    state: Mutex<SyslogWriterState>,
+}
+
+impl SyslogLoggerInner {
+    pub fn new(settings: SyslogSettings) -> Self {
+        let mut log_events = LogEventsQueue::new();
+        if let Some(queue_size) = settings.queue_size {
+            log_events.configure_size(queue_size);
+        }
+
+        let pending = PendingEvents::new(settings.queue_size.unwrap_or(DEFAULT_PENDING_SIZE));
+
+        Self {
+            log_events,
+            settings,
+            state: Mutex::new(SyslogWriterState {
+                connection: None,
+                pending,
+            }),
+        }
+    }
+
+    /// Sends the queued events and the ones left from the previous attempts.
+    pub async fn send_queued_events(&self) {
+        let mut state = self.state.lock().await;
+
+        if let Some(events) = self.log_events.dequeue() {
+            state.pending.add(events);
+        }
+
+        if state.pending.is_empty() {
+            return;
+        }
+
+        if state.connection.is_none() {
+            match SyslogConnection::connect(&self.settings.transport, self.settings.timeout).await {
+                Ok(result) => state.connection = Some(result),
+                Err(err) => {
+                    eprintln!(
+                        "Can not connect to syslog {:?}. Err: {:?}",
+                        self.settings.transport, err
+                    );
+                    return;
+                }
+            }
+        }
+
+        state
+            .pending
+            .report_dropped(format_args!("syslog {:?}", self.settings.transport));
+
+        let populated_params = my_logger_core::LOGGER.get_populated_params();
+        let mut payload = String::with_capacity(INITIAL_PAYLOAD_CAPACITY);
+        let state = &mut *state;
+
+        while let Some(log_event) = state.pending.front() {
+            payload = crate::serialize_syslog_message(
+                self.settings.format,
+                payload,
+                log_event,
+                &populated_params,
+                self.settings.facility,
+                self.settings.hostname.as_str(),
+            );
+
+            if let Err(err) = state
+                .connection
+                .as_mut()
+                .unwrap()
+                .send(payload.as_bytes())
+                .await
+            {
+                eprintln!("Error while sending logs to syslog. Err: {:?}", err);
+                state.connection = None;
+                return;
+            }
+
+            state.pending.pop_front();
+        }
+
+        if let Err(err) = state.connection.as_mut().unwrap().flush().await {
+            eprintln!("Error while sending logs to syslog. Err: {:?}", err);
+            state.connection = None;
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl EventsLoopTick<()> for SyslogLoggerInner {
+    async fn started(&self) {
+        println!("Syslog writer is started");
+    }
+    async fn tick(&self, _: ()) {
+        self.send_queued_events().await;
+    }
+    async fn finished(&self) {}
+}
@@ -0,0 +1,119 @@
+use std::time::Duration;
+
+use tokio::{
+    io::AsyncWriteExt,
+    net::{TcpStream, UdpSocket, UnixDatagram},
+};
+
+use crate::SyslogTransport;
+
+pub enum SyslogConnection {
+    Udp(UdpSocket),
+    Tcp(TcpStream),
+    Unix(UnixDatagram),
+}
+
+impl SyslogConnection {
+    pub async fn connect(transport: &SyslogTransport, timeout: Duration) -> std::io::Result<Self> {
+        let future = async {
+            match transport {
+                SyslogTransport::Udp(addr) => {
+                    let socket = UdpSocket::bind("0.0.0.0:0").await?;
+                    socket.connect(addr.as_str()).await?;
+                    Ok(Self::Udp(socket))
+                }
+                SyslogTransport::Tcp(addr) => {
+                    let stream = TcpStream::connect(addr.as_str()).await?;
+                    Ok(Self::Tcp(stream))
+                }
+                SyslogTransport::Unix(path) => {
+                    let socket = UnixDatagram::unbound()?;
+                    socket.connect(path.as_str())?;
+                    Ok(Self::Unix(socket))
+                }
+            }
+        };
+
+        match tokio::time::timeout(timeout, future).await {
+            Ok(result) => result,
+            Err(_) => Err(std::io::ErrorKind::TimedOut.into()),
+        }
+    }
+
+    /// Datagram transports send one message per datagram,
+    /// TCP uses octet-counting framing (RFC 6587).
+    pub async fn send(&mut self, message: &[u8]) -> std::io::Result<()> {
+        match self {
+            SyslogConnection::Udp(socket) => {
+                socket.send(message).await?;
+            }
+            SyslogConnection::Unix(socket) => {
+                socket.send(message).await?;
+            }
+            SyslogConnection::Tcp(stream) => {
+                let header = format!("{} ", message.len());
+                stream.write_all(header.as_bytes()).await?;
+                stream.write_all(message).await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    pub async fn flush(&mut self) -> std::io::Result<()> {
+        if let SyslogConnection::Tcp(stream) = self {
+            stream.flush().await?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use tokio::{
+        io::AsyncReadExt,
+        net::{TcpListener, UdpSocket},
+    };
+
+    use crate::SyslogTransport;
+
+    use super::SyslogConnection;
+
+    #[tokio::test]
+    async fn test_udp_sends_one_datagram_per_message() {
+        let listener = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let transport = SyslogTransport::Udp(listener.local_addr().unwrap().to_string());
+
+        let mut connection = SyslogConnection::connect(&transport, Duration::from_secs(1))
+            .await
+            .unwrap();
+        connection.send(b"<14>1 message").await.unwrap();
+
+        let mut buffer = [0u8; 64];
+        let len = listener.recv(&mut buffer).await.unwrap();
+        assert_eq!(b"<14>1 message", &buffer[..len]);
+    }
+
+    #[tokio::test]
+    async fn test_tcp_uses_octet_counting() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let transport = SyslogTransport::Tcp(listener.local_addr().unwrap().to_string());
+
+        let mut connection = SyslogConnection::connect(&transport, Duration::from_secs(1))
+            .await
+            .unwrap();
+        let (mut stream, _) = listener.accept().await.unwrap();
+
+        connection.send(b"<14>1 a").await.unwrap();
+        connection.send(b"<14>1 bc").await.unwrap();
+        connection.flush().await.unwrap();
+        drop(connection);
+
+        let mut result = String::new();
+        stream.read_to_string(&mut result).await.unwrap();
+        assert_eq!("7 <14>1 a8 <14>1 bc", result);
+    }
+}
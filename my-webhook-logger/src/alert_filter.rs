@@ -0,0 +1,62 @@
+use my_logger_core::{LogLevel, MyLogEvent};
+
+/// Decides which events raise an alert: the level must be one of `levels`
+/// and the process must match one of `processes` if any is set.
+/// Process patterns are exact names or prefixes ending with `*`.
+pub struct AlertFilter {
+    levels: Vec<LogLevel>,
+    processes: Vec<String>,
+}
+
+impl AlertFilter {
+    pub fn new(levels: Vec<LogLevel>) -> Self {
+        Self {
+            levels,
+            processes: Vec::new(),
+        }
+    }
+
+    pub fn with_processes(mut self, processes: Vec<String>) -> Self {
+        self.processes = processes;
+        self
+    }
+
+    pub fn matches(&self, log_event: &MyLogEvent) -> bool {
+        if !self.levels.contains(&log_event.level) {
+            return false;
+        }
+
+        if self.processes.is_empty() {
+            return true;
+        }
+
+        self.processes
+            .iter()
+            .any(|pattern| match pattern.strip_suffix('*') {
+                Some(prefix) => log_event.process.starts_with(prefix),
+                None => log_event.process == *pattern,
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use my_logger_core::{test_utils::create_test_event, LogLevel, MyLogEvent};
+
+    use super::AlertFilter;
+
+    fn create_event(level: LogLevel, process: &str) -> MyLogEvent {
+        create_test_event(level, process, "Test")
+    }
+
+    #[test]
+    fn test_filter() {
+        let filter = AlertFilter::new(vec![LogLevel::FatalError])
+            .with_processes(vec!["Orders*".to_string(), "Payments".to_string()]);
+
+        assert!(filter.matches(&create_event(LogLevel::FatalError, "Orders::sync")));
+        assert!(filter.matches(&create_event(LogLevel::FatalError, "Payments")));
+        assert!(!filter.matches(&create_event(LogLevel::FatalError, "Payments::refund")));
+        assert!(!filter.matches(&create_event(LogLevel::Error, "Orders")));
+    }
+}
@@ -0,0 +1,130 @@
+use std::fmt::Write;
+
+use my_json::json_writer::JsonObjectWriter;
+use my_logger_core::PopulatedParams;
+use my_seq_logger::format_seq_string;
+
+use crate::{Alert, WebhookFormat};
+
+/// Human readable alert: header line, message, context and the repeat count.
+pub fn format_alert_text(alert: &Alert, populated_params: &PopulatedParams) -> String {
+    let log_event = alert.log_event.as_ref();
+    let mut result = String::new();
+
+    let _ = write!(result, "[{}]", log_event.level.as_str());
+    if let Some(app) = populated_params.get("Application") {
+        let _ = write!(result, " {}", app);
+    }
+    let _ = writeln!(result, " {}", log_event.process);
+    result.push_str(log_event.message.as_str());
+
+    if let Some(ctx) = &log_event.context {
+        let mut keys: Vec<&String> = ctx.keys().collect();
+        keys.sort();
+        for key in keys {
+            let _ = write!(result, "\n{}: {}", key, ctx[key]);
+        }
+    }
+
+    if alert.count > 1 {
+        let _ = write!(result, "\nRepeated {} times", alert.count);
+    }
+
+    result
+}
+
+pub fn serialize_alert(
+    format: &WebhookFormat,
+    alert: &Alert,
+    populated_params: &PopulatedParams,
+) -> String {
+    let text = format_alert_text(alert, populated_params);
+
+    match format {
+        WebhookFormat::Slack | WebhookFormat::Teams => JsonObjectWriter::from_string(String::new())
+            .write("text", format_seq_string(text.as_str()).as_str())
+            .build(),
+        WebhookFormat::Telegram { chat_id } => JsonObjectWriter::from_string(String::new())
+            .write("chat_id", format_seq_string(chat_id.as_str()).as_str())
+            .write("text", format_seq_string(text.as_str()).as_str())
+            .build(),
+        WebhookFormat::Template(template) => {
+            let log_event = alert.log_event.as_ref();
+            let count = alert.count.to_string();
+
+            let placeholders = [
+                ("{text}", text.as_str()),
+                ("{level}", log_event.level.as_str()),
+                ("{process}", log_event.process.as_str()),
+                ("{message}", log_event.message.as_str()),
+                ("{count}", count.as_str()),
+                (
+                    "{app}",
+                    populated_params.get("Application").unwrap_or_default(),
+                ),
+            ];
+
+            let mut result = template.to_string();
+            for (placeholder, value) in placeholders {
+                if result.contains(placeholder) {
+                    result = result.replace(placeholder, escape_json_string(value).as_str());
+                }
+            }
+
+            result
+        }
+    }
+}
+
+/// Escapes a value substituted inside a JSON string literal of a template.
+fn escape_json_string(src: &str) -> String {
+    let mut result = String::with_capacity(src.len());
+    for c in src.chars() {
+        match c {
+            '"' => result.push_str("\\\""),
+            '\\' => result.push_str("\\\\"),
+            '\n' => result.push_str("\\n"),
+            '\r' => result.push_str("\\r"),
+            '\t' => result.push_str("\\t"),
+            c if (c as u32) < 32 => {
+                let _ = write!(result, "\\u{:04x}", c as u32);
+            }
+            _ => result.push(c),
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use my_logger_core::{test_utils::create_test_event, LogLevel, PopulatedParams};
+
+    use crate::{Alert, WebhookFormat};
+
+    #[test]
+    fn test_template() {
+        let alert = Alert {
+            log_event: Arc::new(create_test_event(
+                LogLevel::FatalError,
+                "Orders",
+                "Panic \"x\"",
+            )),
+            count: 3,
+        };
+
+        let result = super::serialize_alert(
+            &WebhookFormat::Template(
+                "{\"title\":\"{app}/{process}\",\"body\":\"{message}\",\"n\":{count}}".to_string(),
+            ),
+            &alert,
+            &PopulatedParams::new(vec![("Application", "oms".into())]),
+        );
+
+        assert_eq!(
+            "{\"title\":\"oms/Orders\",\"body\":\"Panic \\\"x\\\"\",\"n\":3}",
+            result
+        );
+    }
+}
@@ -0,0 +1,163 @@
+use std::{
+    collections::HashMap,
+    hash::{DefaultHasher, Hash, Hasher},
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use my_logger_core::MyLogEvent;
+
+pub struct Alert {
+    pub log_event: Arc<MyLogEvent>,
+    /// Events with the same fingerprint since the previous alert, including this one.
+    pub count: usize,
+}
+
+struct ThrottleWindow {
+    started: Instant,
+    suppressed: usize,
+    last_event: Arc<MyLogEvent>,
+}
+
+/// The first event of a fingerprint is alerted immediately. Repeats within `period` are counted
+/// and reported as one alert when the period is over, so a crash loop sends one alert per period.
+pub struct AlertThrottler {
+    period: Duration,
+    windows: HashMap<u64, ThrottleWindow>,
+}
+
+impl AlertThrottler {
+    pub fn new(period: Duration) -> Self {
+        Self {
+            period,
+            windows: HashMap::new(),
+        }
+    }
+
+    pub fn register(&mut self, log_event: Arc<MyLogEvent>, now: Instant) -> Option<Alert> {
+        let fingerprint = get_fingerprint(&log_event);
+
+        match self.windows.get_mut(&fingerprint) {
+            Some(window) if now.duration_since(window.started) < self.period => {
+                window.suppressed += 1;
+                window.last_event = log_event;
+                None
+            }
+            Some(window) => {
+                let count = window.suppressed + 1;
+                window.started = now;
+                window.suppressed = 0;
+                window.last_event = log_event.clone();
+                Some(Alert { log_event, count })
+            }
+            None => {
+                self.windows.insert(
+                    fingerprint,
+                    ThrottleWindow {
+                        started: now,
+                        suppressed: 0,
+                        last_event: log_event.clone(),
+                    },
+                );
+                Some(Alert {
+                    log_event,
+                    count: 1,
+                })
+            }
+        }
+    }
+
+    /// Reports repeats of windows which are over. Windows without repeats are forgotten.
+    pub fn take_expired(&mut self, now: Instant) -> Vec<Alert> {
+        let mut result = Vec::new();
+        let period = self.period;
+
+        self.windows.retain(|_, window| {
+            if now.duration_since(window.started) < period {
+                return true;
+            }
+
+            if window.suppressed == 0 {
+                return false;
+            }
+
+            result.push(Alert {
+                log_event: window.last_event.clone(),
+                count: window.suppressed,
+            });
+
+            window.started = now;
+            window.suppressed = 0;
+            true
+        });
+
+        result
+    }
+}
+
+/// Level, process and message with digits masked, so ids and timings do not split a crash loop
+/// into different alerts.
+pub fn get_fingerprint(log_event: &MyLogEvent) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    log_event.level.to_u8().hash(&mut hasher);
+    log_event.process.hash(&mut hasher);
+
+    for c in log_event.message.chars() {
+        if c.is_ascii_digit() {
+            '#'.hash(&mut hasher);
+        } else {
+            c.hash(&mut hasher);
+        }
+    }
+
+    hasher.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        sync::Arc,
+        time::{Duration, Instant},
+    };
+
+    use my_logger_core::{test_utils::create_test_event, LogLevel, MyLogEvent};
+
+    use super::AlertThrottler;
+
+    fn create_event(message: &str) -> Arc<MyLogEvent> {
+        Arc::new(create_test_event(LogLevel::FatalError, "Orders", message))
+    }
+
+    #[test]
+    fn test_crash_loop_is_throttled() {
+        let mut throttler = AlertThrottler::new(Duration::from_secs(60));
+        let now = Instant::now();
+
+        let alert = throttler.register(create_event("Panic at 1"), now).unwrap();
+        assert_eq!(1, alert.count);
+
+        assert!(throttler
+            .register(create_event("Panic at 2"), now + Duration::from_secs(1))
+            .is_none());
+        assert!(throttler
+            .register(create_event("Panic at 3"), now + Duration::from_secs(2))
+            .is_none());
+        assert!(throttler
+            .register(create_event("Other"), now + Duration::from_secs(2))
+            .is_some());
+
+        assert!(throttler
+            .take_expired(now + Duration::from_secs(30))
+            .is_empty());
+
+        let expired = throttler.take_expired(now + Duration::from_secs(61));
+        assert_eq!(1, expired.len());
+        assert_eq!(2, expired[0].count);
+        assert_eq!("Panic at 3", expired[0].log_event.message);
+
+        let alert = throttler
+            .register(create_event("Panic at 4"), now + Duration::from_secs(122))
+            .unwrap();
+        assert_eq!(1, alert.count);
+    }
+}
@@ -0,0 +1,14 @@
+mod webhook_logger;
+pub use webhook_logger::WebhookLogger;
+mod webhook_logger_inner;
+pub use webhook_logger_inner::*;
+mod settings;
+pub use settings::*;
+mod alert_filter;
+pub use alert_filter::*;
+mod alert_throttler;
+pub use alert_throttler::*;
+mod alert_message;
+pub use alert_message::*;
+mod uploader;
+pub use uploader::*;
@@ -0,0 +1,154 @@
+use std::time::Duration;
+
+use my_logger_core::LogLevel;
+use my_seq_logger::{parse_number, split_key_value};
+
+use crate::AlertFilter;
+
+const DEFAULT_THROTTLE_MINUTES: u64 = 10;
+const DEFAULT_TIMEOUT: u64 = 10;
+const DEFAULT_FLUSH_DELAY: u64 = 1;
+
+#[derive(Debug, Clone)]
+pub enum WebhookFormat {
+    /// `{"text": ...}`
+    Slack,
+    /// `{"text": ...}` of an incoming webhook connector.
+    Teams,
+    /// `{"chat_id": ..., "text": ...}` of the Bot API `sendMessage` method.
+    Telegram { chat_id: String },
+    /// JSON body with `{text}`, `{level}`, `{process}`, `{message}`, `{count}` and `{app}` placeholders.
+    /// Values are JSON-escaped, so placeholders are expected inside string literals.
+    Template(String),
+}
+
+pub struct WebhookSettings {
+    pub url: String,
+    pub format: WebhookFormat,
+    pub filter: AlertFilter,
+    /// Events with the same fingerprint produce one alert per period with a repeat count.
+    pub throttle_period: Duration,
+    /// Interval at which queued events are turned into alerts and expired throttle periods are reported.
+    pub flush_delay: Duration,
+    pub timeout: Duration,
+}
+
+impl WebhookSettings {
+    pub fn new(url: String, format: WebhookFormat) -> Self {
+        Self {
+            url,
+            format,
+            filter: AlertFilter::new(vec![LogLevel::FatalError]),
+            throttle_period: Duration::from_secs(DEFAULT_THROTTLE_MINUTES * 60),
+            flush_delay: Duration::from_secs(DEFAULT_FLUSH_DELAY),
+            timeout: Duration::from_secs(DEFAULT_TIMEOUT),
+        }
+    }
+
+    /// `url=https://hooks.slack.com/services/xxx;format=slack;levels=FatalError,Error;processes=Orders*;throttle=10`
+    ///
+    /// Telegram requires `chatid`. Custom templates can be set with [`WebhookFormat::Template`] only.
+    pub fn try_parse(conn_string: &str) -> Result<Self, String> {
+        let mut url = None;
+        let mut format = None;
+        let mut chat_id = None;
+        let mut levels = vec![LogLevel::FatalError];
+        let mut processes = Vec::new();
+        let mut throttle = DEFAULT_THROTTLE_MINUTES;
+        let mut flush_delay = DEFAULT_FLUSH_DELAY;
+        let mut timeout = DEFAULT_TIMEOUT;
+
+        for item in conn_string.split(';') {
+            if item.is_empty() {
+                continue;
+            }
+
+            let (key, value) = split_key_value(item, "webhook connection string")?;
+
+            match key {
+                "url" => {
+                    url = Some(value.to_string());
+                }
+                "format" => {
+                    format = Some(value.to_string());
+                }
+                "chatid" => {
+                    chat_id = Some(value.to_string());
+                }
+                "levels" => {
+                    levels = split_list(value)
+                        .map(|itm| itm.parse::<LogLevel>())
+                        .collect::<Result<Vec<_>, _>>()?;
+                }
+                "processes" => {
+                    processes = split_list(value).map(|itm| itm.to_string()).collect();
+                }
+                "throttle" => {
+                    throttle = parse_number(key, value)?;
+                }
+                "flushdelay" => {
+                    flush_delay = parse_number(key, value)?;
+                }
+                "timeout" => {
+                    timeout = parse_number(key, value)?;
+                }
+                _ => {
+                    return Err(format!("Invalid key {} of webhook connection string", key));
+                }
+            }
+        }
+
+        let url = match url {
+            Some(url) => url,
+            None => {
+                return Err("There is no URL parameter in webhook connection string".to_string())
+            }
+        };
+
+        let format = match format.as_deref() {
+            None | Some("slack") => WebhookFormat::Slack,
+            Some("teams") => WebhookFormat::Teams,
+            Some("telegram") => match chat_id {
+                Some(chat_id) => WebhookFormat::Telegram { chat_id },
+                None => return Err("Telegram webhook requires chatid parameter".to_string()),
+            },
+            Some(other) => return Err(format!("Invalid webhook format {}", other)),
+        };
+
+        Ok(Self {
+            url,
+            format,
+            filter: AlertFilter::new(levels).with_processes(processes),
+            throttle_period: Duration::from_secs(throttle * 60),
+            flush_delay: Duration::from_secs(flush_delay),
+            timeout: Duration::from_secs(timeout),
+        })
+    }
+}
+
+fn split_list(src: &str) -> impl Iterator<Item = &str> {
+    src.split(',')
+        .map(|itm| itm.trim())
+        .filter(|itm| !itm.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{WebhookFormat, WebhookSettings};
+
+    #[test]
+    fn test_parse_connection_string() {
+        let settings = WebhookSettings::try_parse(
+            "url=https://api.telegram.org/botxxx/sendMessage;format=telegram;chatid=-100;levels=fatalerror,Error;throttle=5",
+        )
+        .unwrap();
+
+        assert!(
+            matches!(settings.format, WebhookFormat::Telegram { chat_id } if chat_id == "-100")
+        );
+        assert_eq!(300, settings.throttle_period.as_secs());
+
+        assert!(WebhookSettings::try_parse("url=http://a;format=telegram").is_err());
+        assert!(WebhookSettings::try_parse("url=http://a;levels=Critical").is_err());
+    }
+}
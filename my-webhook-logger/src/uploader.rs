@@ -0,0 +1,88 @@
+use std::time::Duration;
+
+use flurl::{body::FlUrlBody, *};
+
+pub struct WebhookUploader {
+    pub url: String,
+    pub webhook_debug: bool,
+    pub timeout: Duration,
+}
+
+impl WebhookUploader {
+    pub fn new(url: String, timeout: Duration) -> Self {
+        Self {
+            url,
+            webhook_debug: std::env::var("WEBHOOK_DEBUG").is_ok(),
+            timeout,
+        }
+    }
+
+    pub async fn send(&self, body: String) {
+        const MAX_ATTEMPTS: u32 = 4;
+
+        if self.webhook_debug {
+            println!("Sending webhook alert: {}", body);
+        }
+
+        let mut attempt_no: u32 = 0;
+        let mut body = Some(body.into_bytes());
+        loop {
+            attempt_no += 1;
+
+            let data = if attempt_no == MAX_ATTEMPTS {
+                body.take().unwrap()
+            } else {
+                body.as_ref().unwrap().clone()
+            };
+
+            let response = FlUrl::new(self.url.as_str())
+                .set_timeout(self.timeout)
+                .with_header("Content-Type", "application/json")
+                .post(FlUrlBody::Raw {
+                    data,
+                    content_type: None,
+                })
+                .await;
+
+            match response {
+                Ok(mut response) => {
+                    let status_code = response.get_status_code();
+                    if self.webhook_debug {
+                        println!("Webhook result: {}", status_code);
+                    }
+
+                    if (200..300).contains(&status_code) {
+                        return;
+                    }
+
+                    // 4xx other than throttling means the body or the url is wrong and a retry won't help
+                    if status_code != 429 && (400..500).contains(&status_code) {
+                        let body = response.get_body_as_slice().await;
+                        eprintln!(
+                            "Webhook rejected alert. Status code: {}. Body: {:?}",
+                            status_code,
+                            body.map(|itm| String::from_utf8_lossy(itm).to_string())
+                        );
+                        return;
+                    }
+
+                    if attempt_no >= MAX_ATTEMPTS {
+                        return;
+                    }
+                }
+                Err(err) => {
+                    eprintln!(
+                        "Attempt: {} Error while sending webhook alert. Err: {:?}",
+                        attempt_no, err
+                    );
+
+                    if attempt_no >= MAX_ATTEMPTS {
+                        return;
+                    }
+                }
+            }
+
+            tokio::time::sleep(Duration::from_secs(1)).await;
+        }
+    }
+}
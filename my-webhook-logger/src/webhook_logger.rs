@@ -0,0 +1,114 @@
+use std::{sync::Arc, time::Duration};
+
+use crate::{WebhookLoggerInner, WebhookSettings};
+use my_logger_core::{MyLogEvent, MyLoggerReader};
+use rust_extensions::{events_loop::EventsLoop, AppStates};
+
+pub struct WebhookLogger {
+    inner: Arc<WebhookLoggerInner>,
+    events_loop: EventsLoop<()>,
+    app_states: Arc<AppStates>,
+}
+
+impl WebhookLogger {
+    /// Plugs the webhook alerts writer into `LOGGER`. Must be called inside a tokio runtime.
+    pub fn enable(settings: WebhookSettings) -> Arc<Self> {
+        let flush_delay = settings.flush_delay;
+        let timeout = settings.timeout;
+
+        let result = Self {
+            app_states: AppStates::create_initialized().into(),
+            inner: Arc::new(WebhookLoggerInner::new(settings)),
+            events_loop: EventsLoop::new("WebhookLogger".to_string())
+                .set_iteration_timeout(timeout),
+        };
+
+        result.events_loop.register_event_loop(result.inner.clone());
+
+        result
+            .events_loop
+            .start(result.app_states.clone(), my_logger_core::LOGGER.clone());
+
+        let webhook_logger = Arc::new(result);
+        my_seq_logger::start_flush_timer(Arc::downgrade(&webhook_logger), flush_delay, |logger| {
+            logger.events_loop.send(())
+        });
+        my_logger_core::LOGGER.plug_reader(webhook_logger.clone());
+        webhook_logger
+    }
+
+    pub fn enable_from_connection_string(conn_string: &str) -> Arc<Self> {
+        match WebhookSettings::try_parse(conn_string) {
+            Ok(settings) => Self::enable(settings),
+            Err(err) => panic!("Can not parse webhook settings. Err: {}", err),
+        }
+    }
+}
+
+impl MyLoggerReader for WebhookLogger {
+    fn write_log(&self, log_event: Arc<MyLogEvent>) {
+        if !self.inner.settings.filter.matches(&log_event) {
+            return;
+        }
+
+        self.inner.log_events.enqueue(log_event);
+    }
+
+    fn flush(&self, timeout: Duration) {
+        let inner = self.inner.clone();
+        my_seq_logger::flush_on_private_runtime("Webhook", timeout, async move {
+            inner.send_alerts().await
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{sync::Arc, time::Duration};
+
+    use my_logger_core::{test_utils::create_test_event, LogLevel, MyLoggerReader};
+    use my_seq_logger::LogsChunkUploader;
+    use parking_lot::Mutex;
+    use rust_extensions::{events_loop::EventsLoop, AppStates};
+
+    use crate::{WebhookLoggerInner, WebhookSettings};
+
+    use super::WebhookLogger;
+
+    #[derive(Default)]
+    struct MockUploader {
+        chunks: Mutex<Vec<String>>,
+    }
+
+    #[async_trait::async_trait]
+    impl LogsChunkUploader for MockUploader {
+        async fn upload_chunk(&self, chunk: Vec<u8>) {
+            self.chunks.lock().push(String::from_utf8(chunk).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_flush_sends_alerts() {
+        let uploader = Arc::new(MockUploader::default());
+        let mut inner =
+            WebhookLoggerInner::new(WebhookSettings::try_parse("url=http://localhost").unwrap());
+        inner.custom_uploader = Some(uploader.clone());
+
+        let webhook_logger = WebhookLogger {
+            inner: Arc::new(inner),
+            events_loop: EventsLoop::new("WebhookLogger".to_string()),
+            app_states: AppStates::create_initialized().into(),
+        };
+
+        webhook_logger.write_log(Arc::new(create_test_event(
+            LogLevel::FatalError,
+            "Orders",
+            "Payment failed",
+        )));
+        webhook_logger.flush(Duration::from_secs(5));
+
+        let chunks = uploader.chunks.lock();
+        assert_eq!(1, chunks.len());
+        assert!(chunks[0].contains("Payment failed"));
+    }
+}
@@ -0,0 +1,78 @@
+use std::{sync::Arc, time::Instant};
+
+use my_seq_logger::{LogEventsQueue, LogsChunkUploader};
+use parking_lot::Mutex;
+use rust_extensions::events_loop::EventsLoopTick;
+
+use crate::{AlertThrottler, WebhookSettings, WebhookUploader};
+
+pub struct WebhookLoggerInner {
+    pub(crate) log_events: LogEventsQueue,
+    pub(crate) settings: WebhookSettings,
+    throttler: Mutex<AlertThrottler>,
+    uploader: WebhookUploader,
+    /// Replaces the HTTP uploader built from settings.
+    pub(crate) custom_uploader: Option<Arc<dyn LogsChunkUploader + Send + Sync + 'static>>,
+    /// Held while alerts are sent, so a flush waits for the alerts which are already in flight.
+    send_lock: tokio::sync::Mutex<()>,
+}
+
+impl WebhookLoggerInner {
+    pub fn new(settings: WebhookSettings) -> Self {
+        Self {
+            log_events: LogEventsQueue::new(),
+            throttler: Mutex::new(AlertThrottler::new(settings.throttle_period)),
+            uploader: WebhookUploader::new(settings.url.clone(), settings.timeout),
+            custom_uploader: None,
+            send_lock: tokio::sync::Mutex::new(()),
+            settings,
+        }
+    }
+
+    /// Sends alerts for the queued events and for the throttle periods which are over.
+    pub async fn send_alerts(&self) {
+        let _send_lock = self.send_lock.lock().await;
+
+        let now = Instant::now();
+
+        let alerts = {
+            let mut throttler = self.throttler.lock();
+            let mut alerts = throttler.take_expired(now);
+
+            if let Some(events) = self.log_events.dequeue() {
+                for log_event in events {
+                    if let Some(alert) = throttler.register(log_event, now) {
+                        alerts.push(alert);
+                    }
+                }
+            }
+
+            alerts
+        };
+
+        if alerts.is_empty() {
+            return;
+        }
+
+        let populated_params = my_logger_core::LOGGER.get_populated_params();
+
+        for alert in alerts {
+            let body = crate::serialize_alert(&self.settings.format, &alert, &populated_params);
+            match self.custom_uploader.as_ref() {
+                Some(custom_uploader) => custom_uploader.upload_chunk(body.into_bytes()).await,
+                None => self.uploader.send(body).await,
+            }
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl EventsLoopTick<()> for WebhookLoggerInner {
+    async fn started(&self) {
+        println!("Webhook alerts writer is started");
+    }
+    async fn tick(&self, _: ()) {
+        self.send_alerts().await;
+    }
+    async fn finished(&self) {}
+}